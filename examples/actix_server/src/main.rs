@@ -0,0 +1,75 @@
+use actix_web::{get, App, HttpResponse, HttpServer};
+use polars::df;
+use polars::prelude::*;
+use polars_styler::actix::StyledTable;
+use polars_styler::colors::Color;
+use polars_styler::styler::StylerExt;
+
+fn data() -> DataFrame {
+    df!(
+        "Fruit" => &["Apple", "Apple", "Pear"],
+        "Color" => &["Red", "Yellow", "Green"],
+        "Price" => &[1.23, 2.34, 3.45],
+    )
+    .unwrap()
+}
+
+/// The table alone, e.g. for a dashboard that swaps it in with htmx.
+#[get("/table")]
+async fn table() -> StyledTable {
+    data()
+        .style()
+        .background_gradient("Price", &Color::new(230, 30, 40), &None, &None)
+        .set_table_classes(vec!["table".to_string(), "table-hover".to_string()])
+        .into()
+}
+
+#[get("/")]
+async fn index() -> HttpResponse {
+    let html = data()
+        .style()
+        .background_gradient("Price", &Color::new(230, 30, 40), &None, &None)
+        .set_table_classes(vec![
+            "table".to_string(),
+            "table-hover".to_string(),
+            "table-bordered".to_string(),
+        ])
+        .render();
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n  <meta charset=\"UTF-8\">\n  \
+         <title>Examples | polars-styler</title>\n</head>\n<body>\n  <h1>Examples</h1>\n  \
+         <h2>Background gradient</h2>\n  {}\n</body>\n</html>",
+            html
+        ))
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    HttpServer::new(|| App::new().service(index).service(table))
+        .bind(("127.0.0.1", 8080))?
+        .run()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::header::CONTENT_TYPE;
+    use actix_web::test;
+
+    #[actix_web::test]
+    async fn test_table() {
+        let app = test::init_service(App::new().service(index).service(table)).await;
+        for uri in ["/", "/table"] {
+            let response =
+                test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+            assert!(response.status().is_success());
+            assert_eq!(
+                response.headers().get(CONTENT_TYPE).unwrap(),
+                "text/html; charset=utf-8"
+            );
+        }
+    }
+}
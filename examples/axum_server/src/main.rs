@@ -0,0 +1,73 @@
+use axum::response::Html;
+use axum::routing::get;
+use axum::Router;
+use polars::df;
+use polars::prelude::*;
+use polars_styler::axum::StyledTable;
+use polars_styler::colors::Color;
+use polars_styler::styler::StylerExt;
+
+fn data() -> DataFrame {
+    df!(
+        "Fruit" => &["Apple", "Apple", "Pear"],
+        "Color" => &["Red", "Yellow", "Green"],
+        "Price" => &[1.23, 2.34, 3.45],
+    )
+    .unwrap()
+}
+
+/// The table alone, e.g. for a dashboard that swaps it in with htmx.
+async fn table() -> StyledTable {
+    data()
+        .style()
+        .background_gradient("Price", &Color::new(230, 30, 40), &None, &None)
+        .set_table_classes(vec!["table".to_string(), "table-hover".to_string()])
+        .into()
+}
+
+async fn index() -> Html<String> {
+    let table = data()
+        .style()
+        .background_gradient("Price", &Color::new(230, 30, 40), &None, &None)
+        .render();
+    Html(format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n  <meta charset=\"UTF-8\">\n  \
+         <title>Examples | polars-styler</title>\n</head>\n<body>\n  <h1>Examples</h1>\n  \
+         {}\n</body>\n</html>",
+        table
+    ))
+}
+
+fn app() -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/table", get(table))
+}
+
+#[tokio::main]
+async fn main() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    axum::serve(listener, app()).await.unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::header::CONTENT_TYPE;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_table() {
+        let request = Request::builder()
+            .uri("/table")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+    }
+}
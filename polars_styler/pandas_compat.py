@@ -0,0 +1,144 @@
+"""pandas `Styler` method names and signatures on top of polars-styler, so existing pandas
+styling code can be ported with minimal changes:
+
+    from polars_styler.pandas_compat import pandas_style
+    pandas_style(df).highlight_max(color="lightgreen").format("{:.2f}", subset=["b"])
+"""
+from typing import Callable
+
+import polars as pl
+
+from polars_styler.styler import ColorMap, Styler, style
+
+
+def pandas_style(df: pl.DataFrame) -> "PandasStyler":
+    return PandasStyler(style(df), df)
+
+
+class PandasStyler:
+
+    def __init__(self, styler: Styler, df: pl.DataFrame):
+        self._styler = styler
+        self._df = df
+        self._table_styles = []
+
+    def _subset(self, subset, numeric: bool = False) -> list[str]:
+        if subset is None:
+            subset = [c for c, t in self._df.schema.items() if not numeric or t.is_numeric()]
+        elif isinstance(subset, str):
+            subset = [subset]
+        return list(subset)
+
+    @staticmethod
+    def _check_axis(axis):
+        if axis not in (0, "index", "rows"):
+            raise NotImplementedError("only axis=0 (per column) is supported")
+
+    def highlight_max(self, subset=None, color: str = "yellow", axis=0, props=None):
+        self._check_axis(axis)
+        for column in self._subset(subset, numeric=True):
+            value = self._df[column].max()
+            if value is not None:
+                self._styler.highlight_between(column, value, value, color)
+        return self
+
+    def highlight_min(self, subset=None, color: str = "yellow", axis=0, props=None):
+        self._check_axis(axis)
+        for column in self._subset(subset, numeric=True):
+            value = self._df[column].min()
+            if value is not None:
+                self._styler.highlight_between(column, value, value, color)
+        return self
+
+    def highlight_between(self, subset=None, color: str = "yellow", axis=0, left=None, right=None,
+                          inclusive="both", props=None):
+        self._check_axis(axis)
+        if inclusive != "both":
+            raise NotImplementedError("only inclusive='both' is supported")
+        left = float("-inf") if left is None else left
+        right = float("inf") if right is None else right
+        for column in self._subset(subset, numeric=True):
+            self._styler.highlight_between(column, left, right, color)
+        return self
+
+    def highlight_null(self, color: str = "red", subset=None, props=None):
+        for column in self._subset(subset):
+            self._styler.highlight_null(column, color)
+        return self
+
+    def format(self, formatter: "str | Callable | dict | None" = None, subset=None,
+               na_rep: str = None, precision: int = None):
+        formatters = formatter if isinstance(formatter, dict) else {
+            column: formatter for column in self._subset(subset)
+        }
+        for column, f in formatters.items():
+            if f is None:
+                self._styler.format(column, precision=precision, na_rep=na_rep)
+                continue
+            self._styler.format(column, self._with_na_rep(f, na_rep))
+        return self
+
+    @staticmethod
+    def _with_na_rep(formatter, na_rep):
+        if isinstance(formatter, str):
+            template = formatter if "{" in formatter else "{:" + formatter + "}"
+            formatter = template.format
+        na_rep = "null" if na_rep is None else na_rep
+        return lambda v: na_rep if v is None else formatter(v)
+
+    def set_caption(self, caption: str):
+        self._styler.set_caption(caption)
+        return self
+
+    def set_table_styles(self, table_styles: list[dict], overwrite: bool = True):
+        """Entries such as `{"selector": "th", "props": [("color", "red")]}`; `props` may also
+        be a CSS string. Dict-of-columns styles are not supported."""
+        if isinstance(table_styles, dict):
+            raise NotImplementedError("per-column table styles are not supported")
+        styles = [] if overwrite else self._table_styles
+        for style in table_styles:
+            props = style["props"]
+            if not isinstance(props, str):
+                props = "; ".join(f"{k}: {v}" for k, v in props)
+            styles.append((style["selector"], props))
+        self._table_styles = styles
+        self._styler.set_table_styles(styles)
+        return self
+
+    def set_table_attributes(self, attributes: str):
+        raise NotImplementedError("use set_table_classes on the polars-styler Styler")
+
+    def hide(self, subset=None, axis=0, level=None, names: bool = False):
+        if axis in (0, "index", "rows"):
+            # polars frames have no index to hide
+            if subset is not None:
+                raise NotImplementedError("hiding rows is not supported")
+            return self
+        self._styler.hide_columns(self._subset(subset))
+        return self
+
+    def bar(self, subset=None, axis=0, color: str = None, vmin: float = None, vmax: float = None,
+            **kwargs):
+        self._check_axis(axis)
+        self._styler.bar(subset=self._subset(subset, numeric=True), color=color, vmin=vmin,
+                         vmax=vmax)
+        return self
+
+    def background_gradient(self, cmap: "str | ColorMap" = "PuBu", low: float = 0, high: float = 0,
+                            axis=0, subset=None, text_color_threshold: float = 0.408,
+                            vmin: float = None, vmax: float = None, **kwargs):
+        if axis is None:
+            self._styler.background_gradient_shared(self._subset(subset, numeric=True), cmap=cmap,
+                                                    vmin=vmin, vmax=vmax)
+            return self
+        self._check_axis(axis)
+        self._styler.background_gradient(cmap=cmap, subset=self._subset(subset, numeric=True),
+                                         vmin=vmin, vmax=vmax,
+                                         text_color_threshold=text_color_threshold)
+        return self
+
+    def to_html(self, buf=None, doctype_html: bool = False, **kwargs):
+        return self._styler.to_html(buf, doctype_html=doctype_html)
+
+    def _repr_html_(self):
+        return self._styler._repr_html_()
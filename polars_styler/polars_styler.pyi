@@ -0,0 +1,130 @@
+# Type stubs for the compiled extension module (src/python.rs). Keep in sync with the
+# `#[pymethods]` there; py_tests/test_stubs.py checks that every method is covered.
+from typing import Any, Callable
+
+import polars as pl
+
+class PyStyler:
+    def __init__(self, df: pl.DataFrame) -> None: ...
+    def set_table_classes(self, classes: list[str]) -> None: ...
+    def add_table_classes(self, classes: list[str]) -> None: ...
+    def set_labels(self, labels: list[str]) -> None: ...
+    def relabel_column(self, column: str, label: str) -> None: ...
+    def relabel(self, mapping: dict[str, str]) -> None: ...
+    def set_precision(self, precision: int) -> None: ...
+    def set_column_precision(self, column: str, precision: int) -> None: ...
+    def set_caption(self, caption: str) -> None: ...
+    def hide_columns(self, columns: list[str]) -> None: ...
+    def set_table_styles(self, styles: list[tuple[str, str]]) -> None: ...
+    def highlight_between(self, column: str, left: float, right: float, color: PyColor) -> None: ...
+    def highlight_null(self, column: str, color: PyColor) -> None: ...
+    def heatmap_symmetric(self, cmap: PyColorMap, vmin: float, vmax: float, mask_diagonal: bool) -> None: ...
+    def diff(self, old: pl.DataFrame, tooltips: bool) -> None: ...
+    def set_details(self, key: str, detail: PyStyler, collapsible: bool) -> None: ...
+    def icon_set(self, column: str, icons: str, thresholds: list[float] | None) -> None: ...
+    def emphasize_series(self, column: str, mask: pl.Series, style: str) -> None: ...
+    def set_text(
+        self,
+        subset: list[str],
+        mask: pl.Series | None = None,
+        weight: str | None = None,
+        style: str | None = None,
+        decoration: str | None = None,
+        color: PyColor | None = None,
+        family: str | None = None,
+    ) -> None: ...
+    def highlight_rows_at(self, rows: list[int], color: PyColor) -> None: ...
+    def highlight_rows_where_key(self, column: str, values: list[str], color: PyColor) -> None: ...
+    def format_percent_of_total(self, column: str, with_value: bool) -> None: ...
+    def show_row_numbers(self, start: int) -> None: ...
+    def add_summary_row(self, label: str, aggregation: str, columns: list[str], sticky: bool) -> None: ...
+    def add_column_separator(self, after_column: str, width: int, color: PyColor, style: str) -> None: ...
+    def expandable_text(self, column: str, max_chars: int) -> None: ...
+    def clip_column(self, column: str, max_width: str) -> None: ...
+    def set_column_meta(
+        self, column: str, unit: str | None = None, description: str | None = None, unit_in_values: bool = False
+    ) -> None: ...
+    def border_series(
+        self, subset: list[str], mask: pl.Series, width: int, color: PyColor, style: str
+    ) -> None: ...
+    def map_icons(self, column: str, icons: dict[str, str]) -> None: ...
+    def trend_arrows(self, column: str, compare: str) -> None: ...
+    def trend_arrows_series(self, column: str, compare: pl.Series) -> None: ...
+    def background_gradient(
+        self,
+        cmap: PyColorMap | None = None,
+        subset: list[str] | None = None,
+        vmin: float | None = None,
+        vmax: float | None = None,
+        text_color_threshold: float | None = None,
+        opacity: float | None = None,
+        color: PyColor | None = None,
+    ) -> None: ...
+    def background_gradient_shared(
+        self,
+        subset: list[str] | None = None,
+        cmap: PyColorMap | None = None,
+        vmin: float | None = None,
+        vmax: float | None = None,
+    ) -> None: ...
+    def bar(
+        self,
+        cmap: PyColorMap | None = None,
+        subset: list[str] | None = None,
+        vmin: float | None = None,
+        vmax: float | None = None,
+        color: PyColor | None = None,
+    ) -> None: ...
+    def data(self) -> pl.DataFrame: ...
+    def background_gradient_series(self, series: pl.Series, color: PyColor | None = None) -> None: ...
+    def format(self, column: str, formatter: Callable[[Any], str]) -> None: ...
+    def set_format(
+        self,
+        subset: list[str] | None = None,
+        precision: int | None = None,
+        thousands: str | None = None,
+        decimal: str | None = None,
+        na_rep: str | None = None,
+        datetime: str | None = None,
+        prefix: str | None = None,
+        suffix: str | None = None,
+        percent: bool = False,
+    ) -> None: ...
+    def __getnewargs__(self) -> tuple[pl.DataFrame]: ...
+    def __getstate__(self) -> str: ...
+    def __setstate__(self, state: str) -> None: ...
+    def merge(self, other: PyStyler) -> None: ...
+    def add_computed_series(self, s: pl.Series, position: int | None) -> None: ...
+    def add_rank_column(self, by_column: str, descending: bool, medals: bool) -> None: ...
+    def __copy__(self) -> PyStyler: ...
+    def __deepcopy__(self, memo: Any) -> PyStyler: ...
+    def describe(self) -> str: ...
+    def render(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+
+class PyColor:
+    def __init__(self, color: str) -> None: ...
+    @staticmethod
+    def from_rgb(r: int, g: int, b: int) -> PyColor: ...
+    def to_hex(self) -> str: ...
+    def to_rgb(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+
+class PyColorMap:
+    def __init__(self, points: list[tuple[float, PyColor]]) -> None: ...
+    @staticmethod
+    def from_palette(colors: list[PyColor]) -> PyColorMap: ...
+    @staticmethod
+    def red_scale() -> PyColorMap: ...
+    @staticmethod
+    def three_point(low: PyColor, mid: PyColor, high: PyColor) -> PyColorMap: ...
+    def palette(self) -> list[PyColor]: ...
+    @staticmethod
+    def named(name: str) -> PyColorMap: ...
+    @staticmethod
+    def names() -> list[str]: ...
+    def reversed(self) -> PyColorMap: ...
+    def resampled(self, n: int) -> PyColorMap: ...
+    def is_colorblind_safe(self) -> bool: ...
+
+def pydf_to_pystyler(df: pl.DataFrame) -> PyStyler: ...
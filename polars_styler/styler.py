@@ -1,9 +1,113 @@
-from polars_styler.polars_styler import pydf_to_pystyler
+import copy
+import os
+import pathlib
+import tempfile
+import webbrowser
+from typing import Callable
+
+from polars_styler.polars_styler import PyColor, PyColorMap, pydf_to_pystyler
 import polars as pl
 
 
+class Color:
+
+    def __init__(self, color: "str | Color"):
+        """Hex, rgb(), hsl() or CSS named color such as "steelblue"."""
+        self._color = color._color if isinstance(color, Color) else PyColor(color)
+
+    @staticmethod
+    def from_rgb(r: int, g: int, b: int) -> "Color":
+        return Color._wrap(PyColor.from_rgb(r, g, b))
+
+    @staticmethod
+    def _wrap(py_color: PyColor) -> "Color":
+        color = Color.__new__(Color)
+        color._color = py_color
+        return color
+
+    def to_hex(self) -> str:
+        return self._color.to_hex()
+
+    def to_rgb(self) -> str:
+        return self._color.to_rgb()
+
+    def __repr__(self) -> str:
+        return repr(self._color)
+
+    def __eq__(self, other) -> bool:
+        return isinstance(other, Color) and self.to_hex() == other.to_hex()
+
+    def __hash__(self) -> int:
+        return hash(self.to_hex())
+
+
+def _py_color(color: "str | Color | None"):
+    return Color(color)._color if color is not None else None
+
+
+def _py_cmap(cmap):
+    """`ColorMap`, built-in colormap name or matplotlib `Colormap` as a `PyColorMap`."""
+    if cmap is None:
+        return None
+    if isinstance(cmap, str):
+        cmap = ColorMap.named(cmap)
+    elif not isinstance(cmap, ColorMap):
+        cmap = ColorMap.from_matplotlib(cmap)
+    return cmap._cmap
+
+
 class ColorMap:
-    pass
+
+    def __init__(self, cmap: PyColorMap):
+        self._cmap = cmap
+
+    @staticmethod
+    def from_points(points: list[tuple[float, "str | Color"]]) -> "ColorMap":
+        """Break points with unique values in 0..1, in increasing order."""
+        return ColorMap(PyColorMap([(value, _py_color(c)) for value, c in points]))
+
+    @staticmethod
+    def from_palette(colors: list["str | Color"]) -> "ColorMap":
+        """Colors evenly spaced from the lowest to the highest value."""
+        return ColorMap(PyColorMap.from_palette([_py_color(c) for c in colors]))
+
+    @staticmethod
+    def from_matplotlib(cmap, n: int = 32) -> "ColorMap":
+        """Samples a matplotlib `Colormap` at `n` evenly spaced points."""
+        colors = []
+        for i in range(n):
+            r, g, b, _ = cmap(i / (n - 1) if n > 1 else 0.5)
+            colors.append(Color.from_rgb(round(r * 255), round(g * 255), round(b * 255)))
+        return ColorMap.from_palette(colors)
+
+    @staticmethod
+    def red_scale() -> "ColorMap":
+        return ColorMap(PyColorMap.red_scale())
+
+    @staticmethod
+    def three_point(low: "str | Color", mid: "str | Color", high: "str | Color") -> "ColorMap":
+        return ColorMap(PyColorMap.three_point(_py_color(low), _py_color(mid), _py_color(high)))
+
+    def palette(self) -> list["Color"]:
+        return [Color._wrap(c) for c in self._cmap.palette()]
+
+    @staticmethod
+    def named(name: str) -> "ColorMap":
+        """Built-in colormap such as "viridis" or "RdYlGn"."""
+        return ColorMap(PyColorMap.named(name))
+
+    @staticmethod
+    def names() -> list[str]:
+        return PyColorMap.names()
+
+    def reversed(self) -> "ColorMap":
+        return ColorMap(self._cmap.reversed())
+
+    def resampled(self, n: int) -> "ColorMap":
+        return ColorMap(self._cmap.resampled(n))
+
+    def is_colorblind_safe(self) -> bool:
+        return self._cmap.is_colorblind_safe()
 
 
 class Styler:
@@ -17,38 +121,262 @@ class Styler:
 
     def background_gradient(
         self,
-        cmap: ColorMap = None,
+        cmap: "ColorMap | str" = None,
         subset: list[str] = None,
         vmin: float = None,
         vmax: float = None,
         text_color_threshold: float = None,
+        opacity: float = None,
+        color: str | Color = None,
     ):
+        """Gradient from white to `color` (red by default) unless a `cmap` is given;
+        `opacity` below 1 renders the backgrounds as subtle tints. With
+        `text_color_threshold`, text turns white on backgrounds whose relative luminance
+        is below it (pandas uses 0.408)."""
         self._s.background_gradient(
-            cmap=cmap,
+            cmap=_py_cmap(cmap),
             subset=subset,
             vmin=vmin,
             vmax=vmax,
             text_color_threshold=text_color_threshold,
+            opacity=opacity,
+            color=_py_color(color),
         )
         return self
 
+    def background_gradient_expr(self, expr: pl.Expr, color: "str | Color" = None):
+        """Gradient on the column the expression is named after, scaled by its values,
+        e.g. `pl.col("a").log()`."""
+        series = self._s.data().select(expr).to_series()
+        if series.name not in self._s.data().columns:
+            raise ValueError(f"Expression result {series.name!r} is not a column; use .alias()")
+        self._s.background_gradient_series(series, _py_color(color))
+        return self
+
+    def background_gradient_shared(
+        self,
+        subset: list[str] = None,
+        cmap: "ColorMap | str" = None,
+        vmin: float = None,
+        vmax: float = None,
+    ):
+        """Gradient on one scale across the `subset` columns, so that equal values get
+        equal colors in all of them, like pandas `background_gradient(axis=None)`."""
+        self._s.background_gradient_shared(subset=subset, cmap=_py_cmap(cmap), vmin=vmin, vmax=vmax)
+        return self
+
     def bar(
         self,
-        cmap: ColorMap = None,
+        cmap: "ColorMap | str" = None,
         subset: list[str] = None,
         vmin: float = None,
         vmax: float = None,
-        text_color_threshold: float = None,
+        color: str | Color = None,
     ):
+        """Bars in `color`, a `Color` or a hex, rgb() or CSS named color such as "steelblue",
+        or colored by value through `cmap`."""
         self._s.bar(
-            cmap=cmap,
+            cmap=_py_cmap(cmap),
             subset=subset,
             vmin=vmin,
             vmax=vmax,
-            text_color_threshold=text_color_threshold,
+            color=_py_color(color),
+        )
+        return self
+
+    def set_column_precision(self, column: str, precision: int):
+        self._s.set_column_precision(column, precision)
+        return self
+
+    def set_caption(self, caption: str):
+        self._s.set_caption(caption)
+        return self
+
+    def hide_columns(self, columns: str | list[str]):
+        if isinstance(columns, str):
+            columns = [columns]
+        self._s.hide_columns(columns)
+        return self
+
+    def set_table_styles(self, styles: list[tuple[str, str]]):
+        """Replaces the CSS rules scoped to the table, as `(selector, props)` pairs such as
+        `("th", "color: red")`."""
+        self._s.set_table_styles(styles)
+        return self
+
+    def highlight_between(self, column: str, left: float, right: float, color: "str | Color" = "yellow"):
+        self._s.highlight_between(column, left, right, _py_color(color))
+        return self
+
+    def highlight_null(self, column: str, color: "str | Color" = "red"):
+        self._s.highlight_null(column, _py_color(color))
+        return self
+
+    def heatmap_symmetric(
+        self,
+        cmap: "ColorMap | str" = "RdBu",
+        vmin: float = -1.0,
+        vmax: float = 1.0,
+        mask_diagonal: bool = False,
+    ):
+        """Heatmap of a correlation matrix such as `df.corr()`: numeric columns get a
+        diverging `cmap` centered at 0 and 2 decimals; `mask_diagonal` hides the diagonal."""
+        self._s.heatmap_symmetric(_py_cmap(cmap), vmin, vmax, mask_diagonal)
+        return self
+
+    def icon_set(self, column: str, icons: str = "three_arrows", thresholds: list[float] = None):
+        """Puts a red, amber or green icon (`"three_arrows"` or `"three_traffic_lights"`)
+        in front of the values below, between and above the two `thresholds`; without
+        them the column range is split into thirds."""
+        self._s.icon_set(column, icons, thresholds)
+        return self
+
+    def emphasize_when(self, column: str, predicate: pl.Expr, style: str = "bold"):
+        """Makes the cells of `column` bold, italic, underlined or struck through
+        (`style`) in the rows where `predicate`, e.g. `pl.col("latency") > 500`, holds."""
+        mask = self._s.data().select(predicate).to_series()
+        self._s.emphasize_series(column, mask, style)
+        return self
+
+    def set_text(
+        self,
+        subset: "str | list[str]" = None,
+        *,
+        bold: bool = False,
+        italic: bool = False,
+        underline: bool = False,
+        strikethrough: bool = False,
+        weight: "str | int" = None,
+        color: "str | Color" = None,
+        font_family: str = None,
+        when: pl.Expr = None,
+    ):
+        """Sets the text weight, style, decoration, color and font family of the `subset`
+        columns (all by default), only in the rows where `when` holds if it is given."""
+        if isinstance(subset, str):
+            subset = [subset]
+        mask = self._s.data().select(when).to_series() if when is not None else None
+        decoration = " ".join(d for d, on in [("underline", underline), ("line-through", strikethrough)] if on)
+        self._s.set_text(
+            subset if subset is not None else self._s.data().columns,
+            mask,
+            weight=str(weight) if weight is not None else ("bold" if bold else None),
+            style="italic" if italic else None,
+            decoration=decoration or None,
+            color=_py_color(color),
+            family=font_family,
         )
         return self
 
+    def highlight_rows_at(self, rows: list[int], color: "str | Color" = "yellow"):
+        """Highlights whole rows given by their positions."""
+        self._s.highlight_rows_at(rows, _py_color(color))
+        return self
+
+    def highlight_rows_where_key(self, column: str, values: list, color: "str | Color" = "yellow"):
+        """Highlights whole rows whose value in `column` is one of `values`."""
+        self._s.highlight_rows_where_key(column, [str(v) for v in values], _py_color(color))
+        return self
+
+    def format_percent_of_total(self, column: str, with_value: bool = False):
+        """Displays values as their share of the column sum, e.g. `12.3%`, or with
+        `with_value` after the value, e.g. `1,234 (12.3%)`."""
+        self._s.format_percent_of_total(column, with_value)
+        return self
+
+    def show_row_numbers(self, start: int = 1):
+        """Renders a leading header column with row numbers counted from `start`."""
+        self._s.show_row_numbers(start)
+        return self
+
+    def add_summary_row(
+        self, label: str, aggregation: str = "sum", subset: "str | list[str]" = None, sticky: bool = False
+    ):
+        """Adds a footer row with the `aggregation` ("sum", "mean", "min", "max" or
+        "count") of all rows of the `subset` columns, numeric ones by default, labeled in
+        the first column. With `sticky`, the footer stays at the bottom of the scroll
+        container."""
+        subset = [subset] if isinstance(subset, str) else subset
+        if subset is None:
+            subset = [c for c, t in self._s.data().schema.items() if t.is_numeric()]
+        self._s.add_summary_row(label, aggregation, subset, sticky)
+        return self
+
+    def add_column_separator(
+        self, after_column: str, width: int = 2, color: "str | Color" = "#444444", style: str = "solid"
+    ):
+        """Draws a vertical line after the column, e.g. between dimensions and metrics."""
+        self._s.add_column_separator(after_column, width, _py_color(color), style)
+        return self
+
+    def expandable_text(self, column: str, max_chars: int = 80):
+        """Shows the first `max_chars` characters of longer values with a "more" toggle
+        revealing the rest."""
+        self._s.expandable_text(column, max_chars)
+        return self
+
+    def clip_column(self, column: str, max_width: str = "20em"):
+        """Limits the width of the cells of the column, cutting overflowing text with an
+        ellipsis; the full value is shown when hovering the cell."""
+        self._s.clip_column(column, max_width)
+        return self
+
+    def set_column_meta(
+        self, column: str, unit: str = None, description: str = None, unit_in_values: bool = False
+    ):
+        """Sets the unit of the column, shown under its label or after the numbers with
+        `unit_in_values`, and a description shown when hovering the header."""
+        self._s.set_column_meta(column, unit, description, unit_in_values)
+        return self
+
+    def border(
+        self,
+        when: pl.Expr,
+        subset: "str | list[str]" = None,
+        width: int = 2,
+        color: "str | Color" = "red",
+        style: str = "solid",
+    ):
+        """Outlines the cells of `subset` (whole rows by default) in the rows where `when`
+        holds; adjacent matching cells share one outline."""
+        if isinstance(subset, str):
+            subset = [subset]
+        mask = self._s.data().select(when).to_series()
+        subset = subset if subset is not None else self._s.data().columns
+        self._s.border_series(subset, mask, width, _py_color(color), style)
+        return self
+
+    def map_icons(self, column: str, icons: dict[str, str]):
+        """Displays values as icons, e.g. `{"ok": "✅", "failed": "❌"}`; the icons may be any
+        HTML such as inline SVG and the original value is shown when hovering them."""
+        self._s.map_icons(column, icons)
+        return self
+
+    def trend_arrows(self, column: str, compare: "str | pl.Expr"):
+        """Appends a green ▲, red ▼ or gray ▬ to the values of `column` when they are above,
+        below or equal to `compare`: another column or an expression such as
+        `pl.col("sales").shift(1)` for the previous period."""
+        if isinstance(compare, str):
+            self._s.trend_arrows(column, compare)
+        else:
+            self._s.trend_arrows_series(column, self._s.data().select(compare).to_series())
+        return self
+
+    def set_details(self, key: str, detail: "Styler | pl.DataFrame", collapsible: bool = True):
+        """Renders the rows of `detail` with the same `key` as an indented nested table
+        under each row, folded in a `<details>` element when `collapsible`."""
+        if isinstance(detail, pl.DataFrame):
+            detail = Styler(pydf_to_pystyler(detail))
+        self._s.set_details(key, detail._s, collapsible)
+        return self
+
+    def diff(self, old: pl.DataFrame, tooltips: bool = True):
+        """Highlights cells that differ from `old`, a frame with the same columns compared
+        row by row: added values in green, removed in red and modified in yellow. With
+        `tooltips`, hovering a changed cell shows the old and the new value."""
+        self._s.diff(old, tooltips)
+        return self
+
     def set_table_classes(self, classes: str | list[str]):
         if isinstance(classes, str):
             classes = [classes]
@@ -65,21 +393,134 @@ class Styler:
         if isinstance(labels, list):
             self._s.set_labels(labels)
         elif isinstance(labels, dict):
-            for column, label in labels.items():
-                self._s.relabel_column(column, label)
+            self._s.relabel(labels)
         else:
             raise ValueError(f"labels must be list or dict, got {type(labels)}")
         return self
 
-    def _prepare_ipynb_table(self):
-        return self.add_table_classes("dataframe")
+    def format(
+        self,
+        column: "str | list[str]" = None,
+        formatter: "str | Callable[[object], str]" = None,
+        *,
+        precision: int = None,
+        thousands: str = None,
+        decimal: str = None,
+        na_rep: str = None,
+        date_format: str = None,
+        currency: str = None,
+        suffix: str = None,
+        percent: bool = False,
+    ):
+        """Formats the values of the column (all columns by default).
+
+        With a `formatter` the values are displayed by a callable, a `str.format` template
+        such as "{:.1%}" or a format spec such as ",.2f"; nulls are shown as `na_rep`
+        ("null" by default) unless a callable handles `None` itself.
+
+        Without it, the keyword arguments describe the format: number of decimals,
+        `thousands` separator, `decimal` mark, `currency` symbol put before and `suffix`
+        after the number, `percent` (multiplies by 100), `na_rep` and a strftime
+        `date_format` for temporal columns. Such formats are kept in the spec and survive
+        pickling; callables do not."""
+        columns = [column] if isinstance(column, str) else column
+        if formatter is None:
+            self._s.set_format(
+                subset=columns,
+                precision=precision,
+                thousands=thousands,
+                decimal=decimal,
+                na_rep=na_rep,
+                datetime=date_format,
+                prefix=currency,
+                suffix=suffix,
+                percent=percent,
+            )
+            return self
+        if isinstance(formatter, str):
+            template = formatter if "{" in formatter else "{:" + formatter + "}"
+            na = "null" if na_rep is None else na_rep
+
+            def formatter(v):
+                return na if v is None else template.format(v)
+
+        for c in columns or self._s.data().columns:
+            self._s.format(c, formatter)
+        return self
+
+    def relabel(self, mapping: dict[str, str]):
+        """Renames the headers of the columns in the mapping."""
+        self._s.relabel(mapping)
+        return self
 
     def _repr_html_(self):
-        return self._prepare_ipynb_table().render()
+        return self._s._repr_html_()
+
+    def pipe(self, func, *args, **kwargs):
+        """Calls `func(self, *args, **kwargs)`, e.g. a function applying a house style,
+        and returns its result so that the chain can continue."""
+        return func(self, *args, **kwargs)
+
+    def merge(self, other: "Styler"):
+        """Combines with a styler of the same data built separately, e.g. a shared theme.
+
+        Styles and settings of `other` take precedence where both set them."""
+        self._s.merge(other._s)
+        return self
+
+    def add_computed_column(self, name: str, expr: pl.Expr, position: int | None = None):
+        """Adds a column computed from the others, e.g. `pl.col("price") - pl.col("cost")`,
+        to the rendered table at `position` or at the end. The DataFrame isn't modified."""
+        self._s.add_computed_series(self._s.data().select(expr.alias(name)).to_series(), position)
+        return self
+
+    def add_rank_column(self, by_column: str, descending: bool = True, medals: bool = False):
+        """Adds a "Rank" column after `by_column` numbering its values 1, 2, 3, ..., the
+        largest first unless `descending` is False. With `medals`, the top three ranks get
+        gold, silver and bronze backgrounds."""
+        self._s.add_rank_column(by_column, descending, medals)
+        return self
+
+    def __copy__(self) -> "Styler":
+        return Styler(copy.copy(self._s))
+
+    def __deepcopy__(self, memo) -> "Styler":
+        return Styler(copy.deepcopy(self._s, memo))
+
+    def describe(self) -> str:
+        """Summary of the data and the styling (gradients, formats, labels, classes) that
+        will be rendered."""
+        return self._s.describe()
+
+    def __repr__(self) -> str:
+        return self.describe()
 
     def render(self):
         return self._s.render()
 
+    def to_html(self, path: str | os.PathLike = None, doctype_html: bool = False) -> str | None:
+        """Renders the table, wrapped in a standalone HTML page if `doctype_html`, and
+        writes it to `path` or returns it if no path is given."""
+        html = self.render()
+        if doctype_html:
+            html = (
+                '<!DOCTYPE html>\n<html>\n<head>\n<meta charset="utf-8">\n</head>\n'
+                f"<body>\n{html}\n</body>\n</html>\n"
+            )
+        if path is None:
+            return html
+        with open(path, "w", encoding="utf-8") as f:
+            f.write(html)
+        return None
+
+    def show(self) -> str:
+        """Opens the table in the default web browser; returns the path of the page."""
+        with tempfile.NamedTemporaryFile("w", suffix=".html", delete=False) as f:
+            path = f.name
+        self.to_html(path, doctype_html=True)
+        webbrowser.open(pathlib.Path(path).as_uri())
+        return path
+
 
 def style(df_self):
     # use this to patch pl.DataFrame.style
@@ -88,4 +529,13 @@ def style(df_self):
     return Styler(py_styler)
 
 
+def from_lazy(lf: pl.LazyFrame, columns: list[str] = None) -> Styler:
+    """Collects the query and styles the result; pass `columns` to read only the
+    columns to render."""
+    if columns is not None:
+        lf = lf.select(columns)
+    return style(lf.collect())
+
+
 pl.DataFrame.style = style
+pl.LazyFrame.style = from_lazy
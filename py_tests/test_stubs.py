@@ -0,0 +1,36 @@
+import ast
+import pathlib
+import unittest
+
+from polars_styler import polars_styler
+
+STUBS = pathlib.Path(polars_styler.__file__).with_name("polars_styler.pyi")
+
+
+def stubbed_names() -> dict[str, set[str]]:
+    tree = ast.parse(STUBS.read_text())
+    names = {"": set()}
+    for node in tree.body:
+        if isinstance(node, ast.ClassDef):
+            names[node.name] = {f.name for f in node.body if isinstance(f, ast.FunctionDef)}
+        elif isinstance(node, ast.FunctionDef):
+            names[""].add(node.name)
+    return names
+
+
+class TestStubs(unittest.TestCase):
+    def test_stubs_cover_module(self):
+        stubs = stubbed_names()
+        for name in dir(polars_styler):
+            obj = getattr(polars_styler, name)
+            if isinstance(obj, type):
+                self.assertIn(name, stubs)
+                methods = {m for m in vars(obj) if not m.startswith("__")}
+                missing = methods - stubs[name]
+                self.assertFalse(missing, f"{name} stubs miss {missing}")
+            elif callable(obj) and not name.startswith("_"):
+                self.assertIn(name, stubs[""])
+
+
+if __name__ == '__main__':
+    unittest.main()
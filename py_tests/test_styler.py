@@ -1,8 +1,14 @@
+import copy
 import math
+import os
+import pickle
+import tempfile
 import unittest
+from unittest import mock
 import polars as pl
 
-from polars_styler.styler import Styler
+from polars_styler.pandas_compat import pandas_style
+from polars_styler.styler import Color, ColorMap, Styler, from_lazy
 
 
 class TestRendering(unittest.TestCase):
@@ -19,9 +25,308 @@ class TestRendering(unittest.TestCase):
         html = style(self.df).background_gradient(subset=["b"]).render()
         self.assertIn("background-color", html)
 
+    def test_gradient_opacity(self):
+        cmap = ColorMap.named("Greys")
+        html = style(self.df).background_gradient(cmap, subset=["a"], opacity=0.5).render()
+        self.assertIn("rgba(0, 0, 0, 0.5)", html)
+
+    def test_named_colormap(self):
+        cmap = ColorMap.named("viridis")
+        html = style(self.df).background_gradient(cmap, subset=["a"]).render()
+        self.assertIn("#440154", html)
+        self.assertIn("viridis", ColorMap.names())
+        with self.assertRaises(ValueError):
+            ColorMap.named("nope")
+
+    def test_bar_named_color(self):
+        html = style(self.df).bar(subset=["a"], color="steelblue").render()
+        self.assertIn("#4682b4", html)
+        with self.assertRaises(ValueError):
+            style(self.df).bar(color="notacolor")
+
+    def test_bar_colormap(self):
+        html = style(self.df).bar(cmap="viridis", subset=["a"]).render()
+        self.assertIn("#fde725", html)
+
+    def test_text_color_threshold(self):
+        default = style(self.df).background_gradient("Greys", subset=["a"]).render()
+        styler = style(self.df).background_gradient("Greys", subset=["a"], text_color_threshold=0.9)
+        html = styler.render()
+        self.assertGreater(html.count("color: #ffffff"), default.count("color: #ffffff"))
+
+    def test_color(self):
+        steel = Color("steelblue")
+        self.assertEqual(steel.to_hex(), "#4682b4")
+        self.assertEqual(steel, Color.from_rgb(70, 130, 180))
+        self.assertEqual(steel.to_rgb(), "rgb(70, 130, 180)")
+        html = style(self.df).background_gradient(subset=["a"], color=steel).render()
+        self.assertIn("rgba(70, 130, 180, 1)", html)
+        with self.assertRaises(ValueError):
+            Color("notacolor")
+
+    def test_colormap_constructors(self):
+        cmap = ColorMap.from_palette(["white", Color("navy")])
+        self.assertEqual(cmap.palette(), [Color("white"), Color("#000080")])
+        html = style(self.df).background_gradient(cmap, subset=["a"]).render()
+        self.assertIn("#000080", html)
+        self.assertEqual(len(ColorMap.three_point("red", "white", "blue").palette()), 3)
+        self.assertEqual(ColorMap.red_scale().palette()[-1], Color("red"))
+        ColorMap.from_points([(0.0, "red"), (1.0, "blue")])
+        with self.assertRaises(ValueError):
+            ColorMap.from_points([(1.0, "red"), (0.0, "blue")])
+        with self.assertRaises(ValueError):
+            ColorMap.from_palette([])
+
+    def test_cmap_name(self):
+        html = style(self.df).background_gradient(cmap="viridis", subset=["a"]).render()
+        self.assertIn("#440154", html)
+
+    def test_cmap_matplotlib(self):
+        try:
+            from matplotlib import colormaps
+        except ImportError:
+            self.skipTest("matplotlib is not installed")
+        html = style(self.df).background_gradient(cmap=colormaps["viridis"], subset=["a"]).render()
+        self.assertIn("#440154", html)
+
+    def test_format(self):
+        html = style(self.df).format("a", lambda v: f"#{v}").format("b", ".1%").render()
+        self.assertIn(">#1<", html)
+        self.assertIn(">314.2%<", html)
+        html = style(self.df).format("b", "~{:.1f}~").render()
+        self.assertIn(">~3.1~<", html)
+        with self.assertRaises(ZeroDivisionError):
+            style(self.df).format("a", lambda v: str(1 / 0)).render()
+
+    def test_format_kwargs(self):
+        html = style(self.df).format("b", currency="$", precision=2, thousands=",").render()
+        self.assertIn(">$3.14<", html)
+        html = style(self.df).format(precision=1, percent=True).render()
+        self.assertIn(">314.2%<", html)
+        restored = pickle.loads(pickle.dumps(style(self.df).format("b", precision=0)))
+        self.assertIn(">3<", restored.render())
+
+    def test_background_gradient_shared(self):
+        df = pl.DataFrame({"a": [0.0, 5.0], "b": [5.0, 10.0]})
+        html = style(df).background_gradient_shared(cmap="viridis").render()
+        self.assertIn("#440154", html)
+        self.assertIn("#fde725", html)
+        with self.assertRaises(KeyError):
+            style(df).background_gradient_shared(["a", "c"])
+        html = pandas_style(df).background_gradient(cmap="viridis", axis=None).to_html()
+        self.assertIn("#fde725", html)
+
+    def test_background_gradient_expr(self):
+        html = style(self.df).background_gradient_expr(pl.col("a").log(), color="black").render()
+        self.assertIn("rgba(0, 0, 0, 1)", html)
+        with self.assertRaises(ValueError):
+            style(self.df).background_gradient_expr(pl.col("a").alias("x"))
+
+    def test_failed_call_keeps_styles(self):
+        styler = style(self.df).background_gradient(subset=["b"]).set_caption("Kept")
+        with self.assertRaises(KeyError):
+            styler.highlight_null("c")
+        with self.assertRaises(ValueError):
+            styler.icon_set("a", "three_arrows", thresholds=[2.0, 1.0])
+        html = styler.render()
+        self.assertIn("background-color", html)
+        self.assertIn("<caption>Kept</caption>", html)
+
+    def test_errors(self):
+        with self.assertRaises(KeyError):
+            style(self.df).highlight_null("c")
+        with self.assertRaises(ValueError):
+            style(self.df).set_precision(2).set_precision(3)
+        styler = style(self.df).set_precision(2)
+        with self.assertRaises(KeyError):
+            styler.background_gradient(subset=["b", "c"])
+        self.assertIn("3.14", styler.render())
+
+    def test_merge(self):
+        theme = style(self.df).set_precision(2)
+        report = style(self.df).highlight_null("a")
+        html = theme.merge(report).render()
+        self.assertIn("3.14", html)
+        self.assertNotIn("3.141", html)
+
+    def test_computed_column(self):
+        styler = style(self.df).add_computed_column("double", pl.col("a") * 2, position=0)
+        self.assertEqual(styler._s.data().columns[0], "double")
+        self.assertNotIn("double", self.df.columns)
+        with self.assertRaises(ValueError):
+            style(self.df).add_computed_column("a", pl.col("a") * 2)
+
+    def test_rank_column(self):
+        styler = style(self.df).add_rank_column("a", medals=True)
+        self.assertEqual(styler._s.data()["a rank"].to_list(), [3, 2, 1])
+        self.assertIn("#ffd700", styler.render())
+
+    def test_pipe(self):
+        def house_style(styler, precision):
+            return styler.set_precision(precision)
+
+        html = style(self.df).pipe(house_style, precision=2).render()
+        self.assertIn("3.14", html)
+        self.assertNotIn("3.141", html)
+
+    def test_diff(self):
+        old = self.df.with_columns(pl.Series("a", [1, 5, 3]))
+        html = style(self.df).diff(old).render()
+        self.assertIn('title="5 → 2"', html)
+        with self.assertRaises(KeyError):
+            style(self.df).diff(old.drop("b"))
+
+    def test_heatmap_symmetric(self):
+        html = style(self.df.corr()).heatmap_symmetric(mask_diagonal=True).render()
+        self.assertIn("visibility: hidden", html)
+
+    def test_icon_set(self):
+        html = style(self.df).icon_set("a", "three_traffic_lights", [1.5, 2.5]).render()
+        self.assertIn("●</span> 3", html)
+        with self.assertRaises(ValueError):
+            style(self.df).icon_set("a", "stars")
+
+    def test_emphasize_when(self):
+        html = style(self.df).emphasize_when("b", pl.col("a") > 2, "italic").render()
+        self.assertIn("font-style: italic", html)
+        with self.assertRaises(ValueError):
+            style(self.df).emphasize_when("b", pl.col("a") > 2, "blink")
+
+    def test_set_text(self):
+        html = style(self.df).set_text("a", bold=True, color="red").render()
+        self.assertIn("font-weight: bold", html)
+        html = style(self.df).set_text(font_family="monospace", when=pl.col("a") > 2).render()
+        self.assertIn("font-family: monospace", html)
+
+    def test_highlight_rows(self):
+        html = style(self.df).highlight_rows_where_key("a", [2], "red").render()
+        self.assertIn("#ff0000", html)
+        with self.assertRaises(IndexError):
+            style(self.df).highlight_rows_at([5])
+
+    def test_percent_of_total(self):
+        html = style(self.df).format_percent_of_total("a", with_value=True).render()
+        self.assertIn(">3 (50.0%)</td>", html)
+
+    def test_row_numbers(self):
+        html = style(self.df).show_row_numbers(0).render()
+        self.assertIn('<th class="ps-row-number" scope="row">2</th>', html)
+
+    def test_summary_row(self):
+        html = style(self.df).add_summary_row("Total", subset="a", sticky=True).render()
+        self.assertIn('<tfoot><tr class="ps-summary"><td>6</td><td></td></tr></tfoot>', html)
+        self.assertIn("position: sticky", html)
+
+    def test_column_separator(self):
+        html = style(self.df).add_column_separator("a").render()
+        self.assertIn("{border-right: 2px solid #444444}", html)
+
+    def test_expandable_text(self):
+        df = pl.DataFrame({"log": ["ok", "disk almost full"]})
+        html = style(df).expandable_text("log", 4).render()
+        self.assertIn('>disk<details class="ps-more"><summary>… more</summary> almost full</details>', html)
+
+    def test_clip_column(self):
+        html = style(self.df).clip_column("b", "4em").render()
+        self.assertIn("max-width: 4em", html)
+        self.assertIn('title="0.2"', html)
+
+    def test_column_meta(self):
+        html = style(self.df).set_column_meta("b", unit="m", description="Length").render()
+        self.assertIn('title="Length">b</th>', html)
+        self.assertIn("<th>m</th>", html)
+
+    def test_border(self):
+        html = style(self.df).border(pl.col("a") >= 2, width=3).render()
+        self.assertIn("border-top: 3px solid #ff0000", html)
+
+    def test_map_icons(self):
+        df = pl.DataFrame({"status": ["ok", "failed"]})
+        html = style(df).map_icons("status", {"ok": "✅"}).render()
+        self.assertIn('title="ok">✅</td>', html)
+        self.assertIn(">failed</td>", html)
+
+    def test_trend_arrows(self):
+        html = style(self.df).trend_arrows("a", pl.col("a").shift(1)).render()
+        self.assertIn("▲</span>", html)
+        with self.assertRaises(KeyError):
+            style(self.df).trend_arrows("a", "c")
+
+    def test_details(self):
+        detail = pl.DataFrame({"a": [1, 1, 3], "item": ["x", "y", "z"]})
+        html = style(self.df).set_details("a", detail).render()
+        self.assertIn("<summary>2 rows</summary>", html)
+        self.assertIn("<summary>1 row</summary>", html)
+
+    def test_from_lazy(self):
+        html = from_lazy(self.df.lazy().filter(pl.col("a") > 1), columns=["a"]).render()
+        self.assertIn(">3<", html)
+        self.assertNotIn(">1<", html)
+        self.assertNotIn("3.14", html)
+
+    def test_to_html(self):
+        self.assertIn("3.14", style(self.df).to_html())
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "table.html")
+            style(self.df).to_html(path, doctype_html=True)
+            with open(path, encoding="utf-8") as f:
+                html = f.read()
+        self.assertTrue(html.startswith("<!DOCTYPE html>"))
+        self.assertIn("<table", html)
+
+    def test_show(self):
+        with mock.patch("webbrowser.open") as open_browser:
+            path = style(self.df).show()
+        open_browser.assert_called_once()
+        self.assertTrue(open_browser.call_args[0][0].startswith("file://"))
+        os.remove(path)
+
+    def test_pandas_compat(self):
+        html = (
+            pandas_style(self.df)
+            .highlight_max(color="lightgreen")
+            .format("{:.2f}", subset=["b"])
+            .set_caption("Numbers")
+            .set_table_styles([{"selector": "th", "props": [("color", "navy")]}])
+            .hide(["a"], axis="columns")
+            .background_gradient(subset=["b"])
+            .to_html()
+        )
+        self.assertIn("<caption>Numbers</caption>", html)
+        self.assertIn("th {color: navy}", html)
+        self.assertIn(">3.14<", html)
+        self.assertIn("#90ee90", html)
+        self.assertNotIn(">a<", html)
+
+    def test_describe(self):
+        styler = style(self.df).background_gradient(subset=["b"]).relabel({"a": "Alpha"})
+        self.assertIn("background_gradient on b", repr(styler))
+        self.assertIn("labels: a -> Alpha", styler.describe())
+
+    def test_pickle(self):
+        styler = style(self.df).set_precision(2).background_gradient(subset=["b"])
+        restored = pickle.loads(pickle.dumps(styler))
+        self.assertEqual(restored.describe(), styler.describe())
+        self.assertIn("3.14", restored.render())
+
+    def test_copy(self):
+        styler = style(self.df)
+        duplicate = copy.copy(styler).relabel({"a": "Alpha"})
+        self.assertNotIn("Alpha", styler.render())
+        self.assertIn("Alpha", duplicate.render())
+        self.assertIn("Alpha", copy.deepcopy(duplicate).render())
+
+    def test_relabel(self):
+        html = style(self.df).relabel({"a": "Alpha"}).set_labels({"b": "Beta"}).render()
+        self.assertIn(">Alpha<", html)
+        self.assertIn(">Beta<", html)
+
     def test_ipynb_table(self):
-        html = style(self.df)._repr_html_()
+        styler = style(self.df)
+        html = styler._repr_html_()
         self.assertIn("class=\"dataframe\"", html)
+        self.assertEqual(styler._repr_html_().count("dataframe"), 1)
+        self.assertNotIn("dataframe", styler.render())
 
     @property
     def df(self):
@@ -0,0 +1,42 @@
+pub use crate::web::StyledTable;
+use ::actix_web::body::BoxBody;
+use ::actix_web::http::header::ContentType;
+use ::actix_web::{HttpRequest, HttpResponse, Responder};
+
+impl Responder for StyledTable {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse {
+        match self.0.try_render() {
+            Ok(html) => HttpResponse::Ok()
+                .content_type(ContentType::html())
+                .body(html),
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::styler::StylerExt;
+    use ::actix_web::http::header::CONTENT_TYPE;
+    use ::actix_web::http::StatusCode;
+    use ::actix_web::test::TestRequest;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_respond_to() {
+        let req = TestRequest::default().to_http_request();
+        let df = df!("a" => &[1, 2, 3]).unwrap();
+        let response = StyledTable::from(df.style()).respond_to(&req);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+
+        let response = StyledTable(DataFrame::default().style()).respond_to(&req);
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
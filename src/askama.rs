@@ -0,0 +1,28 @@
+pub use crate::web::PreRendered;
+use ::askama::filters::HtmlSafe;
+
+impl HtmlSafe for PreRendered {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::styler::StylerExt;
+    use ::askama::Template;
+    use polars::prelude::*;
+
+    #[derive(Template)]
+    #[template(source = "<div>{{ table }}</div>", ext = "html")]
+    struct Page {
+        table: PreRendered,
+    }
+
+    #[test]
+    fn test_html_safe() {
+        let df = df!("a" => &[1, 2]).unwrap();
+        let table = PreRendered::from(df.style().set_uid("x"));
+        let page = Page {
+            table: table.clone(),
+        };
+        assert_eq!(page.render().unwrap(), format!("<div>{}</div>", table));
+    }
+}
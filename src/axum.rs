@@ -0,0 +1,31 @@
+pub use crate::web::StyledTable;
+use ::axum::http::StatusCode;
+use ::axum::response::{Html, IntoResponse, Response};
+
+impl IntoResponse for StyledTable {
+    fn into_response(self) -> Response {
+        match self.0.try_render() {
+            Ok(html) => Html(html).into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::styler::StylerExt;
+    use ::axum::http::header::CONTENT_TYPE;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_into_response() {
+        let df = df!("a" => &[1, 2, 3]).unwrap();
+        let response = StyledTable::from(df.style()).into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+
+        let response = StyledTable(DataFrame::default().style()).into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
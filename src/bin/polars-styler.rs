@@ -0,0 +1,151 @@
+use clap::{Parser, ValueEnum};
+use polars_styler::colors::Color;
+use polars_styler::spec::StyleSpec;
+use polars_styler::styler::Styler;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Render a CSV or IPC file as a styled table.
+#[derive(Parser)]
+#[command(name = "polars-styler", version)]
+struct Args {
+    /// Input file (.csv, .arrow, .ipc or .feather)
+    input: PathBuf,
+
+    /// Output file; prints to stdout if omitted
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Output format; inferred from the output extension by default
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// JSON or YAML style spec applied before the other flags
+    #[arg(short, long)]
+    spec: Option<PathBuf>,
+
+    /// Number of decimals of float columns
+    #[arg(long)]
+    precision: Option<u32>,
+
+    /// Column with background gradient (repeatable)
+    #[arg(long, value_name = "COLUMN")]
+    gradient: Vec<String>,
+
+    /// Column with bar chart (repeatable)
+    #[arg(long, value_name = "COLUMN")]
+    bar: Vec<String>,
+
+    /// Color used by gradients and bars
+    #[arg(long, default_value = "#ff0000")]
+    color: String,
+
+    /// Class added to the table element (repeatable)
+    #[arg(long = "class", value_name = "CLASS")]
+    classes: Vec<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Html,
+    Markdown,
+    Latex,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()? {
+            "html" | "htm" => Some(Format::Html),
+            "md" | "markdown" => Some(Format::Markdown),
+            "tex" => Some(Format::Latex),
+            _ => None,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let mut styler = match args.input.extension().and_then(|e| e.to_str()) {
+        Some("arrow" | "ipc" | "feather") => Styler::from_ipc(&args.input)?,
+        _ => Styler::from_csv(&args.input)?,
+    };
+    let color = Color::try_from(args.color.as_str())
+        .map_err(|_| format!("invalid color: {}", args.color))?;
+
+    if let Some(path) = &args.spec {
+        styler = styler.apply_spec(&StyleSpec::parse(&std::fs::read_to_string(path)?)?);
+    }
+    if let Some(precision) = args.precision {
+        styler = styler.apply_spec(&StyleSpec {
+            precision: Some(precision),
+            ..Default::default()
+        });
+    }
+    if !args.classes.is_empty() {
+        styler = styler.add_table_classes(args.classes);
+    }
+    let styler = args.gradient.iter().try_fold(styler, |s, column| {
+        s.try_background_gradient(column, &color, &None, &None)
+    })?;
+    let styler = args
+        .bar
+        .iter()
+        .try_fold(styler, |s, column| s.try_bar(column, &color, &None, &None))?;
+
+    let format = args
+        .format
+        .or_else(|| args.output.as_deref().and_then(Format::from_path))
+        .unwrap_or(Format::Html);
+    let rendered = render(styler, format);
+
+    match &args.output {
+        Some(path) => std::fs::write(path, rendered)?,
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+fn render(styler: Styler, format: Format) -> String {
+    match format {
+        Format::Html => styler.render(),
+        Format::Markdown => styler.render_markdown(),
+        Format::Latex => styler.render_latex(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unknown_column() {
+        let input = std::env::temp_dir().join("polars_styler_test_cli.csv");
+        std::fs::write(&input, "a,b\n1,x\n2,y\n").unwrap();
+        let output = input.with_extension("html");
+        let args = |flag: &str, column: &str| {
+            Args::parse_from([
+                "polars-styler",
+                input.to_str().unwrap(),
+                "-o",
+                output.to_str().unwrap(),
+                flag,
+                column,
+            ])
+        };
+
+        assert!(run(args("--gradient", "a")).is_ok());
+        let err = run(args("--gradient", "c")).unwrap_err();
+        assert!(err.to_string().starts_with("Unknown column `c`"));
+        assert!(run(args("--bar", "c")).is_err());
+        assert!(run(args("--bar", "b")).is_err());
+    }
+}
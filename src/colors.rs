@@ -1,10 +1,11 @@
 use itertools::Itertools;
 use polars::export::num::Pow;
-use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::fmt::Error;
+use std::fmt::{Display, Error, Formatter};
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct Color {
     r: u8,
     g: u8,
@@ -16,29 +17,140 @@ impl Color {
         Color { r, g, b }
     }
 
+    /// Parses `#rgb` or `#rrggbb`; the alpha digits of `#rgba` / `#rrggbbaa` are dropped.
     pub fn from_hex(hex: &str) -> Result<Self, Error> {
-        if hex.len() != 7 {
+        let digits = hex.trim().strip_prefix('#').ok_or(Error)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
             return Err(Error);
         }
-        let r = u8::from_str_radix(&hex[1..3], 16).map_err(|_| Error)?;
-        let g = u8::from_str_radix(&hex[3..5], 16).map_err(|_| Error)?;
-        let b = u8::from_str_radix(&hex[5..7], 16).map_err(|_| Error)?;
-        Ok(Color::new(r, g, b))
+        let channel = |i: usize, width: usize| {
+            let v = u8::from_str_radix(&digits[i * width..(i + 1) * width], 16).unwrap();
+            if width == 1 {
+                v * 17
+            } else {
+                v
+            }
+        };
+        let width = match digits.len() {
+            3 | 4 => 1,
+            6 | 8 => 2,
+            _ => return Err(Error),
+        };
+        Ok(Color::new(
+            channel(0, width),
+            channel(1, width),
+            channel(2, width),
+        ))
     }
 
+    /// Parses CSS `rgb()` / `rgba()` with numeric or percentage channels, either
+    /// comma-separated or space-separated with an optional `/ alpha`. The alpha is
+    /// validated but dropped.
     pub fn from_rgb(rgb: &str) -> Result<Self, Error> {
-        let re = Regex::new(r"rgb\((\d+), (\d+), (\d+)\)$").unwrap();
+        let (name, channels) = css_function(rgb).ok_or(Error)?;
+        if name != "rgb" && name != "rgba" {
+            return Err(Error);
+        }
+        let channel = |v: &str| {
+            let v = match v.strip_suffix('%') {
+                Some(p) => p.parse::<f64>().map_err(|_| Error)? / 100.0 * 255.0,
+                None => v.parse::<f64>().map_err(|_| Error)?,
+            };
+            if !(0.0..=255.0).contains(&v) {
+                return Err(Error);
+            }
+            Ok(v.round() as u8)
+        };
+        Ok(Color::new(
+            channel(channels[0])?,
+            channel(channels[1])?,
+            channel(channels[2])?,
+        ))
+    }
 
-        let captures = re.captures(rgb).ok_or(Error)?;
-        let mut groups = captures.iter().skip(1).map(|m| {
-            let m = m.ok_or(Error)?;
-            m.as_str().parse::<u8>().map_err(|_| Error)
-        });
+    /// Color from hue in degrees and saturation and lightness in `0..=1`.
+    pub fn from_hsl(h: f64, s: f64, l: f64) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+        Color::new(channel(r), channel(g), channel(b))
+    }
 
-        let r = groups.next().ok_or(Error)??;
-        let g = groups.next().ok_or(Error)??;
-        let b = groups.next().ok_or(Error)??;
-        Ok(Color::new(r, g, b))
+    /// Parses CSS `hsl(210, 50%, 40%)` or `hsl(210deg 50% 40%)`; the alpha of
+    /// `hsla()` is validated but dropped.
+    pub fn from_hsl_str(hsl: &str) -> Result<Self, Error> {
+        let (name, channels) = css_function(hsl).ok_or(Error)?;
+        if name != "hsl" && name != "hsla" {
+            return Err(Error);
+        }
+        let hue = channels[0].strip_suffix("deg").unwrap_or(channels[0]);
+        let h = hue.parse::<f64>().map_err(|_| Error)?;
+        let percent = |v: &str| {
+            let v = v.strip_suffix('%').ok_or(Error)?;
+            let v = v.parse::<f64>().map_err(|_| Error)?;
+            if !(0.0..=100.0).contains(&v) {
+                return Err(Error);
+            }
+            Ok(v / 100.0)
+        };
+        Ok(Color::from_hsl(
+            h,
+            percent(channels[1])?,
+            percent(channels[2])?,
+        ))
+    }
+
+    /// Hue in degrees `0..360` and saturation and lightness in `0..=1`.
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let [r, g, b] = [self.r, self.g, self.b].map(|c| c as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return (0.0, 0.0, l);
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            ((g - b) / d).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    /// CSS `hsl()` notation, rounded to whole degrees and percent.
+    pub fn to_hsl_string(&self) -> String {
+        let (h, s, l) = self.to_hsl();
+        format!(
+            "hsl({}, {}%, {}%)",
+            h.round(),
+            (s * 100.0).round(),
+            (l * 100.0).round()
+        )
+    }
+
+    /// CSS named color such as `steelblue`, case-insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        let i = CSS_NAMED_COLORS
+            .binary_search_by_key(&name.as_str(), |(n, _)| n)
+            .ok()?;
+        let [_, r, g, b] = CSS_NAMED_COLORS[i].1.to_be_bytes();
+        Some(Color::new(r, g, b))
     }
 
     fn to_csv(&self) -> String {
@@ -54,15 +166,106 @@ impl Color {
     }
 
     pub fn to_rgba(&self, a: f64) -> String {
-        // TODO: create struct for color with opacity
         format!("rgba({}, {})", self.to_csv(), a)
     }
 
+    /// This color with opacity `alpha` in `0..=1`, e.g. for hover states.
+    pub fn with_alpha(&self, alpha: f64) -> Rgba {
+        Rgba {
+            color: self.clone(),
+            alpha: alpha.clamp(0.0, 1.0),
+        }
+    }
+
+    /// This color moved the fraction `f` of the way towards `other`, channel-wise in sRGB.
+    pub fn mix(&self, other: &Color, f: f64) -> Color {
+        let f = f.clamp(0.0, 1.0);
+        Color::new(
+            interpolate(self.r, other.r, f),
+            interpolate(self.g, other.g, f),
+            interpolate(self.b, other.b, f),
+        )
+    }
+
+    /// Raises the HSL lightness the fraction `f` of the way towards white.
+    pub fn lighten(&self, f: f64) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::from_hsl(h, s, l + (1.0 - l) * f.clamp(0.0, 1.0))
+    }
+
+    /// Lowers the HSL lightness the fraction `f` of the way towards black.
+    pub fn darken(&self, f: f64) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::from_hsl(h, s, l * (1.0 - f.clamp(0.0, 1.0)))
+    }
+
+    /// Raises the HSL saturation the fraction `f` of the way towards full saturation.
+    pub fn saturate(&self, f: f64) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::from_hsl(h, s + (1.0 - s) * f.clamp(0.0, 1.0), l)
+    }
+
+    /// Lowers the HSL saturation the fraction `f` of the way towards gray.
+    pub fn desaturate(&self, f: f64) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::from_hsl(h, s * (1.0 - f.clamp(0.0, 1.0)), l)
+    }
+
     pub fn relative_luminance(&self) -> f64 {
         0.2126 * normalize_channel(self.r)
             + 0.7152 * normalize_channel(self.g)
             + 0.0722 * normalize_channel(self.b)
     }
+
+    /// Black or white, whichever has the higher WCAG contrast ratio on this color.
+    pub fn text_color(&self) -> Color {
+        let (black, white) = (Color::new(0, 0, 0), Color::new(255, 255, 255));
+        if contrast_ratio(self, &white) > contrast_ratio(self, &black) {
+            white
+        } else {
+            black
+        }
+    }
+
+    /// The color closest to this one, mixed towards white or black (away from `text`),
+    /// on which `text` reaches the contrast `ratio`, e.g. `WCAG_AA`.
+    pub fn with_contrast(&self, text: &Color, ratio: f64) -> Color {
+        let target = if text.relative_luminance() < 0.18 {
+            Color::new(255, 255, 255)
+        } else {
+            Color::new(0, 0, 0)
+        };
+        (0..=100)
+            .map(|step| self.mix(&target, step as f64 / 100.0))
+            .find(|c| contrast_ratio(c, text) >= ratio)
+            .unwrap_or(target)
+    }
+
+    /// Color seen when this one is drawn with opacity `alpha` on a white background.
+    pub(crate) fn over_white(&self, alpha: f64) -> Color {
+        let blend = |c: u8| (c as f64 * alpha + 255.0 * (1.0 - alpha)).round() as u8;
+        Color::new(blend(self.r), blend(self.g), blend(self.b))
+    }
+}
+
+/// Color with opacity, rendered as CSS `rgba()`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Rgba {
+    pub color: Color,
+    pub alpha: f64,
+}
+
+impl Rgba {
+    /// Opaque color seen when this one is drawn on a white background.
+    pub fn over_white(&self) -> Color {
+        self.color.over_white(self.alpha)
+    }
+}
+
+impl Display for Rgba {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.color.to_rgba(self.alpha))
+    }
 }
 
 impl TryFrom<&str> for Color {
@@ -75,48 +278,257 @@ impl TryFrom<&str> for Color {
         if let Ok(rgb) = Color::from_rgb(s) {
             return Ok(rgb);
         }
-        match s {
-            "red" => Ok(Color::new(255, 0, 0)),
-            "green" => Ok(Color::new(0, 255, 0)),
-            "blue" => Ok(Color::new(0, 0, 255)),
-            "yellow" => Ok(Color::new(255, 255, 0)),
-            "cyan" => Ok(Color::new(0, 255, 255)),
-            "magenta" => Ok(Color::new(255, 0, 255)),
-            "black" => Ok(Color::new(0, 0, 0)),
-            "white" => Ok(Color::new(255, 255, 255)),
-            _ => Err(Error),
+        if let Ok(hsl) = Color::from_hsl_str(s) {
+            return Ok(hsl);
         }
+        Color::from_name(s).ok_or(Error)
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Error> {
+        Color::try_from(s.as_str())
+    }
+}
+
+impl From<Color> for String {
+    fn from(c: Color) -> Self {
+        c.to_hex()
     }
 }
 
+/// Color space in which gradients are interpolated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorSpace {
+    /// Channel-wise in sRGB; cheap but midpoints look muddy and darker.
+    #[default]
+    Srgb,
+    /// Perceptually uniform Oklab.
+    Oklab,
+    /// Polar CIELAB (lightness, chroma, hue), along the shorter hue arc.
+    Hcl,
+}
+
+/// WCAG 2 AA minimum contrast ratio for normal-size text.
+pub const WCAG_AA: f64 = 4.5;
+
+/// WCAG 2 contrast ratio between two colors, from 1 (same luminance) to 21 (black on white).
+pub fn contrast_ratio(a: &Color, b: &Color) -> f64 {
+    let (la, lb) = (a.relative_luminance(), b.relative_luminance());
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// CSS named colors, sorted by name.
+const CSS_NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
 #[derive(PartialEq, Debug)]
 pub struct Gradient {
     start: Color,
     end: Color,
+    space: ColorSpace,
 }
 
 impl Gradient {
     pub fn new(start: Color, end: Color) -> Self {
-        Gradient { start, end }
+        Gradient {
+            start,
+            end,
+            space: ColorSpace::Srgb,
+        }
+    }
+
+    pub fn with_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
     }
 
     pub fn interpolate(&self, a: f64) -> Result<Color, Error> {
         if !(0.0..=1.0).contains(&a) {
             return Err(Error);
         }
-        let r = interpolate(self.start.r, self.end.r, a);
-        let g = interpolate(self.start.g, self.end.g, a);
-        let b = interpolate(self.start.b, self.end.b, a);
-        Ok(Color::new(r, g, b))
+        match self.space {
+            ColorSpace::Srgb => {
+                let r = interpolate(self.start.r, self.end.r, a);
+                let g = interpolate(self.start.g, self.end.g, a);
+                let b = interpolate(self.start.b, self.end.b, a);
+                Ok(Color::new(r, g, b))
+            }
+            ColorSpace::Oklab => {
+                let (x, y) = (to_oklab(&self.start), to_oklab(&self.end));
+                Ok(from_oklab(lerp3(x, y, a)))
+            }
+            ColorSpace::Hcl => {
+                let (x, y) = (to_hcl(&self.start), to_hcl(&self.end));
+                Ok(from_hcl(lerp_hcl(x, y, a)))
+            }
+        }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ColorBreakPoint {
     value: f64,
     color: Color,
 }
 
+impl ColorBreakPoint {
+    pub fn new(value: f64, color: Color) -> Self {
+        ColorBreakPoint { value, color }
+    }
+}
+
 impl PartialEq<Self> for ColorBreakPoint {
     fn eq(&self, other: &Self) -> bool {
         self.value.eq(&other.value)
@@ -129,58 +541,536 @@ impl PartialOrd for ColorBreakPoint {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawColorMap", into = "RawColorMap")]
 pub struct ColorMap {
     v: Vec<ColorBreakPoint>,
+    out_of_range: OutOfRange,
+    space: ColorSpace,
+}
+
+/// What `ColorMap::get` returns for values outside the first and last break points.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutOfRange {
+    /// The color of the nearest end.
+    #[default]
+    Clamp,
+    /// An error.
+    Error,
+    /// Dedicated colors below and above the range.
+    Colors { under: Color, over: Color },
+}
+
+/// Why break points don't make a valid `ColorMap`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColorMapError {
+    Empty,
+    /// Values must be in increasing order.
+    NotSorted,
+    /// Two break points have the same value.
+    Duplicate(f64),
+    /// Values must be finite and within `0..=1`.
+    OutOfDomain(f64),
+}
+
+impl Display for ColorMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorMapError::Empty => write!(f, "color map has no break points"),
+            ColorMapError::NotSorted => write!(f, "color map break points are not sorted"),
+            ColorMapError::Duplicate(v) => write!(f, "duplicate color map break point {}", v),
+            ColorMapError::OutOfDomain(v) => {
+                write!(f, "color map break point {} is outside 0..=1", v)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColorMapError {}
+
+/// Serialized form of `ColorMap`, validated when deserialized.
+#[derive(Serialize, Deserialize)]
+struct RawColorMap {
+    points: Vec<ColorBreakPoint>,
+    #[serde(default, skip_serializing_if = "is_clamp")]
+    out_of_range: OutOfRange,
+    #[serde(default, skip_serializing_if = "is_srgb")]
+    space: ColorSpace,
+}
+
+fn is_srgb(space: &ColorSpace) -> bool {
+    space == &ColorSpace::Srgb
+}
+
+fn is_clamp(out_of_range: &OutOfRange) -> bool {
+    out_of_range == &OutOfRange::Clamp
+}
+
+impl TryFrom<RawColorMap> for ColorMap {
+    type Error = ColorMapError;
+
+    fn try_from(raw: RawColorMap) -> Result<Self, Self::Error> {
+        Ok(ColorMap::try_new(raw.points)?
+            .with_out_of_range(raw.out_of_range)
+            .with_space(raw.space))
+    }
+}
+
+impl From<ColorMap> for RawColorMap {
+    fn from(cmap: ColorMap) -> Self {
+        RawColorMap {
+            points: cmap.v,
+            out_of_range: cmap.out_of_range,
+            space: cmap.space,
+        }
+    }
+}
+
+/// Collects break points of a `ColorMap`, validated by `build`.
+#[derive(Clone, Debug, Default)]
+pub struct ColorMapBuilder {
+    v: Vec<ColorBreakPoint>,
+    out_of_range: OutOfRange,
+}
+
+impl ColorMapBuilder {
+    pub fn point(mut self, value: f64, color: Color) -> Self {
+        self.v.push(ColorBreakPoint::new(value, color));
+        self
+    }
+
+    pub fn out_of_range(mut self, out_of_range: OutOfRange) -> Self {
+        self.out_of_range = out_of_range;
+        self
+    }
+
+    pub fn build(self) -> Result<ColorMap, ColorMapError> {
+        Ok(ColorMap::try_new(self.v)?.with_out_of_range(self.out_of_range))
+    }
 }
 
 impl ColorMap {
+    /// Creates a color map without validating the break points; see `try_new`.
     pub fn new(v: Vec<ColorBreakPoint>) -> Self {
-        // TODO: check that v is sorted
-        ColorMap { v }
+        ColorMap {
+            v,
+            out_of_range: OutOfRange::Clamp,
+            space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a color map from break points with unique values in `0..=1`, in increasing order.
+    pub fn try_new(v: Vec<ColorBreakPoint>) -> Result<Self, ColorMapError> {
+        if v.is_empty() {
+            return Err(ColorMapError::Empty);
+        }
+        if let Some(p) = v.iter().find(|p| !(0.0..=1.0).contains(&p.value)) {
+            return Err(ColorMapError::OutOfDomain(p.value));
+        }
+        for (left, right) in v.iter().tuple_windows() {
+            if left.value == right.value {
+                return Err(ColorMapError::Duplicate(left.value));
+            }
+            if left.value > right.value {
+                return Err(ColorMapError::NotSorted);
+            }
+        }
+        Ok(ColorMap::new(v))
+    }
+
+    pub fn builder() -> ColorMapBuilder {
+        ColorMapBuilder::default()
+    }
+
+    pub fn with_out_of_range(mut self, out_of_range: OutOfRange) -> Self {
+        self.out_of_range = out_of_range;
+        self
+    }
+
+    /// Interpolates between break points in `space` instead of sRGB.
+    pub fn with_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Built-in colormap by its matplotlib / ColorBrewer name (case-insensitive),
+    /// e.g. `viridis` or `RdYlGn`; a `_r` suffix reverses it. See `ColorMap::names`.
+    pub fn named(name: &str) -> Option<Self> {
+        if let Some(name) = name.strip_suffix("_r") {
+            return ColorMap::named(name).map(|cmap| cmap.reversed());
+        }
+        let (_, colors) = NAMED_COLORMAPS
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))?;
+        let colors = colors.iter().map(|c| Color::from_hex(c).unwrap()).collect();
+        Some(ColorMap::from_palette(colors))
+    }
+
+    /// Names accepted by `ColorMap::named`.
+    pub fn names() -> Vec<&'static str> {
+        NAMED_COLORMAPS.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// The same colors in the opposite direction, e.g. for "higher is worse" metrics;
+    /// dedicated colors for values out of range swap too.
+    pub fn reversed(&self) -> Self {
+        let v = self
+            .v
+            .iter()
+            .rev()
+            .map(|p| ColorBreakPoint {
+                value: 1.0 - p.value,
+                color: p.color.clone(),
+            })
+            .collect();
+        let out_of_range = match &self.out_of_range {
+            OutOfRange::Colors { under, over } => OutOfRange::Colors {
+                under: over.clone(),
+                over: under.clone(),
+            },
+            out_of_range => out_of_range.clone(),
+        };
+        ColorMap::new(v)
+            .with_space(self.space)
+            .with_out_of_range(out_of_range)
+    }
+
+    /// Colormap of `n` evenly spaced samples of this one; its `palette` can be used
+    /// with `background_bins`. `n` is at least 1, so that the colormap isn't empty.
+    /// Samples outside the break points take the color of the nearest end.
+    pub fn resampled(&self, n: usize) -> Self {
+        let n = n.max(1);
+        let (first, last) = match (self.v.first(), self.v.last()) {
+            (Some(first), Some(last)) => (first.value, last.value),
+            _ => return self.clone(),
+        };
+        let colors = (0..n)
+            .map(|i| {
+                let value = if n > 1 {
+                    i as f64 / (n - 1) as f64
+                } else {
+                    0.5
+                };
+                self.get(value.clamp(first, last)).unwrap()
+            })
+            .collect();
+        ColorMap::from_palette(colors)
+            .with_space(self.space)
+            .with_out_of_range(self.out_of_range.clone())
+    }
+
+    /// Colors of the break points.
+    pub fn palette(&self) -> Vec<Color> {
+        self.v.iter().map(|p| p.color.clone()).collect()
+    }
+
+    /// CSS `linear-gradient` color stops, e.g. for a legend.
+    pub fn css_stops(&self) -> Vec<String> {
+        self.css_stops_with_opacity(1.0)
+    }
+
+    /// Like `css_stops`, with `rgba()` colors when `opacity` is below 1.
+    pub fn css_stops_with_opacity(&self, opacity: f64) -> Vec<String> {
+        self.v
+            .iter()
+            .map(|p| {
+                let color = if opacity < 1.0 {
+                    p.color.to_rgba(opacity)
+                } else {
+                    p.color.to_hex()
+                };
+                format!("{} {}%", color, p.value * 100.0)
+            })
+            .collect()
+    }
+
+    /// Heuristic: no pair of break-point colors loses more than three quarters of its
+    /// perceptual (Oklab) difference under simulated protanopia, deuteranopia or
+    /// tritanopia. The `okabe_ito`, `viridis`, `cividis` and ColorBrewer diverging
+    /// presets such as `RdBu` and `PuOr` pass; `RdYlGn` does not.
+    pub fn is_colorblind_safe(&self) -> bool {
+        self.palette().iter().tuple_combinations().all(|(x, y)| {
+            let distance = oklab_distance(x, y);
+            CVD_MATRICES.iter().all(|m| {
+                oklab_distance(&simulate_cvd(x, m), &simulate_cvd(y, m)) >= 0.25 * distance
+            })
+        })
     }
 
     pub fn red_scale() -> Self {
         ColorMap::from_palette(vec![Color::new(255, 255, 255), Color::new(255, 0, 0)])
     }
 
+    /// Excel-style scale from `low` through `mid` (at 0.5) to `high`.
+    pub fn three_point(low: Color, mid: Color, high: Color) -> Self {
+        ColorMap::from_palette(vec![low, mid, high])
+    }
+
     pub fn from_palette(colors: Vec<Color>) -> Self {
         let n = colors.len();
         let v = colors
             .iter()
             .enumerate()
             .map(|(i, c)| ColorBreakPoint {
-                value: i as f64 / (n - 1) as f64,
+                value: if n > 1 {
+                    i as f64 / (n - 1) as f64
+                } else {
+                    0.0
+                },
                 color: c.clone(),
             })
             .collect();
         ColorMap::new(v)
     }
 
+    /// Color of the value; values outside the break points follow the `OutOfRange` policy.
+    /// A colormap without break points, e.g. from an empty palette, has no colors.
     pub fn get(&self, value: f64) -> Result<Color, Error> {
-        if value < self.v[0].value {
-            // TODO: maybe throw an error?
-            return Ok(self.v[0].color.clone());
+        let (Some(first), Some(last)) = (self.v.first(), self.v.last()) else {
+            return Err(Error);
+        };
+        if value < first.value || value > last.value {
+            return match &self.out_of_range {
+                OutOfRange::Clamp if value < first.value => Ok(first.color.clone()),
+                OutOfRange::Clamp => Ok(last.color.clone()),
+                OutOfRange::Error => Err(Error),
+                OutOfRange::Colors { under, .. } if value < first.value => Ok(under.clone()),
+                OutOfRange::Colors { over, .. } => Ok(over.clone()),
+            };
         }
-        for (left, right) in self.v.iter().tuples() {
+        for (left, right) in self.v.iter().tuple_windows() {
             if value == left.value {
                 return Ok(left.color.clone());
             }
             if value < right.value {
                 let a = (value - left.value) / (right.value - left.value);
-                let gradient = Gradient::new(left.color.clone(), right.color.clone());
+                let gradient =
+                    Gradient::new(left.color.clone(), right.color.clone()).with_space(self.space);
                 return gradient.interpolate(a);
             }
         }
-        let n = self.v.len();
-        Ok(self.v[n - 1].color.clone())
+        Ok(last.color.clone())
     }
 }
 
+/// Evenly spaced samples of common matplotlib and ColorBrewer colormaps.
+const NAMED_COLORMAPS: &[(&str, &[&str])] = &[
+    (
+        "viridis",
+        &[
+            "#440154", "#472d7b", "#3b528b", "#2c728e", "#21918c", "#28ae80", "#5ec962", "#addc30",
+            "#fde725",
+        ],
+    ),
+    (
+        "plasma",
+        &[
+            "#0d0887", "#4c02a1", "#7e03a8", "#a92395", "#cc4778", "#e56b5d", "#f89540", "#fdc527",
+            "#f0f921",
+        ],
+    ),
+    (
+        "inferno",
+        &[
+            "#000004", "#1f0c48", "#550f6d", "#88226a", "#ba3655", "#e35933", "#f98e09", "#f9cb35",
+            "#fcffa4",
+        ],
+    ),
+    (
+        "magma",
+        &[
+            "#000004", "#1c1044", "#4f127b", "#812581", "#b5367a", "#e55064", "#fb8761", "#fec287",
+            "#fcfdbf",
+        ],
+    ),
+    (
+        "cividis",
+        &[
+            "#00224e", "#123570", "#3b496c", "#575d6d", "#707173", "#8a8779", "#a69d75", "#c4b56c",
+            "#e4cf5b",
+        ],
+    ),
+    (
+        "coolwarm",
+        &[
+            "#3b4cc0", "#5977e3", "#7b9ff9", "#9ebeff", "#c0d4f5", "#dddcdc", "#f2cbb7", "#f7ac8e",
+            "#ee8468", "#d65244", "#b40426",
+        ],
+    ),
+    (
+        "RdYlGn",
+        &[
+            "#a50026", "#d73027", "#f46d43", "#fdae61", "#fee08b", "#ffffbf", "#d9ef8b", "#a6d96a",
+            "#66bd63", "#1a9850", "#006837",
+        ],
+    ),
+    (
+        "RdBu",
+        &[
+            "#67001f", "#b2182b", "#d6604d", "#f4a582", "#fddbc7", "#f7f7f7", "#d1e5f0", "#92c5de",
+            "#4393c3", "#2166ac", "#053061",
+        ],
+    ),
+    (
+        "Spectral",
+        &[
+            "#9e0142", "#d53e4f", "#f46d43", "#fdae61", "#fee08b", "#ffffbf", "#e6f598", "#abdda4",
+            "#66c2a5", "#3288bd", "#5e4fa2",
+        ],
+    ),
+    (
+        "Blues",
+        &[
+            "#f7fbff", "#deebf7", "#c6dbef", "#9ecae1", "#6baed6", "#4292c6", "#2171b5", "#08519c",
+            "#08306b",
+        ],
+    ),
+    (
+        "PuBu",
+        &[
+            "#fff7fb", "#ece7f2", "#d0d1e6", "#a6bddb", "#74a9cf", "#3690c0", "#0570b0", "#045a8d",
+            "#023858",
+        ],
+    ),
+    (
+        "Greens",
+        &[
+            "#f7fcf5", "#e5f5e0", "#c7e9c0", "#a1d99b", "#74c476", "#41ab5d", "#238b45", "#006d2c",
+            "#00441b",
+        ],
+    ),
+    (
+        "Reds",
+        &[
+            "#fff5f0", "#fee0d2", "#fcbba1", "#fc9272", "#fb6a4a", "#ef3b2c", "#cb181d", "#a50f15",
+            "#67000d",
+        ],
+    ),
+    (
+        "Greys",
+        &[
+            "#ffffff", "#f0f0f0", "#d9d9d9", "#bdbdbd", "#969696", "#737373", "#525252", "#252525",
+            "#000000",
+        ],
+    ),
+    (
+        "YlOrRd",
+        &[
+            "#ffffcc", "#ffeda0", "#fed976", "#feb24c", "#fd8d3c", "#fc4e2a", "#e31a1c", "#bd0026",
+            "#800026",
+        ],
+    ),
+    // colorblind-safe presets
+    (
+        "okabe_ito",
+        &[
+            "#e69f00", "#56b4e9", "#009e73", "#f0e442", "#0072b2", "#d55e00", "#cc79a7", "#000000",
+        ],
+    ),
+    (
+        "RdYlBu",
+        &[
+            "#a50026", "#d73027", "#f46d43", "#fdae61", "#fee090", "#ffffbf", "#e0f3f8", "#abd9e9",
+            "#74add1", "#4575b4", "#313695",
+        ],
+    ),
+    (
+        "PuOr",
+        &[
+            "#7f3b08", "#b35806", "#e08214", "#fdb863", "#fee0b6", "#f7f7f7", "#d8daeb", "#b2abd2",
+            "#8073ac", "#542788", "#2d004b",
+        ],
+    ),
+    (
+        "BrBG",
+        &[
+            "#543005", "#8c510a", "#bf812d", "#dfc27d", "#f6e8c3", "#f5f5f5", "#c7eae5", "#80cdc1",
+            "#35978f", "#01665e", "#003c30",
+        ],
+    ),
+    (
+        "PiYG",
+        &[
+            "#8e0152", "#c51b7d", "#de77ae", "#f1b6da", "#fde0ef", "#f7f7f7", "#e6f5d0", "#b8e186",
+            "#7fbc41", "#4d9221", "#276419",
+        ],
+    ),
+];
+
+/// Machado et al. (2009) simulation of protanopia, deuteranopia and tritanopia in linear RGB.
+const CVD_MATRICES: [[[f64; 3]; 3]; 3] = [
+    [
+        [0.152286, 1.052583, -0.204868],
+        [0.114503, 0.786281, 0.099216],
+        [-0.003882, -0.048116, 1.051998],
+    ],
+    [
+        [0.367322, 0.860646, -0.227968],
+        [0.280085, 0.672501, 0.047413],
+        [-0.011820, 0.042940, 0.968881],
+    ],
+    [
+        [1.255528, -0.076749, -0.178779],
+        [-0.078411, 0.930809, 0.147602],
+        [0.004733, 0.691367, 0.303900],
+    ],
+];
+
+fn simulate_cvd(c: &Color, m: &[[f64; 3]; 3]) -> Color {
+    let rgb = linear_rgb(c);
+    from_linear_rgb(m.map(|row| row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]))
+}
+
+fn oklab_distance(x: &Color, y: &Color) -> f64 {
+    let (x, y) = (to_oklab(x), to_oklab(y));
+    ((x[0] - y[0]).powi(2) + (x[1] - y[1]).powi(2) + (x[2] - y[2]).powi(2)).sqrt()
+}
+
 fn interpolate(x: u8, y: u8, a: f64) -> u8 {
     (x as f64 * (1.0 - a) + y as f64 * a).round() as u8
 }
 
+/// Splits a CSS color function such as `rgba(1, 2, 3, 0.5)` or `rgb(1 2 3 / 50%)`
+/// into its lowercase name and three channel arguments. `rgb` and `hsl` take no
+/// alpha in the comma-separated form, `rgba` and `hsla` require it; a given alpha
+/// must lie in `0..=1` (or `0%..=100%`).
+fn css_function(s: &str) -> Option<(String, Vec<&str>)> {
+    let s = s.trim();
+    let open = s.find('(')?;
+    let name = s[..open].trim().to_ascii_lowercase();
+    let args = s[open + 1..].strip_suffix(')')?.trim();
+    let (channels, alpha): (Vec<&str>, Option<&str>) = if args.contains(',') {
+        let mut parts: Vec<&str> = args.split(',').map(str::trim).collect();
+        let alpha = match (name.ends_with('a'), parts.len()) {
+            (false, 3) => None,
+            (true, 4) => parts.pop(),
+            _ => return None,
+        };
+        (parts, alpha)
+    } else {
+        let (channels, alpha) = match args.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (args, None),
+        };
+        let channels: Vec<&str> = channels.split_whitespace().collect();
+        if channels.len() != 3 {
+            return None;
+        }
+        (channels, alpha)
+    };
+    if let Some(alpha) = alpha {
+        let a = match alpha.strip_suffix('%') {
+            Some(p) => p.parse::<f64>().ok()? / 100.0,
+            None => alpha.parse::<f64>().ok()?,
+        };
+        if !(0.0..=1.0).contains(&a) {
+            return None;
+        }
+    }
+    Some((name, channels))
+}
+
 fn normalize_channel(x: u8) -> f64 {
     let x = x as f64 / 255.0;
     if x <= 0.04045 {
@@ -189,11 +1079,169 @@ fn normalize_channel(x: u8) -> f64 {
     ((x + 0.055) / 1.055).pow(2.4)
 }
 
+fn encode_channel(x: f64) -> u8 {
+    let x = x.clamp(0.0, 1.0);
+    let x = if x <= 0.0031308 {
+        x * 12.92
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    };
+    (x * 255.0).round() as u8
+}
+
+fn linear_rgb(c: &Color) -> [f64; 3] {
+    [c.r, c.g, c.b].map(normalize_channel)
+}
+
+fn from_linear_rgb([r, g, b]: [f64; 3]) -> Color {
+    Color::new(encode_channel(r), encode_channel(g), encode_channel(b))
+}
+
+fn lerp3(x: [f64; 3], y: [f64; 3], a: f64) -> [f64; 3] {
+    [0, 1, 2].map(|i| x[i] * (1.0 - a) + y[i] * a)
+}
+
+fn to_oklab(c: &Color) -> [f64; 3] {
+    let [r, g, b] = linear_rgb(c);
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+fn from_oklab([l, a, b]: [f64; 3]) -> Color {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    from_linear_rgb([
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    ])
+}
+
+/// D65 reference white in CIE XYZ.
+const WHITE_XYZ: [f64; 3] = [0.95047, 1.0, 1.08883];
+
+fn to_hcl(c: &Color) -> [f64; 3] {
+    let [r, g, b] = linear_rgb(c);
+    let xyz = [
+        0.4124564 * r + 0.3575761 * g + 0.1804375 * b,
+        0.2126729 * r + 0.7151522 * g + 0.0721750 * b,
+        0.0193339 * r + 0.1191920 * g + 0.9503041 * b,
+    ];
+    let f = |i: usize| {
+        let t = xyz[i] / WHITE_XYZ[i];
+        if t > (6.0_f64 / 29.0).powi(3) {
+            t.cbrt()
+        } else {
+            t / (3.0 * (6.0_f64 / 29.0).powi(2)) + 4.0 / 29.0
+        }
+    };
+    let (fx, fy, fz) = (f(0), f(1), f(2));
+    let (a, b) = (500.0 * (fx - fy), 200.0 * (fy - fz));
+    [
+        116.0 * fy - 16.0,
+        a.hypot(b),
+        b.atan2(a).to_degrees().rem_euclid(360.0),
+    ]
+}
+
+fn from_hcl([l, c, h]: [f64; 3]) -> Color {
+    let h = h.to_radians();
+    let fy = (l + 16.0) / 116.0;
+    let f = [fy + c * h.cos() / 500.0, fy, fy - c * h.sin() / 200.0];
+    let [x, y, z] = [0, 1, 2].map(|i| {
+        let t = if f[i] > 6.0 / 29.0 {
+            f[i].powi(3)
+        } else {
+            3.0 * (6.0_f64 / 29.0).powi(2) * (f[i] - 4.0 / 29.0)
+        };
+        t * WHITE_XYZ[i]
+    });
+    from_linear_rgb([
+        3.2404542 * x - 1.5371385 * y - 0.4985314 * z,
+        -0.9692660 * x + 1.8760108 * y + 0.0415560 * z,
+        0.0556434 * x - 0.2040259 * y + 1.0572252 * z,
+    ])
+}
+
+/// Interpolates lightness and chroma linearly and hue along the shorter arc;
+/// the hue of a gray end is taken from the other end.
+fn lerp_hcl(x: [f64; 3], y: [f64; 3], a: f64) -> [f64; 3] {
+    const GRAY: f64 = 1e-6;
+    let (hx, hy) = match (x[1] < GRAY, y[1] < GRAY) {
+        (true, false) => (y[2], y[2]),
+        (false, true) => (x[2], x[2]),
+        _ => (x[2], y[2]),
+    };
+    let dh = (hy - hx + 180.0).rem_euclid(360.0) - 180.0;
+    [
+        x[0] * (1.0 - a) + y[0] * a,
+        x[1] * (1.0 - a) + y[1] * a,
+        (hx + dh * a).rem_euclid(360.0),
+    ]
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use polars::export::num::abs;
 
+    #[test]
+    fn test_text_color() {
+        assert_eq!(
+            Color::new(0, 0, 128).text_color(),
+            Color::new(255, 255, 255)
+        );
+        assert_eq!(Color::new(255, 255, 0).text_color(), Color::new(0, 0, 0));
+        let black = Color::new(0, 0, 0);
+        assert_eq!(black.over_white(0.1).text_color(), Color::new(0, 0, 0));
+        assert_eq!(
+            black.over_white(0.9).text_color(),
+            Color::new(255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let (black, white) = (Color::new(0, 0, 0), Color::new(255, 255, 255));
+        assert!((contrast_ratio(&black, &white) - 21.0).abs() < 1e-9);
+        assert_eq!(
+            contrast_ratio(&white, &black),
+            contrast_ratio(&black, &white)
+        );
+        assert_eq!(contrast_ratio(&black, &black), 1.0);
+        let navy = Color::new(0, 0, 128);
+        let light = navy.with_contrast(&black, WCAG_AA);
+        assert!(contrast_ratio(&light, &black) >= WCAG_AA);
+        assert!(navy.with_contrast(&white, WCAG_AA) == navy);
+        let yellow = Color::new(255, 255, 0);
+        assert!(contrast_ratio(&yellow.with_contrast(&white, WCAG_AA), &white) >= WCAG_AA);
+    }
+
+    #[test]
+    fn test_manipulation() {
+        let steel = Color::new(70, 130, 180);
+        let (white, black) = (Color::new(255, 255, 255), Color::new(0, 0, 0));
+        assert_eq!(steel.lighten(1.0), white);
+        assert_eq!(steel.darken(1.0), black);
+        assert_eq!(steel.lighten(0.0), steel);
+        assert!(steel.lighten(0.3).relative_luminance() > steel.relative_luminance());
+        assert!(steel.darken(0.3).relative_luminance() < steel.relative_luminance());
+        assert_eq!(black.mix(&white, 0.5), Color::new(128, 128, 128));
+        let gray = steel.desaturate(1.0);
+        assert!(gray.r == gray.g && gray.g == gray.b);
+        assert!(steel.saturate(0.5).to_hsl().1 > steel.to_hsl().1);
+        let hover = steel.with_alpha(0.25);
+        assert_eq!(hover.to_string(), "rgba(70, 130, 180, 0.25)");
+        assert_eq!(hover.over_white(), steel.mix(&white, 0.75));
+    }
+
     #[test]
     fn test_color_csv() {
         let c = Color::new(110, 50, 0);
@@ -244,6 +1292,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_from_hex_short() {
+        assert_eq!(Color::from_hex("#abc"), Ok(Color::new(0xaa, 0xbb, 0xcc)));
+        assert_eq!(Color::from_hex("#6E3200ff"), Ok(Color::new(110, 50, 0)));
+        assert!(Color::from_hex("6e3200").is_err());
+        assert!(Color::from_hex("#6e32").is_ok());
+        assert!(Color::from_hex("#6e320").is_err());
+        assert!(Color::from_hex("#ééé").is_err());
+    }
+
+    #[test]
+    fn test_from_rgb_variants() {
+        let c = Color::new(110, 50, 0);
+        for s in [
+            "rgb(110,50,0)",
+            " RGB( 110 , 50 , 0 ) ",
+            "rgb(110 50 0)",
+            "rgb(110 50 0 / 50%)",
+            "rgba(110, 50, 0, 0.5)",
+            "rgba(110 50 0 / .5)",
+        ] {
+            assert_eq!(Color::try_from(s), Ok(c.clone()), "{}", s);
+        }
+        assert_eq!(
+            Color::from_rgb("rgb(100%, 50%, 0%)"),
+            Ok(Color::new(255, 128, 0))
+        );
+        assert!(Color::from_rgb("rgba(110, 50, 0)").is_err());
+        assert!(Color::from_rgb("rgba(110, 50, 0, 2)").is_err());
+        assert!(Color::from_rgb("rgb(256, 50, 0)").is_err());
+    }
+
     #[test]
     fn test_from_rgb_fail() {
         assert!(Color::from_rgb("110, 50, 0").is_err());
@@ -252,6 +1332,45 @@ mod test {
         assert!(Color::from_rgb("#6e3200").is_err());
     }
 
+    #[test]
+    fn test_named_colors() {
+        assert_eq!(Color::try_from("steelblue"), Ok(Color::new(70, 130, 180)));
+        assert_eq!(
+            Color::try_from("RebeccaPurple"),
+            Ok(Color::new(102, 51, 153))
+        );
+        assert_eq!(Color::try_from("green"), Ok(Color::new(0, 128, 0)));
+        assert!(Color::try_from("notacolor").is_err());
+        assert!(CSS_NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_hsl() {
+        assert_eq!(Color::from_hsl(0.0, 1.0, 0.5), Color::new(255, 0, 0));
+        assert_eq!(Color::from_hsl(240.0, 1.0, 0.5), Color::new(0, 0, 255));
+        assert_eq!(Color::from_hsl(0.0, 0.0, 1.0), Color::new(255, 255, 255));
+        let steel = Color::new(70, 130, 180);
+        let (h, s, l) = steel.to_hsl();
+        assert_eq!(Color::from_hsl(h, s, l), steel);
+        assert_eq!(steel.to_hsl_string(), "hsl(207, 44%, 49%)");
+    }
+
+    #[test]
+    fn test_from_hsl_str() {
+        let green = Color::new(0, 255, 0);
+        assert_eq!(
+            Color::from_hsl_str("hsl(120, 100%, 50%)"),
+            Ok(green.clone())
+        );
+        assert_eq!(
+            Color::from_hsl_str("hsla(120deg,100%,50%, 0.5)"),
+            Ok(green.clone())
+        );
+        assert_eq!(Color::try_from("hsl(120, 100%, 50%)"), Ok(green));
+        assert!(Color::from_hsl_str("hsl(120, 100, 50)").is_err());
+        assert!(Color::from_hsl_str("hsl(120, 150%, 50%)").is_err());
+    }
+
     #[test]
     fn test_interpolate_color() {
         let gradient = Gradient::new(Color::new(0, 0, 0), Color::new(10, 10, 10));
@@ -269,4 +1388,146 @@ mod test {
         assert_eq!(gradient.interpolate(0.0), Ok(start));
         assert_eq!(gradient.interpolate(1.0), Ok(end));
     }
+
+    #[test]
+    fn test_interpolate_space() {
+        let (red, blue) = (Color::new(255, 0, 0), Color::new(0, 0, 255));
+        for space in [ColorSpace::Srgb, ColorSpace::Oklab, ColorSpace::Hcl] {
+            let gradient = Gradient::new(red.clone(), blue.clone()).with_space(space);
+            assert_eq!(gradient.interpolate(0.0), Ok(red.clone()));
+            assert_eq!(gradient.interpolate(1.0), Ok(blue.clone()));
+        }
+        let mid = |space| {
+            let gradient = Gradient::new(red.clone(), blue.clone()).with_space(space);
+            gradient.interpolate(0.5).unwrap().relative_luminance()
+        };
+        // sRGB midpoints are darker than either end in perceptual terms
+        assert!(mid(ColorSpace::Oklab) > mid(ColorSpace::Srgb));
+        assert!(mid(ColorSpace::Hcl) > mid(ColorSpace::Srgb));
+    }
+
+    #[test]
+    fn test_colormap_space_serde() {
+        let cmap = ColorMap::red_scale().with_space(ColorSpace::Oklab);
+        let json = serde_json::to_string(&cmap).unwrap();
+        assert!(json.contains(r#""space":"oklab""#));
+        assert_eq!(serde_json::from_str::<ColorMap>(&json).unwrap(), cmap);
+        let plain = serde_json::to_string(&ColorMap::red_scale()).unwrap();
+        assert!(!plain.contains("space"));
+    }
+
+    #[test]
+    fn test_three_point() {
+        let cmap = ColorMap::three_point(
+            Color::new(255, 0, 0),
+            Color::new(255, 255, 255),
+            Color::new(0, 0, 255),
+        );
+        assert_eq!(cmap.get(0.25).unwrap(), Color::new(255, 128, 128));
+        assert_eq!(cmap.get(0.5).unwrap(), Color::new(255, 255, 255));
+        assert_eq!(cmap.get(0.75).unwrap(), Color::new(128, 128, 255));
+    }
+
+    #[test]
+    fn test_named() {
+        let viridis = ColorMap::named("Viridis").unwrap();
+        assert_eq!(viridis.get(0.0).unwrap(), Color::new(0x44, 0x01, 0x54));
+        assert_eq!(viridis.get(1.0).unwrap(), Color::new(0xfd, 0xe7, 0x25));
+        assert!(ColorMap::named("nope").is_none());
+        assert!(ColorMap::names()
+            .iter()
+            .all(|name| ColorMap::named(name).is_some()));
+    }
+
+    #[test]
+    fn test_is_colorblind_safe() {
+        for name in ["okabe_ito", "viridis", "cividis", "RdBu", "PuOr", "RdYlBu"] {
+            assert!(
+                ColorMap::named(name).unwrap().is_colorblind_safe(),
+                "{}",
+                name
+            );
+        }
+        assert!(!ColorMap::named("RdYlGn").unwrap().is_colorblind_safe());
+        let red_green = ColorMap::from_palette(vec![Color::new(200, 0, 0), Color::new(0, 140, 0)]);
+        assert!(!red_green.is_colorblind_safe());
+    }
+
+    #[test]
+    fn test_reversed_resampled() {
+        let (red, blue) = (Color::new(255, 0, 0), Color::new(0, 0, 255));
+        let cmap = ColorMap::from_palette(vec![red.clone(), blue.clone()]);
+        assert_eq!(cmap.reversed().get(0.0).unwrap(), blue);
+        assert_eq!(
+            ColorMap::named("Greys_r").unwrap().get(0.0).unwrap(),
+            Color::new(0, 0, 0)
+        );
+        let palette = cmap.resampled(3).palette();
+        assert_eq!(palette, vec![red, Color::new(128, 0, 128), blue]);
+        assert_eq!(cmap.resampled(1).palette(), vec![Color::new(128, 0, 128)]);
+        let clamped = cmap.resampled(0);
+        assert_eq!(clamped.palette(), vec![Color::new(128, 0, 128)]);
+        assert_eq!(clamped.get(0.7).unwrap(), Color::new(128, 0, 128));
+    }
+
+    #[test]
+    fn test_builder() {
+        let (red, blue) = (Color::new(255, 0, 0), Color::new(0, 0, 255));
+        let builder = ColorMap::builder().point(0.2, red.clone());
+        let cmap = builder.clone().point(0.8, blue.clone()).build().unwrap();
+        assert_eq!(cmap.get(0.0).unwrap(), red);
+        assert_eq!(
+            builder.clone().point(0.1, blue.clone()).build(),
+            Err(ColorMapError::NotSorted)
+        );
+        assert_eq!(
+            builder.clone().point(0.2, blue.clone()).build(),
+            Err(ColorMapError::Duplicate(0.2))
+        );
+        assert_eq!(
+            builder.clone().point(1.5, blue.clone()).build(),
+            Err(ColorMapError::OutOfDomain(1.5))
+        );
+        assert_eq!(ColorMap::builder().build(), Err(ColorMapError::Empty));
+        let empty = ColorMap::from_palette(vec![]);
+        assert!(empty.get(0.5).is_err());
+        assert!(empty.resampled(3).get(0.5).is_err());
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let (red, blue) = (Color::new(255, 0, 0), Color::new(0, 0, 255));
+        let cmap = ColorMap::builder()
+            .point(0.2, red.clone())
+            .point(0.8, blue.clone())
+            .out_of_range(OutOfRange::Error)
+            .build()
+            .unwrap();
+        assert!(cmap.get(0.1).is_err());
+        assert_eq!(cmap.get(0.8).unwrap(), blue);
+        assert!(cmap.reversed().get(0.1).is_err());
+        let resampled = cmap.resampled(3);
+        assert_eq!(resampled.palette()[0], red);
+        assert_eq!(resampled.palette()[2], blue);
+        assert_eq!(resampled.out_of_range, OutOfRange::Error);
+        let white = Color::new(255, 255, 255);
+        let cmap = cmap.with_out_of_range(OutOfRange::Colors {
+            under: white.clone(),
+            over: white.clone(),
+        });
+        assert_eq!(cmap.get(0.9).unwrap(), white);
+        let black = Color::new(0, 0, 0);
+        let cmap = cmap.with_out_of_range(OutOfRange::Colors {
+            under: white.clone(),
+            over: black.clone(),
+        });
+        let reversed = cmap.reversed();
+        assert_eq!(reversed.get(0.1).unwrap(), black);
+        assert_eq!(reversed.get(0.9).unwrap(), white);
+        let json = serde_json::to_string(&cmap).unwrap();
+        assert_eq!(serde_json::from_str::<ColorMap>(&json).unwrap(), cmap);
+        let unsorted =
+            r##"{"points": [{"value": 1, "color": "red"}, {"value": 0, "color": "red"}]}"##;
+        assert!(serde_json::from_str::<ColorMap>(unsorted).is_err());
+    }
 }
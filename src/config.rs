@@ -0,0 +1,88 @@
+use crate::format::CellFormat;
+use crate::spec::StyleSpec;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+static GLOBAL: RwLock<Option<StylerConfig>> = RwLock::new(None);
+
+/// Defaults shared by the tables of an application: precision, missing value text,
+/// thousands separator, theme and alignment.
+///
+/// Pass it to `Styler::with_config`, or set it once with `set_global` to apply it to
+/// every new styler. Settings made on the styler take precedence over the defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct StylerConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub na_rep: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thousands: Option<String>,
+    /// Spec whose settings (table classes, table styles, caption, ...) are applied;
+    /// its operations are not, as they refer to columns of a particular table.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<StyleSpec>,
+    /// `text-align` of the data cells, e.g. `right`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_align: Option<String>,
+}
+
+impl StylerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn precision(mut self, precision: u32) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn na_rep(mut self, na_rep: &str) -> Self {
+        self.na_rep = Some(na_rep.to_string());
+        self
+    }
+
+    pub fn thousands(mut self, separator: &str) -> Self {
+        self.thousands = Some(separator.to_string());
+        self
+    }
+
+    pub fn theme(mut self, theme: StyleSpec) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub fn text_align(mut self, align: &str) -> Self {
+        self.text_align = Some(align.to_string());
+        self
+    }
+
+    /// Makes the config the default of every styler created afterwards.
+    pub fn set_global(self) {
+        *GLOBAL.write().unwrap() = Some(self);
+    }
+
+    /// Stops applying the global config to new stylers.
+    pub fn clear_global() {
+        *GLOBAL.write().unwrap() = None;
+    }
+
+    pub fn global() -> Option<StylerConfig> {
+        GLOBAL.read().unwrap().clone()
+    }
+
+    /// Format of the values not formatted otherwise; its precision is used only when
+    /// the styler doesn't set one.
+    pub(crate) fn format(&self) -> Option<CellFormat> {
+        if self.precision.is_none() && self.na_rep.is_none() && self.thousands.is_none() {
+            return None;
+        }
+        Some(CellFormat {
+            precision: self.precision,
+            thousands: self.thousands.clone(),
+            na_rep: self.na_rep.clone(),
+            ..CellFormat::default()
+        })
+    }
+}
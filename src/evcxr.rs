@@ -0,0 +1,41 @@
+use crate::styler::Styler;
+
+impl Styler {
+    /// Notebook display in the evcxr Jupyter kernel, which calls it for the value of a
+    /// cell: prints the rendered table, with pandas' `dataframe` class like the Python
+    /// `_repr_html_`, and `describe` as the plain text fallback.
+    pub fn evcxr_display(&self) {
+        print!("{}", self.evcxr_bundle());
+    }
+
+    fn evcxr_bundle(&self) -> String {
+        let html = self
+            .clone()
+            .add_table_classes(vec!["dataframe".to_string()])
+            .render();
+        format!(
+            "EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT\n\
+             EVCXR_BEGIN_CONTENT text/plain\n{}\nEVCXR_END_CONTENT\n",
+            html,
+            self.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::styler::StylerExt;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_evcxr_bundle() {
+        let df = df!("a" => &[1, 2]).unwrap();
+        let bundle = df.style().set_uid("x").evcxr_bundle();
+        assert!(bundle.starts_with("EVCXR_BEGIN_CONTENT text/html\n"));
+        assert!(bundle.contains("class=\"dataframe\""));
+        assert!(
+            bundle.contains("EVCXR_END_CONTENT\nEVCXR_BEGIN_CONTENT text/plain\nStyler: 2 rows")
+        );
+        assert!(bundle.ends_with("EVCXR_END_CONTENT\n"));
+    }
+}
@@ -0,0 +1,302 @@
+use polars::export::arrow::temporal_conversions::{
+    date32_to_date, time64ns_to_time, timestamp_ms_to_datetime, timestamp_ns_to_datetime,
+    timestamp_us_to_datetime,
+};
+use polars::export::chrono;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Declarative display format of a column: number of decimals, separators, currency
+/// and percent signs, missing value text and date/time pattern.
+///
+/// Unlike closures passed to `Styler::set_column_formatter`, formats are saved in the spec.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CellFormat {
+    /// Number of decimals; integers get decimals only when it is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u32>,
+    /// Separator inserted between groups of three digits, e.g. `","`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thousands: Option<String>,
+    /// Decimal mark used instead of `"."`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimal: Option<String>,
+    /// Multiplies numbers by 100 and appends `%`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub percent: bool,
+    /// Text put before numbers (after the minus sign), e.g. a currency symbol.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Text put after numbers, e.g. `" €"` or a unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    /// Text displayed for nulls and NaNs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub na_rep: Option<String>,
+    /// `strftime` pattern of dates, datetimes and times, e.g. `"%d.%m.%Y"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datetime: Option<String>,
+    /// Texts displayed for `true` and `false`, e.g. `("✓", "✗")`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub booleans: Option<(String, String)>,
+}
+
+/// Group of dtypes that share a default format, see `Styler::set_dtype_format`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum DtypeKind {
+    Integer,
+    Float,
+    Boolean,
+    Utf8,
+    Date,
+    Datetime,
+    Time,
+}
+
+impl DtypeKind {
+    pub fn of(dtype: &DataType) -> Option<DtypeKind> {
+        let kind = match dtype {
+            DataType::Float32 | DataType::Float64 => DtypeKind::Float,
+            dtype if dtype.is_integer() => DtypeKind::Integer,
+            DataType::Boolean => DtypeKind::Boolean,
+            DataType::Utf8 => DtypeKind::Utf8,
+            DataType::Date => DtypeKind::Date,
+            DataType::Datetime(_, _) => DtypeKind::Datetime,
+            DataType::Time => DtypeKind::Time,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// Commonly used defaults: floats with 2 decimals, ISO 8601 dates and datetimes and
+/// booleans as ✓ and ✗.
+pub fn recommended_dtype_formats() -> BTreeMap<DtypeKind, CellFormat> {
+    BTreeMap::from([
+        (DtypeKind::Float, CellFormat::new().precision(2)),
+        (DtypeKind::Date, CellFormat::new().datetime("%Y-%m-%d")),
+        (
+            DtypeKind::Datetime,
+            CellFormat::new().datetime("%Y-%m-%dT%H:%M:%S"),
+        ),
+        (DtypeKind::Boolean, CellFormat::new().booleans("✓", "✗")),
+    ])
+}
+
+impl CellFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn precision(mut self, precision: u32) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn thousands(mut self, separator: &str) -> Self {
+        self.thousands = Some(separator.to_string());
+        self
+    }
+
+    pub fn decimal(mut self, mark: &str) -> Self {
+        self.decimal = Some(mark.to_string());
+        self
+    }
+
+    pub fn percent(mut self) -> Self {
+        self.percent = true;
+        self
+    }
+
+    /// Currency symbol put in front of the number, e.g. `-$1,200.00`.
+    pub fn currency(self, symbol: &str) -> Self {
+        self.prefix(symbol)
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+
+    pub fn suffix(mut self, suffix: &str) -> Self {
+        self.suffix = Some(suffix.to_string());
+        self
+    }
+
+    pub fn na_rep(mut self, na_rep: &str) -> Self {
+        self.na_rep = Some(na_rep.to_string());
+        self
+    }
+
+    pub fn datetime(mut self, pattern: &str) -> Self {
+        self.datetime = Some(pattern.to_string());
+        self
+    }
+
+    pub fn booleans(mut self, true_rep: &str, false_rep: &str) -> Self {
+        self.booleans = Some((true_rep.to_string(), false_rep.to_string()));
+        self
+    }
+
+    /// Fills the settings this format doesn't set, except precision, from `defaults`.
+    pub fn with_defaults(&self, defaults: &CellFormat) -> CellFormat {
+        CellFormat {
+            precision: self.precision,
+            thousands: self.thousands.clone().or(defaults.thousands.clone()),
+            decimal: self.decimal.clone().or(defaults.decimal.clone()),
+            percent: self.percent || defaults.percent,
+            prefix: self.prefix.clone().or(defaults.prefix.clone()),
+            suffix: self.suffix.clone().or(defaults.suffix.clone()),
+            na_rep: self.na_rep.clone().or(defaults.na_rep.clone()),
+            datetime: self.datetime.clone().or(defaults.datetime.clone()),
+            booleans: self.booleans.clone().or(defaults.booleans.clone()),
+        }
+    }
+
+    /// Formats a cell value; `precision` is used when the format does not set its own.
+    pub fn format(&self, v: &AnyValue, precision: Option<u32>) -> String {
+        let precision = self.precision.or(precision);
+        match v {
+            AnyValue::Null => self.na(),
+            AnyValue::Float64(f) => self.number(*f, precision),
+            AnyValue::Float32(f) => self.number(*f as f64, precision),
+            v if v.dtype().is_numeric() => match (precision, self.percent) {
+                (None, false) => self.affixed(v.to_string()),
+                _ => self.number(v.extract::<f64>().unwrap_or(f64::NAN), precision),
+            },
+            AnyValue::Utf8(s) => s.to_string(),
+            AnyValue::Boolean(b) => match (&self.booleans, b) {
+                (Some((true_rep, _)), true) => true_rep.clone(),
+                (Some((_, false_rep)), false) => false_rep.clone(),
+                (None, b) => b.to_string(),
+            },
+            v => match (&self.datetime, temporal(v)) {
+                (Some(pattern), Some(t)) => t.format(pattern).to_string(),
+                _ => v.to_string(),
+            },
+        }
+    }
+
+    fn na(&self) -> String {
+        self.na_rep.clone().unwrap_or_else(|| "null".to_string())
+    }
+
+    fn number(&self, v: f64, precision: Option<u32>) -> String {
+        if v.is_nan() {
+            return self.na_rep.clone().unwrap_or_else(|| v.to_string());
+        }
+        let v = if self.percent { v * 100.0 } else { v };
+        let text = match precision {
+            Some(p) => format!("{:.1$}", v, p as usize),
+            None => v.to_string(),
+        };
+        self.affixed(text)
+    }
+
+    /// Adds separators, prefix and suffix to a plain formatted number.
+    fn affixed(&self, text: String) -> String {
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", text.as_str()),
+        };
+        let (int, frac) = match digits.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (digits, None),
+        };
+        let mut out = format!("{}{}", sign, self.prefix.as_deref().unwrap_or(""));
+        for (i, c) in int.chars().enumerate() {
+            if i > 0 && (int.len() - i) % 3 == 0 {
+                out.push_str(self.thousands.as_deref().unwrap_or(""));
+            }
+            out.push(c);
+        }
+        if let Some(frac) = frac {
+            out.push_str(self.decimal.as_deref().unwrap_or("."));
+            out.push_str(frac);
+        }
+        if self.percent {
+            out.push('%');
+        }
+        out.push_str(self.suffix.as_deref().unwrap_or(""));
+        out
+    }
+}
+
+enum Temporal {
+    Date(chrono::NaiveDate),
+    Datetime(chrono::NaiveDateTime),
+    Time(chrono::NaiveTime),
+}
+
+impl Temporal {
+    fn format<'a>(
+        &self,
+        pattern: &'a str,
+    ) -> chrono::format::DelayedFormat<chrono::format::StrftimeItems<'a>> {
+        match self {
+            Temporal::Date(d) => d.format(pattern),
+            Temporal::Datetime(dt) => dt.format(pattern),
+            Temporal::Time(t) => t.format(pattern),
+        }
+    }
+}
+
+fn temporal(v: &AnyValue) -> Option<Temporal> {
+    let t = match v {
+        AnyValue::Date(d) => Temporal::Date(date32_to_date(*d)),
+        AnyValue::Datetime(t, unit, _) => Temporal::Datetime(match unit {
+            TimeUnit::Milliseconds => timestamp_ms_to_datetime(*t),
+            TimeUnit::Microseconds => timestamp_us_to_datetime(*t),
+            TimeUnit::Nanoseconds => timestamp_ns_to_datetime(*t),
+        }),
+        AnyValue::Time(t) => Temporal::Time(time64ns_to_time(*t)),
+        _ => return None,
+    };
+    Some(t)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_number_format() {
+        let usd = CellFormat::new().currency("$").thousands(",").precision(2);
+        assert_eq!(
+            usd.format(&AnyValue::Float64(-1234567.891), None),
+            "-$1,234,567.89"
+        );
+        assert_eq!(usd.format(&AnyValue::Int64(1200), None), "$1,200.00");
+        let pct = CellFormat::new().percent().decimal(",");
+        assert_eq!(pct.format(&AnyValue::Float64(0.125), Some(1)), "12,5%");
+        let plain = CellFormat::new().thousands(" ").na_rep("-");
+        assert_eq!(plain.format(&AnyValue::Int32(12345), None), "12 345");
+        assert_eq!(plain.format(&AnyValue::Null, None), "-");
+        assert_eq!(plain.format(&AnyValue::Float64(f64::NAN), None), "-");
+    }
+
+    #[test]
+    fn test_datetime_format() {
+        let f = CellFormat::new().datetime("%d.%m.%Y");
+        assert_eq!(f.format(&AnyValue::Date(19000), None), "08.01.2022");
+        let dt = AnyValue::Datetime(1_650_000_000_000, TimeUnit::Milliseconds, &None);
+        assert_eq!(
+            CellFormat::new()
+                .datetime("%Y-%m-%d %H:%M")
+                .format(&dt, None),
+            "2022-04-15 05:20"
+        );
+    }
+
+    #[test]
+    fn test_dtype_kind() {
+        assert_eq!(DtypeKind::of(&DataType::Float32), Some(DtypeKind::Float));
+        assert_eq!(DtypeKind::of(&DataType::UInt8), Some(DtypeKind::Integer));
+        assert_eq!(DtypeKind::of(&DataType::Null), None);
+        let f = &recommended_dtype_formats()[&DtypeKind::Boolean];
+        assert_eq!(f.format(&AnyValue::Boolean(false), None), "✗");
+    }
+}
@@ -0,0 +1,34 @@
+// Downloads the table as CSV when the button is clicked. Raw values from
+// `data-sort-value` are preferred over the formatted text; rows hidden by the
+// filter or search are left out, the ellipsis row and column and row numbers are skipped.
+var button = table.getRootNode().getElementById(table.id + "_csv");
+
+function quote(value) {
+  return /[",\n\r]/.test(value) ? '"' + value.replace(/"/g, '""') + '"' : value;
+}
+
+function line(cells) {
+  return Array.prototype.filter.call(cells, function (cell) {
+    return !cell.classList.contains("ellipsis") && !cell.classList.contains("ps-row-number");
+  }).map(function (cell) {
+    var raw = cell.getAttribute("data-sort-value");
+    return quote(raw !== null ? raw : cell.textContent);
+  }).join(",");
+}
+
+button.addEventListener("click", function () {
+  var lines = [line(table.tHead.rows[0].cells)];
+  Array.prototype.forEach.call(table.tBodies[0].rows, function (row) {
+    if (row.classList.contains("ellipsis") || row.classList.contains("ps-filter-hidden") ||
+        row.classList.contains("ps-search-hidden")) return;
+    lines.push(line(row.cells));
+  });
+  var blob = new Blob([lines.join("\r\n") + "\r\n"], { type: "text/csv;charset=utf-8" });
+  var link = document.createElement("a");
+  link.href = URL.createObjectURL(blob);
+  link.download = options.filename;
+  document.body.appendChild(link);
+  link.click();
+  document.body.removeChild(link);
+  URL.revokeObjectURL(link.href);
+});
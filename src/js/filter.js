@@ -0,0 +1,48 @@
+// Filters body rows by the inputs of the `ps-filters` header row. A query starting with
+// a comparison (`>5`, `<=10`, `=3`) or a range (`1..5`) compares numbers, anything else
+// is a case-insensitive substring match.
+var inputs = table.tHead.querySelectorAll("tr.ps-filters input");
+
+function predicate(query) {
+  query = query.trim();
+  if (query === "") return null;
+  var range = query.match(/^(-?[\d.]+)\s*\.\.\s*(-?[\d.]+)$/);
+  if (range) {
+    var lo = parseFloat(range[1]), hi = parseFloat(range[2]);
+    return function (_, x) { return x >= lo && x <= hi; };
+  }
+  var cmp = query.match(/^(>=|<=|>|<|=)\s*(-?[\d.]+)$/);
+  if (cmp) {
+    var t = parseFloat(cmp[2]);
+    return {
+      ">": function (_, x) { return x > t; },
+      ">=": function (_, x) { return x >= t; },
+      "<": function (_, x) { return x < t; },
+      "<=": function (_, x) { return x <= t; },
+      "=": function (_, x) { return x === t; }
+    }[cmp[1]];
+  }
+  var needle = query.toLowerCase();
+  return function (text) { return text.toLowerCase().indexOf(needle) !== -1; };
+}
+
+function apply() {
+  var filters = Array.prototype.map.call(inputs, function (input) {
+    return { index: parseInt(input.getAttribute("data-col"), 10), test: predicate(input.value) };
+  }).filter(function (f) { return f.test; });
+  Array.prototype.forEach.call(table.tBodies[0].rows, function (row) {
+    if (row.classList.contains("ellipsis")) return;
+    var keep = filters.every(function (f) {
+      var cell = row.cells[f.index];
+      var raw = cell.getAttribute("data-sort-value");
+      var number = parseFloat(raw !== null ? raw : cell.textContent);
+      return f.test(cell.textContent, number);
+    });
+    row.classList.toggle("ps-filter-hidden", !keep);
+  });
+  table.dispatchEvent(new Event("ps-update"));
+}
+
+Array.prototype.forEach.call(inputs, function (input) {
+  input.addEventListener("input", apply);
+});
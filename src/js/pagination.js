@@ -0,0 +1,39 @@
+// Shows `options.pageSize` rows at a time with previous/next controls below the table.
+// Rows hidden by other scripts (filter, search) are skipped; they signal changes via `ps-update`.
+var size = options.pageSize;
+var page = 0;
+var nav = document.createElement("div");
+nav.className = "ps-pagination";
+var prev = document.createElement("button");
+var label = document.createElement("span");
+var next = document.createElement("button");
+prev.type = next.type = "button";
+prev.textContent = "‹ Previous";
+next.textContent = "Next ›";
+nav.append(prev, label, next);
+table.parentNode.insertBefore(nav, table.nextSibling);
+
+function candidates() {
+  return Array.prototype.filter.call(table.tBodies[0].rows, function (row) {
+    row.classList.remove("ps-page-hidden");
+    return !row.classList.contains("ps-filter-hidden") && !row.classList.contains("ps-search-hidden");
+  });
+}
+
+function show() {
+  var rows = candidates();
+  var pages = Math.max(1, Math.ceil(rows.length / size));
+  page = Math.min(page, pages - 1);
+  rows.forEach(function (row, i) {
+    row.classList.toggle("ps-page-hidden", Math.floor(i / size) !== page);
+  });
+  label.textContent = " " + (page + 1) + " / " + pages + " ";
+  prev.disabled = page === 0;
+  next.disabled = page === pages - 1;
+  nav.hidden = pages === 1;
+}
+
+prev.addEventListener("click", function () { page -= 1; show(); });
+next.addEventListener("click", function () { page += 1; show(); });
+table.addEventListener("ps-update", function () { page = 0; show(); });
+show();
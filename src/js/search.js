@@ -0,0 +1,19 @@
+// Marks cells containing the text of the search box; with `options.hideRows`
+// also hides rows without any match.
+// The root is the document, or the shadow root of a `<polars-table>` element.
+var input = table.getRootNode().getElementById(table.id + "_search");
+
+input.addEventListener("input", function () {
+  var needle = input.value.trim().toLowerCase();
+  Array.prototype.forEach.call(table.tBodies[0].rows, function (row) {
+    if (row.classList.contains("ellipsis")) return;
+    var found = false;
+    Array.prototype.forEach.call(row.cells, function (cell) {
+      var match = needle !== "" && cell.textContent.toLowerCase().indexOf(needle) !== -1;
+      cell.classList.toggle("ps-search-match", match);
+      found = found || match;
+    });
+    row.classList.toggle("ps-search-hidden", options.hideRows && needle !== "" && !found);
+  });
+  table.dispatchEvent(new Event("ps-update"));
+});
@@ -0,0 +1,33 @@
+// Sorts body rows when a header is clicked, using the `data-sort-value` of the cells.
+// Numeric and temporal columns compare as numbers, other columns as text; nulls go last.
+var body = table.tBodies[0];
+var headers = table.tHead.rows[0].cells;
+
+function key(row, index, numeric) {
+  var value = row.cells[index].getAttribute("data-sort-value");
+  if (value === null || value === "") return null;
+  return numeric ? parseFloat(value) : value;
+}
+
+Array.prototype.forEach.call(headers, function (th, index) {
+  var type = th.getAttribute("data-sort-type");
+  if (!type) return;
+  th.style.cursor = "pointer";
+  th.addEventListener("click", function () {
+    var ascending = th.getAttribute("aria-sort") !== "ascending";
+    Array.prototype.forEach.call(headers, function (h) { h.removeAttribute("aria-sort"); });
+    th.setAttribute("aria-sort", ascending ? "ascending" : "descending");
+    var numeric = type === "number";
+    var rows = Array.prototype.slice.call(body.rows);
+    var fixed = rows.filter(function (row) { return row.classList.contains("ellipsis"); });
+    rows = rows.filter(function (row) { return !row.classList.contains("ellipsis"); });
+    rows.sort(function (a, b) {
+      var x = key(a, index, numeric), y = key(b, index, numeric);
+      if (x === null || y === null) return (x === null) - (y === null);
+      var order = numeric ? x - y : String(x).localeCompare(String(y));
+      return ascending ? order : -order;
+    });
+    rows.concat(fixed).forEach(function (row) { body.appendChild(row); });
+    table.dispatchEvent(new Event("ps-update"));
+  });
+});
@@ -0,0 +1,13 @@
+// Defines `<polars-table>`, whose content is a declarative shadow root. Browsers
+// without declarative shadow DOM keep the template, so it is attached here.
+if (!customElements.get("polars-table")) {
+  customElements.define("polars-table", class extends HTMLElement {
+    connectedCallback() {
+      if (this.shadowRoot) return;
+      var template = this.querySelector("template[shadowrootmode]");
+      if (!template) return;
+      this.attachShadow({ mode: "open" }).appendChild(template.content);
+      template.remove();
+    }
+  });
+}
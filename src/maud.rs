@@ -0,0 +1,25 @@
+pub use crate::web::PreRendered;
+use ::maud::Render;
+
+impl Render for PreRendered {
+    fn render_to(&self, buffer: &mut String) {
+        buffer.push_str(self.as_str());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::styler::StylerExt;
+    use ::maud::html;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_render() {
+        let df = df!("a" => &[1, 2]).unwrap();
+        let table = PreRendered::from(df.style().set_uid("x"));
+        let page = html! { div { (table) } }.into_string();
+        assert_eq!(page, format!("<div>{}</div>", table));
+        assert!(page.contains("<table id=\"T_x\""));
+    }
+}
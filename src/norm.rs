@@ -0,0 +1,394 @@
+use crate::styler::StylerError;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Maps column values to `[0, 1]` before they are looked up in a gradient.
+///
+/// Shared by `background_gradient_norm`, `bar_norm` and `text_gradient`, so every scaling
+/// is available to every visual encoding.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Norm {
+    /// Linear so that `vmin` maps to 0 and `vmax` to 1; they default to the column minimum
+    /// and maximum. Values outside are clamped.
+    Linear {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        vmin: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        vmax: Option<f64>,
+    },
+    /// Rank of the value (ties get their average rank), evenly spaced regardless of
+    /// the distances between values.
+    Rank,
+    /// Linear between the `lower` and `upper` quantiles (e.g. 0.05 and 0.95); values
+    /// outside are clipped. Outliers of skewed data no longer squash the other cells.
+    Quantile { lower: f64, upper: f64 },
+    /// Logarithmic between the smallest positive value and the maximum, for data spanning
+    /// several orders of magnitude. Zero and negative values map to 0.
+    Log,
+    /// Symmetric log for signed data: `sign(v) * ln(1 + |v| / linthresh)`, roughly linear
+    /// within `linthresh` of zero and logarithmic outside. `linthresh` must be positive.
+    SymLog {
+        #[serde(deserialize_with = "deserialize_linthresh")]
+        linthresh: f64,
+    },
+    /// Standard scores clipped at `±k` standard deviations, so the middle of the gradient
+    /// is the mean; for anomaly-style heatmaps.
+    ZScore { k: f64 },
+    /// User-provided function; styles using it are not part of saved specs.
+    #[serde(skip)]
+    Custom(CustomNorm),
+}
+
+type NormFn = dyn Fn(&Series) -> Vec<Option<f64>> + Send + Sync;
+
+/// Closure of `Norm::Custom`; values it returns are clamped to `[0, 1]`.
+#[derive(Clone)]
+pub struct CustomNorm(Arc<NormFn>);
+
+impl Debug for CustomNorm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CustomNorm(..)")
+    }
+}
+
+impl PartialEq for CustomNorm {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+fn deserialize_linthresh<'de, D: serde::Deserializer<'de>>(d: D) -> Result<f64, D::Error> {
+    let linthresh = f64::deserialize(d)?;
+    check_linthresh(linthresh).map_err(serde::de::Error::custom)?;
+    Ok(linthresh)
+}
+
+fn check_linthresh(linthresh: f64) -> Result<(), StylerError> {
+    if !(linthresh.is_finite() && linthresh > 0.0) {
+        return Err(StylerError::InvalidArgument(format!(
+            "linthresh must be positive and finite, got {}",
+            linthresh
+        )));
+    }
+    Ok(())
+}
+
+/// Returns an error unless the values can be normalized: numbers, temporal values,
+/// booleans or only nulls.
+pub(crate) fn check_numeric(s: &Series) -> Result<(), StylerError> {
+    let dtype = s.dtype();
+    let numeric = dtype.is_numeric() || dtype.is_temporal();
+    if !(numeric || dtype == &DataType::Boolean || dtype == &DataType::Null) {
+        return Err(StylerError::NotNumeric {
+            column: s.name().to_string(),
+            dtype: dtype.to_string(),
+        });
+    }
+    Ok(())
+}
+
+impl Norm {
+    /// Linear between the column minimum and maximum.
+    pub fn linear() -> Self {
+        Norm::Linear {
+            vmin: None,
+            vmax: None,
+        }
+    }
+
+    pub fn custom(f: impl Fn(&Series) -> Vec<Option<f64>> + Send + Sync + 'static) -> Self {
+        Norm::Custom(CustomNorm(Arc::new(f)))
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Norm::Custom(_))
+    }
+
+    /// Returns an error for parameters that can't scale any values, e.g. a `SymLog`
+    /// with a `linthresh` that isn't positive.
+    pub fn check(&self) -> Result<(), StylerError> {
+        match self {
+            Norm::SymLog { linthresh } => check_linthresh(*linthresh),
+            _ => Ok(()),
+        }
+    }
+
+    /// Values mapped to 0 and 1; `None` if the column has no finite values.
+    pub fn range(&self, s: &Series) -> Option<(f64, f64)> {
+        let mut values = values(s)
+            .into_iter()
+            .flatten()
+            .filter(|v| v.is_finite())
+            .collect::<Vec<_>>();
+        values.sort_by(f64::total_cmp);
+        let (min, max) = (*values.first()?, *values.last()?);
+        let range = match self {
+            Norm::Linear { vmin, vmax } => (vmin.unwrap_or(min), vmax.unwrap_or(max)),
+            Norm::Log => (values.iter().copied().find(|v| *v > 0.0)?, max),
+            Norm::Quantile { lower, upper } => {
+                (quantile(&values, *lower), quantile(&values, *upper))
+            }
+            Norm::ZScore { k } => {
+                let (mean, std) = mean_std(&values);
+                (mean - k * std, mean + k * std)
+            }
+            Norm::Rank | Norm::SymLog { .. } | Norm::Custom(_) => (min, max),
+        };
+        Some(range)
+    }
+
+    /// Normalized values; `None` for nulls and NaN, 0.5 for every value of a constant column.
+    pub fn normalize(&self, s: &Series) -> Result<Vec<Option<f64>>, StylerError> {
+        if let Norm::Custom(CustomNorm(f)) = self {
+            return Ok(f(s)
+                .into_iter()
+                .map(|v| v.map(|v| v.clamp(0.0, 1.0)))
+                .collect());
+        }
+        check_numeric(s)?;
+        self.check()?;
+        if let Norm::Rank = self {
+            return Ok(ranks(&values(s)));
+        }
+        let Some((lo, hi)) = self.range(s) else {
+            return Ok(vec![None; s.len()]);
+        };
+        let (lo, hi) = (self.transform(lo), self.transform(hi));
+        Ok(values(s)
+            .into_iter()
+            .map(|v| {
+                let v = self.transform(v?);
+                if hi == lo {
+                    return Some(0.5);
+                }
+                let a = (v - lo) / (hi - lo);
+                // NaN is the log of a negative value
+                Some(if a.is_nan() { 0.0 } else { a.clamp(0.0, 1.0) })
+            })
+            .collect())
+    }
+
+    /// Scale on which the values are spaced linearly.
+    fn transform(&self, v: f64) -> f64 {
+        match self {
+            Norm::Log => v.ln(),
+            Norm::SymLog { linthresh } => v.signum() * (v.abs() / linthresh).ln_1p(),
+            _ => v,
+        }
+    }
+}
+
+/// Values as floats; NaN counts as null.
+fn values(s: &Series) -> Vec<Option<f64>> {
+    to_float(s)
+        .f64()
+        .unwrap()
+        .into_iter()
+        .map(|v| v.filter(|v| !v.is_nan()))
+        .collect()
+}
+
+/// Casts to `Float64`; dates, datetimes, durations and times use their integer representation.
+pub(crate) fn to_float(s: &Series) -> Series {
+    s.to_physical_repr().cast(&DataType::Float64).unwrap()
+}
+
+/// Average ranks scaled to `[0, 1]`.
+fn ranks(values: &[Option<f64>]) -> Vec<Option<f64>> {
+    let mut order = values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| Some((i, (*v)?)))
+        .collect::<Vec<_>>();
+    order.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let n = order.len();
+    let mut result = vec![None; values.len()];
+    let mut start = 0;
+    while start < n {
+        let end = start
+            + order[start..]
+                .iter()
+                .take_while(|(_, v)| *v == order[start].1)
+                .count();
+        let rank = (start + end - 1) as f64 / 2.0;
+        let scaled = if n > 1 { rank / (n - 1) as f64 } else { 0.5 };
+        for (i, _) in &order[start..end] {
+            result[*i] = Some(scaled);
+        }
+        start = end;
+    }
+    result
+}
+
+/// Mean and sample standard deviation; the deviation is 0 for a single value.
+fn mean_std(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() <= 1 {
+        return (mean, 0.0);
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+/// Linearly interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    let a = position - below as f64;
+    sorted[below] * (1.0 - a) + sorted[above] * a
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_linear() {
+        let s = Series::new("a", &[-1, 2, 3]);
+        assert_eq!(
+            Norm::linear().normalize(&s).unwrap(),
+            vec![Some(0.0), Some(0.75), Some(1.0)]
+        );
+        let s = Series::new("a", &[1.0, 2.0, 3.0]);
+        let norm = Norm::Linear {
+            vmin: Some(0.0),
+            vmax: Some(2.0),
+        };
+        assert_eq!(
+            norm.normalize(&s).unwrap(),
+            vec![Some(0.5), Some(1.0), Some(1.0)]
+        );
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let s = Series::new("a", &[Some(2.0), None, Some(2.0)]);
+        assert_eq!(
+            Norm::linear().normalize(&s).unwrap(),
+            vec![Some(0.5), None, Some(0.5)]
+        );
+        let s = Series::new("a", &[None::<f64>, None]);
+        assert_eq!(Norm::linear().normalize(&s).unwrap(), vec![None, None]);
+        assert_eq!(Norm::Rank.normalize(&s).unwrap(), vec![None, None]);
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let s = Series::new("a", &[1.0, 2.0, f64::NAN, 3.0, f64::INFINITY]);
+        assert_eq!(Norm::linear().range(&s), Some((1.0, 3.0)));
+        assert_eq!(
+            Norm::linear().normalize(&s).unwrap(),
+            vec![Some(0.0), Some(0.5), None, Some(1.0), Some(1.0)]
+        );
+        let s = Series::new("a", &[f64::NAN, 1.0, 3.0]);
+        assert_eq!(
+            Norm::Rank.normalize(&s).unwrap(),
+            vec![None, Some(0.0), Some(1.0)]
+        );
+    }
+
+    #[test]
+    fn test_rank() {
+        let s = Series::new(
+            "a",
+            &[Some(10.0), Some(1.0), None, Some(10.0), Some(1000.0)],
+        );
+        assert_eq!(
+            Norm::Rank.normalize(&s).unwrap(),
+            vec![Some(0.5), Some(0.0), None, Some(0.5), Some(1.0)]
+        );
+    }
+
+    #[test]
+    fn test_quantile() {
+        let s = Series::new(
+            "a",
+            &[
+                Some(100.0),
+                Some(1.0),
+                None,
+                Some(2.0),
+                Some(3.0),
+                Some(4.0),
+            ],
+        );
+        let norm = Norm::Quantile {
+            lower: 0.25,
+            upper: 0.75,
+        };
+        assert_eq!(norm.range(&s), Some((2.0, 4.0)));
+        assert_eq!(
+            norm.normalize(&s).unwrap(),
+            vec![Some(1.0), Some(0.0), None, Some(0.0), Some(0.5), Some(1.0)]
+        );
+    }
+
+    #[test]
+    fn test_log() {
+        let s = Series::new("a", &[-1.0, 1.0, 10.0, 100.0]);
+        assert_eq!(Norm::Log.range(&s), Some((1.0, 100.0)));
+        assert_eq!(
+            Norm::Log.normalize(&s).unwrap(),
+            vec![Some(0.0), Some(0.0), Some(0.5), Some(1.0)]
+        );
+        let norm = Norm::SymLog { linthresh: 1.0 };
+        let normalized = norm.normalize(&Series::new("a", &[-100.0, 0.0, 100.0]));
+        assert_eq!(normalized.unwrap(), vec![Some(0.0), Some(0.5), Some(1.0)]);
+
+        for linthresh in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let norm = Norm::SymLog { linthresh };
+            assert!(matches!(
+                norm.normalize(&Series::new("a", &[1.0, 2.0])),
+                Err(StylerError::InvalidArgument(_))
+            ));
+        }
+        let json = r#"{"type": "sym_log", "linthresh": 0.0}"#;
+        let err = serde_json::from_str::<Norm>(json).unwrap_err();
+        assert!(err.to_string().contains("linthresh must be positive"));
+        let json = r#"{"type": "sym_log", "linthresh": 2.0}"#;
+        assert_eq!(
+            serde_json::from_str::<Norm>(json).unwrap(),
+            Norm::SymLog { linthresh: 2.0 }
+        );
+    }
+
+    #[test]
+    fn test_zscore() {
+        let norm = Norm::ZScore { k: 0.5 };
+        let s = Series::new("a", &[0.0, 2.0, 4.0]);
+        assert_eq!(norm.range(&s), Some((1.0, 3.0)));
+        assert_eq!(
+            norm.normalize(&s).unwrap(),
+            vec![Some(0.0), Some(0.5), Some(1.0)]
+        );
+        let s = Series::new("a", &[Some(3.0), None]);
+        assert_eq!(norm.range(&s), Some((3.0, 3.0)));
+        assert_eq!(norm.normalize(&s).unwrap(), vec![Some(0.5), None]);
+    }
+
+    #[test]
+    fn test_temporal() {
+        let s = Series::new("a", &[0i32, 10, 20])
+            .cast(&DataType::Date)
+            .unwrap();
+        assert_eq!(
+            Norm::linear().normalize(&s).unwrap(),
+            vec![Some(0.0), Some(0.5), Some(1.0)]
+        );
+    }
+
+    #[test]
+    fn test_custom() {
+        let norm = Norm::custom(|s| s.iter().map(|v| v.extract::<f64>()).collect());
+        let s = Series::new("a", &[-1.0, 0.5, 2.0]);
+        assert_eq!(
+            norm.normalize(&s).unwrap(),
+            vec![Some(0.0), Some(0.5), Some(1.0)]
+        );
+        assert!(serde_json::to_string(&norm).is_err());
+    }
+}
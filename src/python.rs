@@ -0,0 +1,794 @@
+// pyo3 0.19 macros expand to impl blocks that newer compilers flag as non-local
+#![allow(non_local_definitions)]
+
+use crate::colors::{Color, ColorBreakPoint, ColorMap};
+use crate::format::CellFormat;
+use crate::norm::Norm;
+use crate::renderer::TableStyle;
+use crate::spec::StyleSpec;
+use crate::styler::{
+    Aggregation, Bins, Border, ColumnMeta, Condition, DiffStyle, IconSet, Styler, StylerError,
+    TextContrast, TextStyle,
+};
+use crate::text::TextProps;
+
+use polars::prelude::AnyValue;
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
+use pyo3_polars::{PyDataFrame, PySeries};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[pyclass(module = "polars_styler.polars_styler")]
+#[derive(Clone)]
+struct PyStyler {
+    s: Styler,
+    /// First exception raised by a Python formatter, re-raised by `render`.
+    format_error: Arc<Mutex<Option<PyErr>>>,
+}
+
+impl PyStyler {
+    fn wrap(s: Styler) -> Self {
+        PyStyler {
+            s,
+            format_error: Arc::default(),
+        }
+    }
+
+    /// Applies a consuming builder method that can't fail in place; the styler is moved
+    /// out rather than cloned, so chained calls don't copy the data and the per-cell styles.
+    fn update(&mut self, f: impl FnOnce(Styler) -> Styler) {
+        self.s = f(std::mem::take(&mut self.s));
+    }
+
+    /// Like `update` for a step that can fail, applied with the `try_*` builder methods so
+    /// that it raises instead of panicking. The step consumes the styler, so `check` has to
+    /// validate it beforehand: an invalid step raises without losing the styles applied
+    /// so far.
+    fn try_update(
+        &mut self,
+        check: Result<(), StylerError>,
+        f: impl FnOnce(Styler) -> Result<Styler, StylerError>,
+    ) -> PyResult<()> {
+        check?;
+        self.s = f(std::mem::take(&mut self.s))?;
+        Ok(())
+    }
+
+    /// Renders with `render`, raising the first error of a Python formatter.
+    fn render_with(&self, render: impl FnOnce(Styler) -> String) -> PyResult<String> {
+        if self.s.data().width() == 0 {
+            return Err(StylerError::NoColumns.into());
+        }
+        let html = render(self.s.clone());
+        match self.format_error.lock().unwrap().take() {
+            Some(e) => Err(e),
+            None => Ok(html),
+        }
+    }
+}
+
+impl From<StylerError> for PyErr {
+    fn from(e: StylerError) -> Self {
+        match e {
+            StylerError::UnknownColumn { .. } => PyKeyError::new_err(e.to_string()),
+            StylerError::NotNumeric { .. } => PyTypeError::new_err(e.to_string()),
+            StylerError::RowOutOfBounds { .. } => PyIndexError::new_err(e.to_string()),
+            _ => PyValueError::new_err(e.to_string()),
+        }
+    }
+}
+
+/// Cell value as the Python object passed to formatters; temporal values are passed as
+/// their text.
+fn any_value_to_py(py: Python, v: &AnyValue) -> PyObject {
+    match v {
+        AnyValue::Null => py.None(),
+        AnyValue::Boolean(b) => b.into_py(py),
+        AnyValue::Utf8(s) => s.into_py(py),
+        AnyValue::Float32(_) | AnyValue::Float64(_) => v.extract::<f64>().into_py(py),
+        v if v.dtype().is_integer() => v.extract::<i64>().into_py(py),
+        v => v.to_string().into_py(py),
+    }
+}
+
+#[pymethods]
+impl PyStyler {
+    /// The columns are imported through the Arrow C data interface and share the buffers
+    /// of the Python frame; only columns of several chunks are copied, when rechunked.
+    #[new]
+    fn new(df: PyDataFrame) -> Self {
+        PyStyler::wrap(Styler::from_shared(Arc::new(df.0)))
+    }
+
+    fn set_table_classes(&mut self, classes: Vec<String>) -> PyResult<()> {
+        let check = match self.s.table_classes() {
+            Some(_) => Err(StylerError::AlreadySet("table_classes")),
+            None => Ok(()),
+        };
+        self.try_update(check, |s| s.try_set_table_classes(classes))
+    }
+
+    fn add_table_classes(&mut self, classes: Vec<String>) {
+        self.update(|s| s.add_table_classes(classes));
+    }
+
+    fn set_labels(&mut self, labels: Vec<String>) {
+        self.update(|s| s.set_labels(labels));
+    }
+
+    fn relabel_column(&mut self, column: &str, label: &str) {
+        self.update(|s| s.relabel_column(column, label));
+    }
+
+    fn relabel(&mut self, mapping: HashMap<String, String>) {
+        self.update(|s| s.relabel(&mapping));
+    }
+
+    fn set_precision(&mut self, precision: u32) -> PyResult<()> {
+        let check = match self.s.precision() {
+            Some(_) => Err(StylerError::AlreadySet("precision")),
+            None => Ok(()),
+        };
+        self.try_update(check, |s| s.try_set_precision(precision))
+    }
+
+    fn set_column_precision(&mut self, column: &str, precision: u32) {
+        self.update(|s| s.set_column_precision(column, precision));
+    }
+
+    fn set_caption(&mut self, caption: &str) {
+        self.update(|s| s.set_caption(caption));
+    }
+
+    fn hide_columns(&mut self, columns: Vec<&str>) {
+        self.update(|s| s.hide_columns(&columns));
+    }
+
+    /// `(selector, props)` pairs of CSS rules scoped to the table.
+    fn set_table_styles(&mut self, styles: Vec<(&str, &str)>) {
+        let styles = styles
+            .into_iter()
+            .map(|(selector, props)| TableStyle::new(selector, props))
+            .collect();
+        self.update(|s| s.set_table_styles(styles));
+    }
+
+    fn highlight_between(
+        &mut self,
+        column: &str,
+        left: f64,
+        right: f64,
+        color: PyColor,
+    ) -> PyResult<()> {
+        let condition = Condition::Between(left, right);
+        let check = self.s.check_columns(&[column], false);
+        self.try_update(check, |s| s.try_highlight(column, &condition, &color.color))
+    }
+
+    fn highlight_null(&mut self, column: &str, color: PyColor) -> PyResult<()> {
+        let check = self.s.check_columns(&[column], false);
+        self.try_update(check, |s| {
+            s.try_highlight(column, &Condition::IsNull, &color.color)
+        })
+    }
+
+    fn heatmap_symmetric(
+        &mut self,
+        cmap: PyColorMap,
+        vmin: f64,
+        vmax: f64,
+        mask_diagonal: bool,
+    ) -> PyResult<()> {
+        let check = self.s.check_colormap(&cmap.cmap);
+        self.try_update(check, |s| {
+            s.try_heatmap_symmetric(&cmap.cmap, vmin, vmax, mask_diagonal)
+        })
+    }
+
+    /// `icons` is `"three_arrows"` or `"three_traffic_lights"`; without `thresholds` the
+    /// column range is split into three equal bands.
+    fn icon_set(
+        &mut self,
+        column: &str,
+        icons: &str,
+        thresholds: Option<Vec<f64>>,
+    ) -> PyResult<()> {
+        let icons = match icons {
+            "three_arrows" => IconSet::ThreeArrows,
+            "three_traffic_lights" => IconSet::ThreeTrafficLights,
+            _ => return Err(PyValueError::new_err(format!("Unknown icon set {}", icons))),
+        };
+        let bins = match thresholds {
+            Some(edges) => Bins::Edges(edges),
+            None => Bins::Count(3),
+        };
+        let check = self.s.check_icon_set(column, &bins);
+        self.try_update(check, |s| s.try_icon_set(column, icons, &bins))
+    }
+
+    /// `style` is `"bold"`, `"italic"`, `"underline"` or `"strikethrough"`.
+    fn emphasize_series(&mut self, column: &str, mask: PySeries, style: &str) -> PyResult<()> {
+        let style = match style {
+            "bold" => TextStyle::Bold,
+            "italic" => TextStyle::Italic,
+            "underline" => TextStyle::Underline,
+            "strikethrough" => TextStyle::Strikethrough,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown text style {}",
+                    style
+                )))
+            }
+        };
+        let check = self
+            .s
+            .check_columns(&[column], false)
+            .and(self.s.check_mask(&mask.0));
+        self.try_update(check, |s| s.try_emphasize_series(column, &mask.0, style))
+    }
+
+    /// Text properties of the `subset` columns, only in the rows of `mask` when given.
+    #[pyo3(signature = (subset, mask=None, weight=None, style=None, decoration=None, color=None, family=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn set_text(
+        &mut self,
+        subset: Vec<String>,
+        mask: Option<PySeries>,
+        weight: Option<&str>,
+        style: Option<&str>,
+        decoration: Option<&str>,
+        color: Option<PyColor>,
+        family: Option<&str>,
+    ) -> PyResult<()> {
+        let props = TextProps {
+            weight: weight.map(str::to_string),
+            style: style.map(str::to_string),
+            decoration: decoration.map(str::to_string),
+            color: color.map(|c| c.color),
+            family: family.map(str::to_string),
+        };
+        let subset = subset.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+        let mut check = self.s.check_columns(&subset, false);
+        if let Some(mask) = &mask {
+            check = check.and(self.s.check_mask(&mask.0));
+        }
+        self.try_update(check, |s| match &mask {
+            Some(mask) => subset.iter().try_fold(s, |s, column| {
+                s.try_set_text_series(column, &mask.0, &props)
+            }),
+            None => s.try_set_text(&subset, &props),
+        })
+    }
+
+    fn highlight_rows_at(&mut self, rows: Vec<usize>, color: PyColor) -> PyResult<()> {
+        let height = self.s.data().height();
+        let check = match rows.iter().find(|&&row| row >= height) {
+            Some(&row) => Err(StylerError::RowOutOfBounds { row, height }),
+            None => Ok(()),
+        };
+        self.try_update(check, |s| s.try_highlight_rows_at(&rows, &color.color))
+    }
+
+    fn highlight_rows_where_key(
+        &mut self,
+        column: &str,
+        values: Vec<String>,
+        color: PyColor,
+    ) -> PyResult<()> {
+        let values = values.iter().map(|v| v.as_str()).collect::<Vec<_>>();
+        let check = self.s.check_columns(&[column], false);
+        self.try_update(check, |s| {
+            s.try_highlight_rows_where_key(column, &values, &color.color)
+        })
+    }
+
+    fn format_percent_of_total(&mut self, column: &str, with_value: bool) -> PyResult<()> {
+        let check = self.s.check_columns(&[column], true);
+        self.try_update(check, |s| Ok(s.format_percent_of_total(column, with_value)))
+    }
+
+    fn show_row_numbers(&mut self, start: usize) {
+        self.update(|s| s.show_row_numbers(start));
+    }
+
+    /// `aggregation` is `"sum"`, `"mean"`, `"min"`, `"max"` or `"count"`.
+    fn add_summary_row(
+        &mut self,
+        label: &str,
+        aggregation: &str,
+        columns: Vec<String>,
+        sticky: bool,
+    ) -> PyResult<()> {
+        let aggregation = match aggregation {
+            "sum" => Aggregation::Sum,
+            "mean" => Aggregation::Mean,
+            "min" => Aggregation::Min,
+            "max" => Aggregation::Max,
+            "count" => Aggregation::Count,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown aggregation {}",
+                    aggregation
+                )))
+            }
+        };
+        let columns = columns.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+        let numeric = matches!(aggregation, Aggregation::Sum | Aggregation::Mean);
+        let check = self.s.check_columns(&columns, numeric);
+        self.try_update(check, |s| {
+            let s = s.try_add_summary_row(label, aggregation, &columns)?;
+            Ok(if sticky { s.sticky_footer() } else { s })
+        })
+    }
+
+    fn add_column_separator(
+        &mut self,
+        after_column: &str,
+        width: u32,
+        color: PyColor,
+        style: &str,
+    ) -> PyResult<()> {
+        let border = Border::new(width, &color.color).style(style);
+        let check = self.s.check_columns(&[after_column], false);
+        self.try_update(check, |s| s.try_add_column_separator(after_column, &border))
+    }
+
+    fn expandable_text(&mut self, column: &str, max_chars: usize) -> PyResult<()> {
+        let check = self.s.check_columns(&[column], false);
+        self.try_update(check, |s| s.try_expandable_text(column, max_chars))
+    }
+
+    fn clip_column(&mut self, column: &str, max_width: &str) -> PyResult<()> {
+        let check = self.s.check_columns(&[column], false);
+        self.try_update(check, |s| s.try_clip_column(column, max_width))
+    }
+
+    #[pyo3(signature = (column, unit=None, description=None, unit_in_values=false))]
+    fn set_column_meta(
+        &mut self,
+        column: &str,
+        unit: Option<String>,
+        description: Option<String>,
+        unit_in_values: bool,
+    ) -> PyResult<()> {
+        let meta = ColumnMeta {
+            unit,
+            description,
+            unit_in_values,
+        };
+        let check = self.s.check_columns(&[column], false);
+        self.try_update(check, |s| s.try_set_column_meta(column, meta))
+    }
+
+    fn border_series(
+        &mut self,
+        subset: Vec<String>,
+        mask: PySeries,
+        width: u32,
+        color: PyColor,
+        style: &str,
+    ) -> PyResult<()> {
+        let border = Border::new(width, &color.color).style(style);
+        let subset = subset.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+        let check = self
+            .s
+            .check_columns(&subset, false)
+            .and(self.s.check_mask(&mask.0));
+        self.try_update(check, |s| s.try_border_series(&subset, &mask.0, &border))
+    }
+
+    fn map_icons(&mut self, column: &str, icons: HashMap<String, String>) -> PyResult<()> {
+        let check = self.s.check_columns(&[column], false);
+        self.try_update(check, |s| Ok(s.map_icons(column, &icons)))
+    }
+
+    fn trend_arrows(&mut self, column: &str, compare: &str) -> PyResult<()> {
+        let check = self.s.check_columns(&[column, compare], true);
+        self.try_update(check, |s| s.try_trend_arrows(column, compare))
+    }
+
+    fn trend_arrows_series(&mut self, column: &str, compare: PySeries) -> PyResult<()> {
+        let check = self.s.check_trend_arrows(column, &compare.0);
+        self.try_update(check, |s| s.try_trend_arrows_series(column, &compare.0))
+    }
+
+    fn set_details(
+        &mut self,
+        key: &str,
+        detail: PyRef<'_, PyStyler>,
+        collapsible: bool,
+    ) -> PyResult<()> {
+        let check = self
+            .s
+            .check_columns(&[key], false)
+            .and(detail.s.check_columns(&[key], false));
+        self.try_update(check, |s| {
+            s.try_set_details(key, detail.s.clone(), collapsible)
+        })
+    }
+
+    /// Highlights cells that differ from `old`, a frame with the same columns.
+    fn diff(&mut self, old: PyDataFrame, tooltips: bool) -> PyResult<()> {
+        let style = DiffStyle {
+            tooltips,
+            ..DiffStyle::default()
+        };
+        let check = self.s.check_diff(&old.0);
+        self.try_update(check, |s| s.try_diff_with(&old.0, &style))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn background_gradient(
+        &mut self,
+        cmap: Option<PyColorMap>,
+        subset: Option<Vec<String>>,
+        vmin: Option<f64>,
+        vmax: Option<f64>,
+        text_color_threshold: Option<f64>,
+        opacity: Option<f64>,
+        color: Option<PyColor>,
+    ) -> PyResult<()> {
+        let contrast = self.s.text_contrast();
+        let color = color.map_or_else(|| Color::new(255, 0, 0), |c| c.color);
+        let subset = subset.unwrap_or_else(|| self.s.column_names());
+        let norm = Norm::Linear { vmin, vmax };
+
+        let subset = subset.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+        let check = self
+            .s
+            .check_columns(&subset, true)
+            .and_then(|_| match &cmap {
+                Some(cmap) => self.s.check_colormap(&cmap.cmap),
+                None => Ok(()),
+            });
+        self.try_update(check, |s| {
+            let mut s = s.set_gradient_opacity(opacity.unwrap_or(1.0));
+            if let Some(threshold) = text_color_threshold {
+                s = s.set_text_contrast(TextContrast::Threshold(threshold));
+            }
+            let s = match &cmap {
+                Some(cmap) => s.try_background_colormap_columns(&subset, &cmap.cmap, &norm),
+                None => s.try_background_gradient_columns(&subset, &color, &vmin, &vmax),
+            }?;
+            Ok(s.set_gradient_opacity(1.0).set_text_contrast(contrast))
+        })
+    }
+
+    /// Colormap on one scale across `subset` (all columns by default); `vmin` and `vmax`
+    /// default to the minimum and maximum across them.
+    #[pyo3(signature = (subset=None, cmap=None, vmin=None, vmax=None))]
+    fn background_gradient_shared(
+        &mut self,
+        subset: Option<Vec<String>>,
+        cmap: Option<PyColorMap>,
+        vmin: Option<f64>,
+        vmax: Option<f64>,
+    ) -> PyResult<()> {
+        let cmap = cmap.map_or_else(ColorMap::red_scale, |c| c.cmap);
+        let subset = subset.unwrap_or_else(|| self.s.column_names());
+        let subset = subset.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+        let check = self
+            .s
+            .check_columns(&subset, true)
+            .and_then(|_| self.s.check_colormap(&cmap));
+        self.try_update(check, |s| {
+            s.try_background_gradient_shared(&subset, &cmap, &vmin, &vmax)
+        })
+    }
+
+    fn bar(
+        &mut self,
+        cmap: Option<PyColorMap>,
+        subset: Option<Vec<String>>,
+        vmin: Option<f64>,
+        vmax: Option<f64>,
+        color: Option<PyColor>,
+    ) -> PyResult<()> {
+        let color = color.map_or_else(|| Color::new(255, 0, 0), |c| c.color);
+        let subset = subset.unwrap_or_else(|| self.s.column_names());
+
+        let columns = subset.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+        let check = self
+            .s
+            .check_columns(&columns, true)
+            .and_then(|_| match &cmap {
+                Some(cmap) => self.s.check_colormap(&cmap.cmap),
+                None => Ok(()),
+            });
+        self.try_update(check, |s| {
+            subset.iter().try_fold(s, |s, column| match &cmap {
+                Some(cmap) => s.try_bar_colormap(column, &cmap.cmap, &vmin, &vmax),
+                None => s.try_bar(column, &color, &vmin, &vmax),
+            })
+        })
+    }
+
+    fn data(&self) -> PyDataFrame {
+        PyDataFrame(self.s.data().clone())
+    }
+
+    /// Gradient on the column named like the series, e.g. the result of a Polars
+    /// expression evaluated on `data()`.
+    fn background_gradient_series(
+        &mut self,
+        series: PySeries,
+        color: Option<PyColor>,
+    ) -> PyResult<()> {
+        let color = color.map_or_else(|| Color::new(255, 0, 0), |c| c.color);
+        let check = self.s.check_series(&series.0);
+        self.try_update(check, |s| {
+            s.try_background_gradient_series(&series.0, &color)
+        })
+    }
+
+    /// Formats the values of the column with a Python callable returning `str`; nulls
+    /// are passed as `None`.
+    fn format(&mut self, column: &str, formatter: PyObject) {
+        let error = self.format_error.clone();
+        let f = move |v: &AnyValue| {
+            Python::with_gil(|py| {
+                let text = formatter
+                    .call1(py, (any_value_to_py(py, v),))
+                    .and_then(|text| text.extract::<String>(py));
+                text.unwrap_or_else(|e| {
+                    error.lock().unwrap().get_or_insert(e);
+                    v.to_string()
+                })
+            })
+        };
+        self.update(|s| s.set_column_formatter(column, f));
+    }
+
+    /// Declarative format of the `subset` columns (all by default), kept in the spec.
+    #[pyo3(signature = (
+        subset=None, precision=None, thousands=None, decimal=None, na_rep=None,
+        datetime=None, prefix=None, suffix=None, percent=false
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn set_format(
+        &mut self,
+        subset: Option<Vec<String>>,
+        precision: Option<u32>,
+        thousands: Option<String>,
+        decimal: Option<String>,
+        na_rep: Option<String>,
+        datetime: Option<String>,
+        prefix: Option<String>,
+        suffix: Option<String>,
+        percent: bool,
+    ) {
+        let format = CellFormat {
+            precision,
+            thousands,
+            decimal,
+            percent,
+            prefix,
+            suffix,
+            na_rep,
+            datetime,
+            booleans: None,
+        };
+        let subset = subset.unwrap_or_else(|| self.s.column_names());
+        self.update(|s| {
+            subset
+                .iter()
+                .fold(s, |s, column| s.set_column_format(column, format.clone()))
+        });
+    }
+
+    /// Pickling: the data is passed to `__new__` (Polars pickles it as IPC) and the
+    /// styling is restored from the spec. Python formatters and gradients driven by
+    /// expressions are not part of the spec and are lost.
+    fn __getnewargs__(&self) -> (PyDataFrame,) {
+        (PyDataFrame(self.s.data().clone()),)
+    }
+
+    fn __getstate__(&self) -> String {
+        self.s.spec().to_json()
+    }
+
+    fn __setstate__(&mut self, state: &str) -> PyResult<()> {
+        let spec = StyleSpec::parse(state)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        self.s = Styler::from_shared(self.s.shared_data()).apply_spec(&spec);
+        Ok(())
+    }
+
+    /// Combines with another styler of the same data; its styles and settings win.
+    fn merge(&mut self, other: PyRef<'_, PyStyler>) -> PyResult<()> {
+        let check = self.s.check_merge(&other.s);
+        self.try_update(check, |s| s.try_merge(&other.s))
+    }
+
+    fn add_computed_series(&mut self, s: PySeries, position: Option<usize>) -> PyResult<()> {
+        let check = self.s.check_computed_series(&s.0);
+        self.try_update(check, |st| st.try_add_computed_series(&s.0, position))
+    }
+
+    fn add_rank_column(&mut self, by_column: &str, descending: bool, medals: bool) -> PyResult<()> {
+        let check = self.s.check_rank_column(by_column);
+        self.try_update(check, |s| {
+            s.try_add_rank_column(by_column, descending, medals)
+        })
+    }
+
+    fn __copy__(&self) -> Self {
+        PyStyler::wrap(self.s.clone())
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        PyStyler::wrap(self.s.clone())
+    }
+
+    /// Summary of the data and the pending styling operations.
+    fn describe(&self) -> String {
+        self.s.describe()
+    }
+
+    fn __repr__(&self) -> String {
+        self.s.describe()
+    }
+
+    fn render(&self) -> PyResult<String> {
+        self.render_with(|s| s.render())
+    }
+
+    /// Notebook display: the rendered table with pandas' `dataframe` class, so it picks
+    /// up the notebook's table styling.
+    fn _repr_html_(&self) -> PyResult<String> {
+        self.render_with(|s| s.add_table_classes(vec!["dataframe".to_string()]).render())
+    }
+}
+
+#[pyclass(module = "polars_styler.polars_styler")]
+#[derive(Clone)]
+struct PyColor {
+    color: Color,
+}
+
+#[pymethods]
+impl PyColor {
+    /// Parses a hex, rgb(), hsl() or CSS named color such as "steelblue".
+    #[new]
+    fn new(color: &str) -> PyResult<Self> {
+        match Color::try_from(color) {
+            Ok(color) => Ok(PyColor { color }),
+            Err(_) => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid color {}",
+                color
+            ))),
+        }
+    }
+
+    #[staticmethod]
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        PyColor {
+            color: Color::new(r, g, b),
+        }
+    }
+
+    fn to_hex(&self) -> String {
+        self.color.to_hex()
+    }
+
+    fn to_rgb(&self) -> String {
+        self.color.to_rgb()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Color('{}')", self.color.to_hex())
+    }
+
+    fn __eq__(&self, other: &PyColor) -> bool {
+        self.color == other.color
+    }
+}
+
+#[pyclass(module = "polars_styler.polars_styler")]
+#[derive(Clone)]
+struct PyColorMap {
+    cmap: ColorMap,
+}
+
+#[pymethods]
+impl PyColorMap {
+    /// Colormap from `(value, color)` break points with unique values in `0..=1`, in
+    /// increasing order.
+    #[new]
+    fn new(points: Vec<(f64, PyColor)>) -> PyResult<Self> {
+        let points = points
+            .into_iter()
+            .map(|(value, c)| ColorBreakPoint::new(value, c.color))
+            .collect();
+        match ColorMap::try_new(points) {
+            Ok(cmap) => Ok(PyColorMap { cmap }),
+            Err(e) => Err(pyo3::exceptions::PyValueError::new_err(e.to_string())),
+        }
+    }
+
+    /// Colors evenly spaced from the lowest to the highest value.
+    #[staticmethod]
+    fn from_palette(colors: Vec<PyColor>) -> PyResult<Self> {
+        if colors.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "The palette is empty",
+            ));
+        }
+        let colors = colors.into_iter().map(|c| c.color).collect();
+        Ok(PyColorMap {
+            cmap: ColorMap::from_palette(colors),
+        })
+    }
+
+    #[staticmethod]
+    fn red_scale() -> Self {
+        PyColorMap {
+            cmap: ColorMap::red_scale(),
+        }
+    }
+
+    #[staticmethod]
+    fn three_point(low: PyColor, mid: PyColor, high: PyColor) -> Self {
+        PyColorMap {
+            cmap: ColorMap::three_point(low.color, mid.color, high.color),
+        }
+    }
+
+    fn palette(&self) -> Vec<PyColor> {
+        self.cmap
+            .palette()
+            .into_iter()
+            .map(|color| PyColor { color })
+            .collect()
+    }
+
+    /// Built-in colormap such as "viridis" or "RdYlGn".
+    #[staticmethod]
+    fn named(name: &str) -> PyResult<Self> {
+        match ColorMap::named(name) {
+            Some(cmap) => Ok(PyColorMap { cmap }),
+            None => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown colormap {}; available: {}",
+                name,
+                ColorMap::names().join(", ")
+            ))),
+        }
+    }
+
+    #[staticmethod]
+    fn names() -> Vec<&'static str> {
+        ColorMap::names()
+    }
+
+    fn reversed(&self) -> Self {
+        PyColorMap {
+            cmap: self.cmap.reversed(),
+        }
+    }
+
+    fn resampled(&self, n: usize) -> Self {
+        PyColorMap {
+            cmap: self.cmap.resampled(n),
+        }
+    }
+
+    fn is_colorblind_safe(&self) -> bool {
+        self.cmap.is_colorblind_safe()
+    }
+}
+
+#[pyfunction]
+fn pydf_to_pystyler(df: PyDataFrame) -> PyResult<PyStyler> {
+    Ok(PyStyler::wrap(Styler::from_shared(Arc::new(df.0))))
+}
+
+#[pymodule]
+fn polars_styler(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyStyler>()?;
+    m.add_class::<PyColor>()?;
+    m.add_class::<PyColorMap>()?;
+    m.add_function(wrap_pyfunction!(pydf_to_pystyler, m)?)?;
+    Ok(())
+}
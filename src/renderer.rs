@@ -1,67 +1,1082 @@
-use build_html::{Html, Table, TableRow};
-use build_html::{HtmlContainer, TableCell, TableCellType};
-use std::collections::HashMap;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
 
+const PAGINATION_JS: &str = include_str!("js/pagination.js");
+const SORT_JS: &str = include_str!("js/sort.js");
+const FILTER_JS: &str = include_str!("js/filter.js");
+const SEARCH_JS: &str = include_str!("js/search.js");
+const CSV_JS: &str = include_str!("js/csv.js");
+const WEB_COMPONENT_JS: &str = include_str!("js/web_component.js");
+
+#[derive(Default)]
 pub(crate) struct Renderer {
     pub column_labels: Vec<String>,
-    pub cell_values: Vec<Vec<String>>, // (col, row)
+    pub cell_values: Vec<ColumnValues>,
     pub cell_styles: HashMap<(usize, usize), HashMap<String, String>>,
+    pub cell_tooltips: HashMap<(usize, usize), String>, // (row, col) => title attribute
+    pub cell_prefixes: HashMap<(usize, usize), String>, // (row, col) => HTML before the value
+    pub cell_suffixes: HashMap<(usize, usize), String>, // (row, col) => HTML after the value
     pub hash: String,
     pub classes: Vec<String>,
+    pub row_ids: Vec<usize>, // original row of each rendered row; identity if empty
+    pub hidden_rows: Option<(usize, usize)>, // (rendered row before the gap, number of hidden rows)
+    pub col_ids: Vec<usize>, // original column of each rendered column; identity if empty
+    pub hidden_columns: Option<(usize, usize)>, // (rendered column before the gap, number of hidden columns)
+    pub interactivity: Interactivity,
+    pub sort_values: Vec<Vec<String>>, // (col, row), raw values used by the sort script
+    pub sort_types: Vec<String>,       // "number" or "text" for each column
+    pub print: Option<PrintOptions>,
+    pub direction: Direction,
+    pub caption: Option<String>,
+    pub row_header: Option<usize>, // original column rendered as row headers
+    pub legends: Vec<Legend>,
+    pub rules: Vec<RuleLegend>,
+    pub table_styles: Vec<TableStyle>,
+    pub cell_rules: OnceLock<CellRules>, // computed on first use
+    pub detail_rows: HashMap<usize, String>, // rendered row => nested table below it
+    pub column_separators: Vec<(usize, String)>, // rendered column => border on its right
+    pub column_units: HashMap<usize, String>, // rendered column => unit shown under the label
+    pub column_descriptions: HashMap<usize, String>, // rendered column => header tooltip
+    pub footer_rows: Vec<Vec<String>>,   // summary rows, values of each rendered column
+    pub sticky_footer: bool,
+    pub row_numbers: Option<usize>, // number of the first row when row numbers are shown
+}
+
+/// CSS of the cells that are not covered by a column rule. Declarations shared by several
+/// cells are written once for a class instead of once per cell.
+#[derive(Default)]
+pub(crate) struct CellRules {
+    classes: HashMap<(usize, usize), u64>,
+    shared: BTreeMap<u64, String>,
+    single: Vec<((usize, usize), String)>,
+}
+
+/// Displayed values of a column. Low-cardinality columns keep every distinct value once
+/// and index it from each row.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ColumnValues {
+    Plain(Vec<String>),
+    Interned {
+        values: Vec<String>,
+        index: Vec<u32>,
+    },
+}
+
+impl ColumnValues {
+    /// Interns the values, e.g. of a string column with few distinct values.
+    pub fn interned<'a>(values: impl Iterator<Item = &'a str>) -> Self {
+        let mut ids: HashMap<&str, u32> = HashMap::new();
+        let mut distinct = vec![];
+        let index = values
+            .map(|v| {
+                *ids.entry(v).or_insert_with(|| {
+                    distinct.push(v.to_string());
+                    distinct.len() as u32 - 1
+                })
+            })
+            .collect();
+        ColumnValues::Interned {
+            values: distinct,
+            index,
+        }
+    }
+
+    pub fn get(&self, row: usize) -> &str {
+        match self {
+            ColumnValues::Plain(values) => &values[row],
+            ColumnValues::Interned { values, index } => &values[index[row] as usize],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnValues::Plain(values) => values.len(),
+            ColumnValues::Interned { index, .. } => index.len(),
+        }
+    }
+
+    /// Total length of the displayed text.
+    fn text_len(&self) -> usize {
+        (0..self.len()).map(|row| self.get(row).len()).sum()
+    }
+}
+
+impl From<Vec<String>> for ColumnValues {
+    fn from(values: Vec<String>) -> Self {
+        ColumnValues::Plain(values)
+    }
+}
+
+/// CSS rule scoped to the table, like an entry of pandas `set_table_styles`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TableStyle {
+    /// Selector relative to the table, e.g. `th` or `tbody tr:hover`; empty for the table.
+    pub selector: String,
+    /// Declarations, e.g. `color: red; font-weight: bold`.
+    pub props: String,
+}
+
+impl TableStyle {
+    pub fn new(selector: &str, props: &str) -> Self {
+        TableStyle {
+            selector: selector.to_string(),
+            props: props.to_string(),
+        }
+    }
+}
+
+/// Color bar explaining a gradient from `min` to `max`; `stops` are CSS color stops.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Legend {
+    pub label: String,
+    pub stops: Vec<String>,
+    pub min: String,
+    pub max: String,
+}
+
+/// Entry of the legend of conditional rules: how the cells are marked and when.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RuleLegend {
+    pub marker: RuleMarker,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RuleMarker {
+    /// CSS color of the cell background.
+    Background(String),
+    /// HTML put next to the values, with its color if it's a glyph.
+    Icon {
+        glyph: String,
+        color: Option<String>,
+    },
+}
+
+/// Custom CSS property marking highlighted cells, so print output can emphasize them
+/// with something that survives grayscale printing.
+pub(crate) const HIGHLIGHT_PROPERTY: &str = "--ps-highlight";
+
+/// Opt-in client-side behaviour implemented by small embedded scripts.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Interactivity {
+    /// Number of rows per page; tables with more rows get previous/next controls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
+    /// Headers sort the table when clicked.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sortable: bool,
+    /// Adds a row of filter inputs under the headers.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub filterable: bool,
+    /// Adds a search box above the table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<SearchMode>,
+    /// Adds a button downloading the table as a CSV file with this name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub csv_download: Option<String>,
+}
+
+/// What the search box does with cells matching the query.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    /// Outline matching cells.
+    Highlight,
+    /// Outline matching cells and hide rows without a match.
+    Filter,
+}
+
+/// Text direction of the table.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[default]
+    Ltr,
+    /// Right-to-left, e.g. for Arabic or Hebrew; columns are laid out from the right.
+    Rtl,
+}
+
+/// `@media print` rules, so HTML reports print (or save as PDF) cleanly.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PrintOptions {
+    /// Repeats the header row at the top of every printed page.
+    pub repeat_headers: bool,
+    /// Keeps each row on a single page.
+    pub avoid_row_split: bool,
+    /// Forces background colors to be printed and makes highlighted cells bold and
+    /// underlined, so they stand out even without color.
+    pub grayscale_safe: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            repeat_headers: true,
+            avoid_row_split: true,
+            grayscale_safe: true,
+        }
+    }
+}
+
+impl Interactivity {
+    /// Whether cells need raw values in `data-sort-value` for the scripts.
+    pub fn needs_raw_values(&self) -> bool {
+        self.sortable || self.filterable || self.csv_download.is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Interactivity::default()
+    }
+}
+
+/// Template-engine friendly view of a styled table.
+///
+/// Contains everything needed to write the markup by hand: header labels, formatted
+/// values and the CSS declarations of every cell.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TableContext {
+    pub uid: String,
+    pub classes: Vec<String>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<CellContext>>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CellContext {
+    pub id: String,
+    pub value: String,
+    pub style: String,
 }
 
 impl Renderer {
+    pub fn context(&self) -> TableContext {
+        let nrow = self.nrow();
+        let ncol = self.cell_values.len();
+        let rows = (0..nrow)
+            .map(|row| {
+                (0..ncol)
+                    .map(|col| CellContext {
+                        id: cell_id(&self.hash, &self.row_id(row), &self.col_id(col)),
+                        value: self.cell_values[col].get(row).to_string(),
+                        style: self
+                            .cell_styles
+                            .get(&(row, col))
+                            .map(css_styles)
+                            .unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .collect();
+        TableContext {
+            uid: self.hash.clone(),
+            classes: self.classes.clone(),
+            headers: self.column_labels.clone(),
+            rows,
+        }
+    }
+
     pub fn render(&self) -> String {
+        let mut out = String::with_capacity(self.table_capacity());
+        let _ = write!(out, "<div{}>\n  ", self.wrapper_attributes());
+        self.write_styles(&mut out);
+        let _ = write!(out, "\n  {}", self.toolbar());
+        self.write_table(&mut out);
+        out.push_str(&self.legend());
+        out.push_str(&self.scripts(&format!("document.getElementById(\"{}\")", self.table_id())));
+        out.push_str("\n</div>");
+        out
+    }
+
+    /// Renders the table inside a `<polars-table>` custom element with an open shadow root,
+    /// so that its styles neither leak into nor get overridden by the host page.
+    pub fn render_web_component(&self) -> String {
+        let host_id = format!("{}_host", self.table_id());
+        let table = format!(
+            "document.getElementById(\"{}\").shadowRoot.getElementById(\"{}\")",
+            host_id,
+            self.table_id()
+        );
         format!(
-            "<div>\n  {}\n  {}\n</div>",
+            "<polars-table id=\"{}\"{}>\n  <template shadowrootmode=\"open\">\n  {}\n  {}{}{}\n  </template>\n</polars-table>\n<script>\n{}</script>{}",
+            host_id,
+            self.wrapper_attributes(),
             self.styles(),
-            self.table().to_html_string()
+            self.toolbar(),
+            self.table(),
+            self.legend(),
+            WEB_COMPONENT_JS,
+            self.scripts(&table)
         )
     }
 
-    fn styles(&self) -> String {
-        let foo_styles = self
-            .cell_styles
+    /// Renders only the body rows (`<tr>` elements), e.g. to be swapped into an existing
+    /// `<tbody>` by htmx or Turbo. Ids are stable as long as the table uid is.
+    pub fn render_rows(&self) -> String {
+        let mut out = String::with_capacity(self.table_capacity());
+        self.write_body_rows(&mut out);
+        out
+    }
+
+    /// Each body row by its element id, with the detail row below it if any.
+    pub fn row_fragments(&self) -> Vec<(String, String)> {
+        (0..self.nrow())
+            .map(|row| {
+                let mut out = String::new();
+                self.write_row(&mut out, row);
+                self.write_detail_row(&mut out, row);
+                (format!("T_{}_row{}", self.hash, self.row_id(row)), out)
+            })
+            .collect()
+    }
+
+    /// Renders the `<tbody>` element with its id, without header, styles or scripts.
+    pub fn render_body(&self) -> String {
+        format!(
+            "<tbody id=\"{}\">{}</tbody>",
+            self.body_id(),
+            self.render_rows()
+        )
+    }
+
+    /// Renders the CSS rules of the table without the surrounding `<style>` element.
+    pub fn render_css(&self) -> String {
+        let mut out = String::new();
+        self.write_css(&mut out, "\n");
+        out
+    }
+
+    /// Attributes of the element wrapping the table: text direction and, when the table has
+    /// a caption, a labelled ARIA region that can be focused to scroll it with the keyboard.
+    fn wrapper_attributes(&self) -> String {
+        let mut attributes = String::new();
+        if self.direction == Direction::Rtl {
+            attributes += " dir=\"rtl\"";
+        }
+        if let Some(caption) = &self.caption {
+            attributes += &format!(
+                " role=\"region\" aria-label=\"{}\" tabindex=\"0\"",
+                escape_attribute(caption)
+            );
+        }
+        attributes
+    }
+
+    fn table_id(&self) -> String {
+        format!("T_{}", self.hash)
+    }
+
+    fn body_id(&self) -> String {
+        format!("T_{}_body", self.hash)
+    }
+
+    /// Interactive scripts; `table` is the JS expression that looks up the table element.
+    fn scripts(&self, table: &str) -> String {
+        let mut scripts = vec![];
+        if let Some(page_size) = self.interactivity.page_size {
+            scripts.push(self.script(
+                table,
+                PAGINATION_JS,
+                serde_json::json!({ "pageSize": page_size }),
+            ));
+        }
+        if self.interactivity.sortable {
+            scripts.push(self.script(table, SORT_JS, serde_json::json!({})));
+        }
+        if self.interactivity.filterable {
+            scripts.push(self.script(table, FILTER_JS, serde_json::json!({})));
+        }
+        if let Some(mode) = self.interactivity.search {
+            let options = serde_json::json!({ "hideRows": mode == SearchMode::Filter });
+            scripts.push(self.script(table, SEARCH_JS, options));
+        }
+        if let Some(filename) = &self.interactivity.csv_download {
+            let options = serde_json::json!({ "filename": filename });
+            scripts.push(self.script(table, CSV_JS, options));
+        }
+        scripts.concat()
+    }
+
+    /// Controls placed above the table.
+    fn toolbar(&self) -> String {
+        let mut controls = String::new();
+        if self.interactivity.search.is_some() {
+            controls += &format!(
+                "<input type=\"search\" id=\"{}_search\" class=\"ps-search\" placeholder=\"Search…\" aria-label=\"Search table\">\n  ",
+                self.table_id()
+            );
+        }
+        if self.interactivity.csv_download.is_some() {
+            controls += &format!(
+                "<button type=\"button\" id=\"{}_csv\" class=\"ps-csv\">Download CSV</button>\n  ",
+                self.table_id()
+            );
+        }
+        controls
+    }
+
+    /// Color bars of the gradients and the list of rules, placed below the table.
+    fn legend(&self) -> String {
+        let rules = self.rules_legend();
+        if self.legends.is_empty() {
+            return rules;
+        }
+        let items = self
+            .legends
             .iter()
-            .map(|((row, col), styles)| {
+            .enumerate()
+            .map(|(i, legend)| {
                 format!(
-                    "#{} {{{}}}",
-                    cell_id(&self.hash, row, col),
-                    css_styles(styles)
+                    "\n    <div class=\"ps-legend-item\"><span class=\"ps-legend-label\">{}</span> \
+                     <span>{}</span><span class=\"ps-legend-bar\" id=\"{}_legend{}\"></span><span>{}</span></div>",
+                    escape_attribute(&legend.label),
+                    escape_attribute(&legend.min),
+                    self.table_id(),
+                    i,
+                    escape_attribute(&legend.max)
                 )
             })
-            .collect::<Vec<_>>()
-            .join("\n  ");
-        format!("<style>\n  {}\n</style>", foo_styles)
+            .collect::<String>();
+        format!(
+            "\n  <div class=\"ps-legend\" id=\"{}_legend\">{}\n  </div>{}",
+            self.table_id(),
+            items,
+            rules
+        )
+    }
+
+    fn rules_legend(&self) -> String {
+        if self.rules.is_empty() {
+            return String::new();
+        }
+        let items = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let marker = match &rule.marker {
+                    RuleMarker::Background(color) => format!(
+                        "<span class=\"ps-rule-swatch\" style=\"background-color: {}\"></span>",
+                        color
+                    ),
+                    RuleMarker::Icon { glyph, color } => match color {
+                        Some(color) => format!(
+                            "<span class=\"ps-rule-icon\" style=\"color: {}\">{}</span>",
+                            color, glyph
+                        ),
+                        None => format!("<span class=\"ps-rule-icon\">{}</span>", glyph),
+                    },
+                };
+                format!(
+                    "\n    <div class=\"ps-rule\">{}{}</div>",
+                    marker,
+                    escape_attribute(&rule.label)
+                )
+            })
+            .collect::<String>();
+        format!(
+            "\n  <div class=\"ps-rules\" id=\"{}_rules\">{}\n  </div>",
+            self.table_id(),
+            items
+        )
+    }
+
+    fn legend_styles(&self) -> Vec<String> {
+        let id = self.table_id();
+        let rules = match self.rules.is_empty() {
+            true => vec![],
+            false => vec![
+                format!(
+                    "#{id}_rules .ps-rule-swatch {{display: inline-block; width: 0.8em; \
+                     height: 0.8em; margin-right: 0.4em; vertical-align: middle}}"
+                ),
+                format!("#{id}_rules .ps-rule-icon {{margin-right: 0.4em}}"),
+            ],
+        };
+        if self.legends.is_empty() {
+            return rules;
+        }
+        let bar = format!(
+            "#{id}_legend .ps-legend-bar {{display: inline-block; width: 8em; height: 0.8em; \
+             margin: 0 0.4em; vertical-align: middle}}"
+        );
+        let gradients = self.legends.iter().enumerate().map(|(i, legend)| {
+            format!(
+                "#{id}_legend{i} {{background: linear-gradient(to right, {})}}",
+                legend.stops.join(", ")
+            )
+        });
+        std::iter::once(bar).chain(gradients).chain(rules).collect()
+    }
+
+    /// Wraps a script body into a function called with the table element and its options.
+    fn script(&self, table: &str, body: &str, options: serde_json::Value) -> String {
+        // `<` is escaped so that a string option can't close the script element early.
+        let options = options.to_string().replace('<', "\\u003c");
+        format!(
+            "\n  <script>\n(function (table, options) {{\n{}}})({}, {});\n</script>",
+            body, table, options
+        )
+    }
+
+    /// Renders values as a GitHub-flavored markdown table. Styles are dropped.
+    pub fn render_markdown(&self) -> String {
+        let labels = self.header_labels();
+        let header = markdown_row(&labels);
+        let separator = markdown_row(&vec!["---".to_string(); labels.len()]);
+        let mut rows = (0..self.nrow())
+            .map(|row| markdown_row(&self.row_values(row)))
+            .collect::<Vec<_>>();
+        if let Some((position, _)) = self.hidden_rows {
+            rows.insert(position, markdown_row(&self.ellipsis_values()));
+        }
+        [vec![header, separator], rows].concat().join("\n") + "\n"
+    }
+
+    /// Renders values as a LaTeX `tabular` environment. Styles are dropped.
+    pub fn render_latex(&self) -> String {
+        let labels = self.header_labels();
+        let alignment = match self.direction {
+            Direction::Ltr => "l",
+            Direction::Rtl => "r",
+        }
+        .repeat(labels.len());
+        let mut lines = vec![
+            format!("\\begin{{tabular}}{{{}}}", alignment),
+            "\\hline".to_string(),
+            latex_row(&labels),
+            "\\hline".to_string(),
+        ];
+        let mut rows = (0..self.nrow())
+            .map(|row| latex_row(&self.row_values(row)))
+            .collect::<Vec<_>>();
+        if let Some((position, _)) = self.hidden_rows {
+            rows.insert(position, latex_row(&self.ellipsis_values()));
+        }
+        lines.extend(rows);
+        lines.push("\\hline".to_string());
+        lines.push("\\end{tabular}".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    fn nrow(&self) -> usize {
+        self.cell_values.first().map_or(0, |c| c.len())
+    }
+
+    fn row_values(&self, row: usize) -> Vec<String> {
+        let mut values = self
+            .cell_values
+            .iter()
+            .map(|c| c.get(row).to_string())
+            .collect::<Vec<_>>();
+        if let Some((position, _)) = self.hidden_columns {
+            values.insert(position, "…".to_string());
+        }
+        values
+    }
+
+    fn header_labels(&self) -> Vec<String> {
+        let mut labels = self
+            .column_labels
+            .iter()
+            .enumerate()
+            .map(|(col, label)| match self.column_units.get(&col) {
+                Some(unit) => format!("{} ({})", label, unit),
+                None => label.clone(),
+            })
+            .collect::<Vec<_>>();
+        if let Some((position, _)) = self.hidden_columns {
+            labels.insert(position, "…".to_string());
+        }
+        labels
+    }
+
+    fn row_id(&self, row: usize) -> usize {
+        self.row_ids.get(row).copied().unwrap_or(row)
+    }
+
+    fn col_id(&self, col: usize) -> usize {
+        self.col_ids.get(col).copied().unwrap_or(col)
+    }
+
+    fn ellipsis_values(&self) -> Vec<String> {
+        vec!["…".to_string(); self.header_labels().len()]
+    }
+
+    fn styles(&self) -> String {
+        let mut out = String::new();
+        self.write_styles(&mut out);
+        out
+    }
+
+    fn write_styles(&self, out: &mut String) {
+        out.push_str("<style>\n  ");
+        self.write_css(out, "\n  ");
+        out.push_str("\n</style>");
+    }
+
+    /// Writes the CSS rules separated by `separator`. Per-cell rules, of which there can be
+    /// one for every cell, are written without intermediate strings.
+    fn write_css(&self, out: &mut String, separator: &str) {
+        let uniform = self.uniform_columns();
+        let (before, after) = self.css_rules(&uniform);
+        let rules = self.cell_rules();
+        out.reserve(rules.single.len() * (self.hash.len() + 64));
+        let mut first = true;
+        let mut separate = |out: &mut String| {
+            if !std::mem::take(&mut first) {
+                out.push_str(separator);
+            }
+        };
+        for rule in before {
+            separate(out);
+            out.push_str(&rule);
+        }
+        for (class, css) in &rules.shared {
+            separate(out);
+            let _ = write!(out, ".{} {{{}}}", self.style_class(*class), css);
+        }
+        for ((row, col), css) in &rules.single {
+            separate(out);
+            out.push('#');
+            self.write_cell_id(out, *row, *col);
+            out.push_str(" {");
+            out.push_str(css);
+            out.push('}');
+        }
+        for rule in after {
+            separate(out);
+            out.push_str(&rule);
+        }
+    }
+
+    fn cell_rules(&self) -> &CellRules {
+        self.cell_rules.get_or_init(|| {
+            let uniform = self.uniform_columns();
+            let mut groups: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+            for (&cell, styles) in &self.cell_styles {
+                let css = css_styles(styles);
+                if !uniform.contains_key(&cell.1) && !css.is_empty() {
+                    groups.entry(css).or_default().push(cell);
+                }
+            }
+            let mut rules = CellRules::default();
+            for (css, cells) in groups {
+                if let [cell] = cells[..] {
+                    rules.single.push((cell, css));
+                    continue;
+                }
+                let mut hasher = DefaultHasher::new();
+                css.hash(&mut hasher);
+                let class = hasher.finish();
+                rules
+                    .classes
+                    .extend(cells.into_iter().map(|cell| (cell, class)));
+                rules.shared.insert(class, css);
+            }
+            rules.single.sort();
+            rules
+        })
+    }
+
+    /// Class of the cells sharing the declarations hashed to `class`; it is unique per table.
+    fn style_class(&self, class: u64) -> String {
+        format!("T_{}_s{:x}", self.hash, class)
+    }
+
+    /// Rules written before and after the per-cell rules.
+    fn css_rules(
+        &self,
+        uniform: &BTreeMap<usize, &HashMap<String, String>>,
+    ) -> (Vec<String>, Vec<String>) {
+        let column_rules = uniform.iter().map(|(&col, styles)| {
+            format!(
+                "#{} > tbody > tr:not(.ellipsis):not(.ps-detail) > :nth-child({}) {{{}}}",
+                self.table_id(),
+                self.position(col) + 1,
+                css_styles(styles)
+            )
+        });
+        let id = self.table_id();
+        let table_rules = self.table_styles.iter().map(|style| {
+            let selector = style
+                .selector
+                .split(',')
+                .map(|part| format!("#{} {}", id, part.trim()).trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "{} {{{}}}",
+                selector,
+                style.props.trim().trim_end_matches(';')
+            )
+        });
+        let separator_rules = self.column_separators.iter().map(|(col, border)| {
+            let n = self.position(*col) + 1;
+            format!(
+                "#{id} > thead > tr > :nth-child({n}), \
+                 #{id} > tbody > tr:not(.ellipsis):not(.ps-detail) > :nth-child({n}) \
+                 {{border-right: {border}}}"
+            )
+        });
+        let details = (!self.detail_rows.is_empty())
+            .then(|| format!("#{} > tbody > tr.ps-detail > td {{padding-left: 2em}}", id));
+        let footer = (self.sticky_footer && !self.footer_rows.is_empty()).then(|| {
+            format!(
+                "#{} > tfoot {{position: sticky; bottom: 0; background-color: white}}",
+                id
+            )
+        });
+        let after = self
+            .legend_styles()
+            .into_iter()
+            .chain(details)
+            .chain(footer)
+            .chain(self.script_styles())
+            .chain(self.print_styles())
+            .collect();
+        let before = table_rules
+            .chain(column_rules)
+            .chain(separator_rules)
+            .collect();
+        (before, after)
+    }
+
+    /// Columns whose cells all have the same styles; they get a single rule instead of
+    /// one per cell.
+    fn uniform_columns(&self) -> BTreeMap<usize, &HashMap<String, String>> {
+        let nrow = self.nrow();
+        if nrow < 2 {
+            return BTreeMap::new();
+        }
+        (0..self.cell_values.len())
+            .filter_map(|col| {
+                let first = self.cell_styles.get(&(0, col))?;
+                (1..nrow)
+                    .all(|row| self.cell_styles.get(&(row, col)) == Some(first))
+                    .then_some((col, first))
+            })
+            .collect()
     }
 
-    fn table(&self) -> Table {
-        if self.cell_values.is_empty() {
-            // It may be possible to set `nrow = 0` and have the table rendered
-            panic!("No data to render; there are no columns in the DataFrame.");
+    /// Index of the rendered column among the cells of an HTML row, counting the row
+    /// number and ellipsis cells.
+    fn position(&self, col: usize) -> usize {
+        let numbers = usize::from(self.row_numbers.is_some());
+        match self.hidden_columns {
+            Some((position, _)) if position <= col => col + 1 + numbers,
+            _ => col + numbers,
         }
-        let nrow = self.cell_values[0].len();
+    }
 
-        (0..nrow)
-            .map(|i| self.row(i))
-            .fold(Table::new(), |table, row| table.with_custom_body_row(row))
-            .with_header_row(&self.column_labels)
-            .with_attributes([("class".to_string(), self.classes.join(" "))])
+    /// Number of cells in an HTML row.
+    fn html_width(&self) -> usize {
+        self.header_labels().len() + usize::from(self.row_numbers.is_some())
+    }
+
+    fn script_styles(&self) -> Option<String> {
+        if self.interactivity.is_empty() {
+            return None;
+        }
+        let id = self.table_id();
+        Some(format!(
+            "#{id} .ps-page-hidden, #{id} .ps-filter-hidden, #{id} .ps-search-hidden {{display: none}}\n  \
+             #{id} .ps-search-match {{outline: 2px solid #f5c518}}"
+        ))
     }
 
-    fn row(&self, row: usize) -> TableRow {
+    fn print_styles(&self) -> Option<String> {
+        let options = self.print.as_ref()?;
+        let id = self.table_id();
+        let mut rules = vec![];
+        if options.repeat_headers {
+            rules.push(format!("#{id} thead {{display: table-header-group}}"));
+            if !self.footer_rows.is_empty() {
+                rules.push(format!("#{id} tfoot {{display: table-footer-group}}"));
+            }
+        }
+        if options.avoid_row_split {
+            rules.push(format!(
+                "#{id} tr {{break-inside: avoid; page-break-inside: avoid}}"
+            ));
+        }
+        if options.grayscale_safe {
+            rules.push(format!(
+                "#{id} td {{print-color-adjust: exact; -webkit-print-color-adjust: exact}}"
+            ));
+            let mut highlighted = self
+                .cell_styles
+                .iter()
+                .filter(|(_, styles)| styles.contains_key(HIGHLIGHT_PROPERTY))
+                .map(|((row, col), _)| cell_id(&self.hash, &self.row_id(*row), &self.col_id(*col)))
+                .collect::<Vec<_>>();
+            highlighted.sort();
+            rules.extend(
+                highlighted.into_iter().map(|cell| {
+                    format!("#{cell} {{font-weight: bold; text-decoration: underline}}")
+                }),
+            );
+        }
+        if !self.interactivity.is_empty() {
+            // Controls are useless on paper and every page of rows is printed.
+            rules.push(format!(
+                "#{id}_search, #{id}_csv, #{id} + .ps-pagination, #{id} .ps-filters {{display: none}}"
+            ));
+            rules.push(format!("#{id} tr.ps-page-hidden {{display: table-row}}"));
+        }
+        Some(format!(
+            "@media print {{\n    {}\n  }}",
+            rules.join("\n    ")
+        ))
+    }
+
+    /// Writes the `<table>` element. Rows are written straight into one pre-sized buffer,
+    /// which matters for tables with many thousands of cells.
+    fn write_table(&self, out: &mut String) {
+        let _ = write!(
+            out,
+            "<table id=\"{}\" class=\"{}\"",
+            self.table_id(),
+            self.classes.join(" ")
+        );
+        if self.direction == Direction::Rtl {
+            out.push_str(" dir=\"rtl\"");
+        }
+        out.push('>');
+        if let Some(caption) = &self.caption {
+            let _ = write!(out, "<caption>{}</caption>", escape_attribute(caption));
+        }
+        out.push_str("<thead>");
+        self.write_header_row(out);
+        if !self.column_units.is_empty() {
+            self.write_units_row(out);
+        }
+        if self.interactivity.filterable {
+            self.write_filter_row(out);
+        }
+        let _ = write!(out, "</thead><tbody id=\"{}\">", self.body_id());
+        self.write_body_rows(out);
+        out.push_str("</tbody>");
+        if !self.footer_rows.is_empty() {
+            self.write_footer(out);
+        }
+        out.push_str("</table>");
+    }
+
+    /// Rough size of the table markup, so the buffer is allocated once.
+    fn table_capacity(&self) -> usize {
+        let values = self
+            .cell_values
+            .iter()
+            .map(ColumnValues::text_len)
+            .sum::<usize>();
+        values + self.cell_values.len() * self.nrow() * (2 * self.hash.len() + 40) + 1024
+    }
+
+    fn write_body_rows(&self, out: &mut String) {
+        let nrow = self.nrow();
+        for row in 0..=nrow {
+            match self.hidden_rows {
+                Some((position, count)) if position == row => self.write_ellipsis_row(out, count),
+                _ => {}
+            }
+            if row < nrow {
+                self.write_row(out, row);
+                self.write_detail_row(out, row);
+            }
+        }
+    }
+
+    fn write_detail_row(&self, out: &mut String, row: usize) {
+        if let Some(html) = self.detail_rows.get(&row) {
+            let _ = write!(
+                out,
+                "<tr class=\"ps-detail\"><td colspan=\"{}\">{}</td></tr>",
+                self.html_width(),
+                html
+            );
+        }
+    }
+
+    fn write_row(&self, out: &mut String, row: usize) {
+        let _ = write!(out, "<tr id=\"T_{}_row{}\">", self.hash, self.row_id(row));
+        if let Some(start) = self.row_numbers {
+            let _ = write!(
+                out,
+                "<th class=\"ps-row-number\" scope=\"row\">{}</th>",
+                start + self.row_id(row)
+            );
+        }
         let ncol = self.cell_values.len();
-        (0..ncol)
-            .map(|j| self.cell(row, j))
-            .fold(TableRow::new(), |row, cell| row.with_cell(cell))
+        for col in 0..=ncol {
+            match self.hidden_columns {
+                Some((position, count)) if position == col => {
+                    let _ = write!(
+                        out,
+                        "<td class=\"ellipsis\" title=\"{} more columns\">…</td>",
+                        thousands(count)
+                    );
+                }
+                _ => {}
+            }
+            if col < ncol {
+                self.write_cell(out, row, col);
+            }
+        }
+        out.push_str("</tr>");
+    }
+
+    fn write_header_row(&self, out: &mut String) {
+        out.push_str("<tr>");
+        if self.row_numbers.is_some() {
+            out.push_str("<th class=\"ps-row-number\"></th>");
+        }
+        let ncol = self.column_labels.len();
+        for col in 0..=ncol {
+            if matches!(self.hidden_columns, Some((position, _)) if position == col) {
+                out.push_str("<th class=\"ellipsis\">…</th>");
+            }
+            if col == ncol {
+                break;
+            }
+            out.push_str("<th scope=\"col\"");
+            match self.sort_types.get(col) {
+                Some(t) if self.interactivity.sortable => {
+                    let _ = write!(out, " data-sort-type=\"{}\"", t);
+                }
+                _ => {}
+            }
+            if let Some(description) = self.column_descriptions.get(&col) {
+                let _ = write!(out, " title=\"{}\"", escape_attribute(description));
+            }
+            let _ = write!(out, ">{}</th>", self.column_labels[col]);
+        }
+        out.push_str("</tr>");
+    }
+
+    fn write_units_row(&self, out: &mut String) {
+        out.push_str("<tr class=\"ps-units\">");
+        if self.row_numbers.is_some() {
+            out.push_str("<th></th>");
+        }
+        let ncol = self.column_labels.len();
+        for col in 0..=ncol {
+            if matches!(self.hidden_columns, Some((position, _)) if position == col) {
+                out.push_str("<th></th>");
+            }
+            if col == ncol {
+                break;
+            }
+            let unit = self.column_units.get(&col).map(|u| escape_attribute(u));
+            let _ = write!(out, "<th>{}</th>", unit.unwrap_or_default());
+        }
+        out.push_str("</tr>");
+    }
+
+    fn write_filter_row(&self, out: &mut String) {
+        out.push_str("<tr class=\"ps-filters\">");
+        if self.row_numbers.is_some() {
+            out.push_str("<th></th>");
+        }
+        let ncol = self.column_labels.len();
+        for col in 0..=ncol {
+            if matches!(self.hidden_columns, Some((position, _)) if position == col) {
+                out.push_str("<th></th>");
+            }
+            if col == ncol {
+                break;
+            }
+            let _ = write!(
+                out,
+                "<th><input type=\"search\" data-col=\"{}\" placeholder=\"Filter\" aria-label=\"Filter {}\"></th>",
+                self.position(col),
+                escape_attribute(&self.column_labels[col])
+            );
+        }
+        out.push_str("</tr>");
+    }
+
+    fn write_footer(&self, out: &mut String) {
+        out.push_str("<tfoot>");
+        for values in &self.footer_rows {
+            out.push_str("<tr class=\"ps-summary\">");
+            if self.row_numbers.is_some() {
+                out.push_str("<th class=\"ps-row-number\"></th>");
+            }
+            for (col, value) in values.iter().enumerate() {
+                if matches!(self.hidden_columns, Some((position, _)) if position == col) {
+                    out.push_str("<td class=\"ellipsis\">…</td>");
+                }
+                let _ = write!(out, "<td>{}</td>", value);
+            }
+            if matches!(self.hidden_columns, Some((position, _)) if position == values.len()) {
+                out.push_str("<td class=\"ellipsis\">…</td>");
+            }
+            out.push_str("</tr>");
+        }
+        out.push_str("</tfoot>");
+    }
+
+    fn write_ellipsis_row(&self, out: &mut String, count: usize) {
+        let _ = write!(
+            out,
+            "<tr class=\"ellipsis\"><td colspan=\"{}\">… {} more rows …</td></tr>",
+            self.html_width(),
+            thousands(count)
+        );
     }
 
-    fn cell(&self, row: usize, col: usize) -> TableCell {
-        let cell_id = format!("T_{}_row{}_col{}", &self.hash, row, col);
-        let inner = &self.cell_values[col][row];
-        TableCell::new(TableCellType::Data)
-            .with_attributes([("id".to_string(), cell_id)])
-            .with_raw(inner)
+    fn write_cell(&self, out: &mut String, row: usize, col: usize) {
+        let header = self.row_header == Some(self.col_id(col));
+        let tag = if header { "th" } else { "td" };
+        out.push('<');
+        out.push_str(tag);
+        out.push_str(" id=\"");
+        self.write_cell_id(out, row, col);
+        out.push('"');
+        if let Some(class) = self.cell_rules().classes.get(&(row, col)) {
+            let _ = write!(out, " class=\"{}\"", self.style_class(*class));
+        }
+        if let Some(title) = self.cell_tooltips.get(&(row, col)) {
+            let _ = write!(out, " title=\"{}\"", escape_attribute(title));
+        }
+        if self.interactivity.needs_raw_values() {
+            if let Some(value) = self.sort_values.get(col).and_then(|c| c.get(row)) {
+                let _ = write!(out, " data-sort-value=\"{}\"", escape_attribute(value));
+            }
+        }
+        if header {
+            out.push_str(" scope=\"row\"");
+        }
+        out.push('>');
+        if let Some(prefix) = self.cell_prefixes.get(&(row, col)) {
+            out.push_str(prefix);
+        }
+        out.push_str(self.cell_values[col].get(row));
+        if let Some(suffix) = self.cell_suffixes.get(&(row, col)) {
+            out.push_str(suffix);
+        }
+        out.push_str("</");
+        out.push_str(tag);
+        out.push('>');
+    }
+
+    /// Writes `T_<uid>_row<row>_col<col>` with the original row and column.
+    fn write_cell_id(&self, out: &mut String, row: usize, col: usize) {
+        out.push_str("T_");
+        out.push_str(&self.hash);
+        let _ = write!(out, "_row{}_col{}", self.row_id(row), self.col_id(col));
+    }
+
+    /// The `<table>` element as a string.
+    fn table(&self) -> String {
+        let mut out = String::with_capacity(self.table_capacity());
+        self.write_table(&mut out);
+        out
     }
 }
 
@@ -69,12 +1084,70 @@ fn cell_id(hash: &str, row: &usize, col: &usize) -> String {
     format!("T_{}_row{}_col{}", hash, row, col)
 }
 
+fn escape_attribute(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn markdown_row(values: &[String]) -> String {
+    let cells = values
+        .iter()
+        .map(|v| v.replace('|', "\\|").replace('\n', " "))
+        .collect::<Vec<_>>();
+    format!("| {} |", cells.join(" | "))
+}
+
+fn latex_row(values: &[String]) -> String {
+    let cells = values.iter().map(|v| escape_latex(v)).collect::<Vec<_>>();
+    format!("{} \\\\", cells.join(" & "))
+}
+
+fn escape_latex(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\\' => "\\textbackslash{}".to_string(),
+            '~' => "\\textasciitilde{}".to_string(),
+            '^' => "\\textasciicircum{}".to_string(),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => format!("\\{}", c),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
 fn css_styles(styles: &HashMap<String, String>) -> String {
-    styles
+    let mut out = String::new();
+    write_css_styles(&mut out, styles);
+    out
+}
+
+/// Writes the declarations sorted by property, so equal styles give equal text.
+fn write_css_styles(out: &mut String, styles: &HashMap<String, String>) {
+    let declarations = styles
         .iter()
-        .map(|(attr, val)| format!("{}: {}", attr, val))
-        .collect::<Vec<_>>()
-        .join("; ")
+        .filter(|(attr, _)| attr.as_str() != HIGHLIGHT_PROPERTY)
+        .sorted();
+    for (i, (attr, val)) in declarations.enumerate() {
+        if i > 0 {
+            out.push_str("; ");
+        }
+        out.push_str(attr);
+        out.push_str(": ");
+        out.push_str(val);
+    }
 }
 
 #[cfg(test)]
@@ -99,14 +1172,274 @@ mod test {
         let hash = "asdf".to_string();
         let renderer = Renderer {
             column_labels: column_names,
-            cell_values,
+            cell_values: cell_values.into_iter().map(ColumnValues::from).collect(),
             cell_styles,
             hash,
             classes: vec!["foo".to_string(), "bar".to_string()],
+            ..Default::default()
         };
         println!("{}", renderer.render());
     }
 
+    #[test]
+    fn test_shared_cell_styles() {
+        let red = HashMap::from([("color".to_string(), "red".to_string())]);
+        let blue = HashMap::from([("color".to_string(), "blue".to_string())]);
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string(), "y".to_string()],
+            cell_values: vec![
+                ColumnValues::interned(["a", "b", "a"].into_iter()),
+                vec!["1".to_string(), "2".to_string(), "3".to_string()].into(),
+            ],
+            cell_styles: HashMap::from([((0, 0), red.clone()), ((2, 0), red), ((1, 1), blue)]),
+            hash: "asdf".to_string(),
+            ..Default::default()
+        };
+        let html = renderer.render();
+        let class = html
+            .split("class=\"")
+            .nth(2)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap();
+        assert_eq!(html.matches(&format!("class=\"{}\"", class)).count(), 2);
+        assert!(html.contains(&format!(".{} {{color: red}}", class)));
+        assert!(html.contains("#T_asdf_row1_col1 {color: blue}"));
+        assert!(html.contains("<td id=\"T_asdf_row2_col0\" class=\""));
+    }
+
+    #[test]
+    fn test_table_markup() {
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string()],
+            cell_values: vec![vec!["1".to_string(), "2".to_string()].into()],
+            hash: "asdf".to_string(),
+            classes: vec!["foo".to_string()],
+            caption: Some("Cap".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            renderer.table(),
+            "<table id=\"T_asdf\" class=\"foo\"><caption>Cap</caption>\
+             <thead><tr><th scope=\"col\">x</th></tr></thead><tbody id=\"T_asdf_body\">\
+             <tr id=\"T_asdf_row0\"><td id=\"T_asdf_row0_col0\">1</td></tr>\
+             <tr id=\"T_asdf_row1\"><td id=\"T_asdf_row1_col0\">2</td></tr></tbody></table>"
+        );
+    }
+
+    #[test]
+    fn test_context() {
+        let renderer = Renderer {
+            column_labels: vec!["col1".to_string()],
+            cell_values: vec![vec!["a".to_string(), "b".to_string()].into()],
+            cell_styles: HashMap::from([(
+                (1, 0),
+                HashMap::from([("color".to_string(), "red".to_string())]),
+            )]),
+            hash: "asdf".to_string(),
+            ..Default::default()
+        };
+        let context = renderer.context();
+        assert_eq!(context.headers, vec!["col1"]);
+        assert_eq!(context.rows.len(), 2);
+        assert_eq!(context.rows[0][0].style, "");
+        assert_eq!(context.rows[1][0].value, "b");
+        assert_eq!(context.rows[1][0].style, "color: red");
+        assert_eq!(context.rows[1][0].id, "T_asdf_row1_col0");
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string(), "y".to_string()],
+            cell_values: vec![vec!["1".to_string()].into(), vec!["a|b".to_string()].into()],
+            hash: "asdf".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            renderer.render_markdown(),
+            "| x | y |\n| --- | --- |\n| 1 | a\\|b |\n"
+        );
+    }
+
+    #[test]
+    fn test_render_latex() {
+        let renderer = Renderer {
+            column_labels: vec!["a_b".to_string()],
+            cell_values: vec![vec!["50%".to_string()].into()],
+            hash: "asdf".to_string(),
+            ..Default::default()
+        };
+        let latex = renderer.render_latex();
+        assert!(latex.starts_with("\\begin{tabular}{l}"));
+        assert!(latex.contains("a\\_b \\\\"));
+        assert!(latex.contains("50\\% \\\\"));
+        assert!(latex.trim_end().ends_with("\\end{tabular}"));
+    }
+
+    #[test]
+    fn test_hidden_rows() {
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string(), "y".to_string()],
+            cell_values: vec![
+                vec!["a".to_string(), "z".to_string()].into(),
+                vec!["1".to_string(), "9".to_string()].into(),
+            ],
+            cell_styles: HashMap::from([(
+                (1, 0),
+                HashMap::from([("color".to_string(), "red".to_string())]),
+            )]),
+            hash: "asdf".to_string(),
+            row_ids: vec![0, 12346],
+            hidden_rows: Some((1, 12345)),
+            ..Default::default()
+        };
+        let html = renderer.render();
+        assert!(html.contains("<td colspan=\"2\">… 12,345 more rows …</td>"));
+        assert!(html.contains("#T_asdf_row12346_col0 {color: red}"));
+        assert!(html.find("12,345").unwrap() < html.find(">z<").unwrap());
+        assert!(renderer
+            .render_markdown()
+            .contains("| a | 1 |\n| … | … |\n| z | 9 |"));
+    }
+
+    #[test]
+    fn test_hidden_columns() {
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string(), "z".to_string()],
+            cell_values: vec![vec!["1".to_string()].into(), vec!["9".to_string()].into()],
+            cell_styles: HashMap::from([(
+                (0, 1),
+                HashMap::from([("color".to_string(), "red".to_string())]),
+            )]),
+            hash: "asdf".to_string(),
+            col_ids: vec![0, 5],
+            hidden_columns: Some((1, 4)),
+            ..Default::default()
+        };
+        let html = renderer.render();
+        assert!(html.contains(
+            "<th scope=\"col\">x</th><th class=\"ellipsis\">…</th><th scope=\"col\">z</th>"
+        ));
+        assert!(html.contains("title=\"4 more columns\">…</td>"));
+        assert!(html.contains("#T_asdf_row0_col5 {color: red}"));
+        assert!(renderer.render_markdown().contains("| 1 | … | 9 |"));
+    }
+
+    #[test]
+    fn test_pagination_script() {
+        let mut renderer = Renderer {
+            column_labels: vec!["x".to_string()],
+            cell_values: vec![vec!["1".to_string()].into()],
+            hash: "asdf".to_string(),
+            ..Default::default()
+        };
+        assert!(!renderer.render().contains("<script>"));
+
+        renderer.interactivity.page_size = Some(10);
+        let html = renderer.render();
+        assert!(html.contains("<table id=\"T_asdf\""));
+        assert!(html.contains("(document.getElementById(\"T_asdf\"), {\"pageSize\":10});"));
+        assert!(html.contains(".ps-page-hidden"));
+    }
+
+    #[test]
+    fn test_sortable() {
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string()],
+            cell_values: vec![vec!["1.00".to_string(), "\"a\"".to_string()].into()],
+            hash: "asdf".to_string(),
+            interactivity: Interactivity {
+                sortable: true,
+                ..Default::default()
+            },
+            sort_values: vec![vec!["1".to_string(), "\"a\"".to_string()]],
+            sort_types: vec!["number".to_string()],
+            ..Default::default()
+        };
+        let html = renderer.render();
+        assert!(html.contains("<th scope=\"col\" data-sort-type=\"number\">x</th>"));
+        assert!(html.contains("data-sort-value=\"1\">1.00</td>"));
+        assert!(html.contains("data-sort-value=\"&quot;a&quot;\""));
+        assert!(html.contains("aria-sort"));
+    }
+
+    #[test]
+    fn test_filter_row() {
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string(), "y".to_string()],
+            cell_values: vec![vec!["1".to_string()].into(), vec!["2".to_string()].into()],
+            hash: "asdf".to_string(),
+            interactivity: Interactivity {
+                filterable: true,
+                ..Default::default()
+            },
+            sort_values: vec![vec!["1".to_string()], vec!["2".to_string()]],
+            hidden_columns: Some((1, 3)),
+            ..Default::default()
+        };
+        let html = renderer.render();
+        assert!(html.contains("<tr class=\"ps-filters\">"));
+        assert!(html.contains("data-col=\"0\" placeholder=\"Filter\" aria-label=\"Filter x\""));
+        assert!(html.contains("<th></th><th><input type=\"search\" data-col=\"2\""));
+        assert!(html.contains("data-sort-value=\"2\">2</td>"));
+    }
+
+    #[test]
+    fn test_search_box() {
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string()],
+            cell_values: vec![vec!["1".to_string()].into()],
+            hash: "asdf".to_string(),
+            interactivity: Interactivity {
+                search: Some(SearchMode::Filter),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let html = renderer.render();
+        assert!(html.find("id=\"T_asdf_search\"").unwrap() < html.find("<table").unwrap());
+        assert!(html.contains("{\"hideRows\":true}"));
+        assert!(html.contains(".ps-search-match"));
+    }
+
+    #[test]
+    fn test_csv_button() {
+        let renderer = Renderer {
+            column_labels: vec!["x".to_string()],
+            cell_values: vec![vec!["1".to_string()].into()],
+            hash: "asdf".to_string(),
+            interactivity: Interactivity {
+                csv_download: Some("data.csv".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let html = renderer.render();
+        assert!(html.contains("<button type=\"button\" id=\"T_asdf_csv\""));
+        assert!(html.contains("{\"filename\":\"data.csv\"}"));
+
+        let renderer = Renderer {
+            interactivity: Interactivity {
+                csv_download: Some("</script><b>.csv".to_string()),
+                ..Default::default()
+            },
+            ..renderer
+        };
+        let html = renderer.render();
+        assert!(!html.contains("</script><b>"));
+        assert!(html.contains("{\"filename\":\"\\u003c/script>\\u003cb>.csv\"}"));
+    }
+
+    #[test]
+    fn test_thousands() {
+        assert_eq!(thousands(0), "0");
+        assert_eq!(thousands(999), "999");
+        assert_eq!(thousands(1000), "1,000");
+        assert_eq!(thousands(1234567), "1,234,567");
+    }
+
     #[test]
     fn test_css_styles_generation() {
         let styles = HashMap::from([
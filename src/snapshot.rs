@@ -0,0 +1,67 @@
+use std::path::Path;
+
+/// Uid of the tables rendered by `Styler::render_deterministic` that don't set their own.
+pub(crate) const SNAPSHOT_UID: &str = "snapshot";
+
+/// Trims the indentation and trailing whitespace of every line and drops blank lines,
+/// so that snapshots don't break on formatting-only changes.
+pub fn normalize_html(html: &str) -> String {
+    html.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Compares HTML, e.g. of `Styler::render_deterministic`, with the snapshot stored at
+/// `path`, both normalized by `normalize_html`, and panics at the first differing line.
+/// A missing snapshot is written instead, as is every snapshot when the `UPDATE_SNAPSHOTS`
+/// environment variable is set.
+pub fn assert_snapshot(path: impl AsRef<Path>, html: &str) {
+    let path = path.as_ref();
+    let actual = normalize_html(html);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        std::fs::write(path, &actual)
+            .unwrap_or_else(|e| panic!("Cannot write snapshot {}: {}", path.display(), e));
+        return;
+    }
+    let expected = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Cannot read snapshot {}: {}", path.display(), e));
+    let expected = normalize_html(&expected);
+    let (mut expected_lines, mut actual_lines) = (expected.lines(), actual.lines());
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return,
+            (e, a) if e == a => continue,
+            (e, a) => panic!(
+                "Snapshot {} differs at line {}:\n  expected: {}\n    actual: {}\n\
+                 Run with UPDATE_SNAPSHOTS=1 to accept the new output.",
+                path.display(),
+                line,
+                e.unwrap_or("<end of snapshot>"),
+                a.unwrap_or("<end of output>")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        assert_eq!(
+            normalize_html("<div>\n  <p>x</p>  \n\n</div>"),
+            "<div>\n<p>x</p>\n</div>\n"
+        );
+        let path = std::env::temp_dir().join("polars_styler_test_snapshot.html");
+        std::fs::write(&path, "<div>\n  <p>x</p>\n</div>").unwrap();
+        assert_snapshot(&path, "<div>\n<p>x</p>\n</div>\n");
+        let err = std::panic::catch_unwind(|| assert_snapshot(&path, "<div>\n<p>y</p>\n</div>"))
+            .unwrap_err();
+        let message = err.downcast_ref::<String>().unwrap();
+        assert!(message.contains("differs at line 2:\n  expected: <p>x</p>\n    actual: <p>y</p>"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,388 @@
+use crate::colors::{Color, ColorMap};
+use crate::format::{CellFormat, DtypeKind};
+use crate::norm::Norm;
+use crate::renderer::{Direction, Interactivity, PrintOptions, TableStyle};
+use crate::styler::{Axis, Bins, Border, ColumnMeta, Condition, IconSet, SummaryRow, TextContrast};
+use crate::text::TextProps;
+use polars_lazy::prelude::Expr;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Styling configuration of a `Styler` without the data.
+///
+/// The spec can be serialized, stored and later re-applied to another DataFrame
+/// with the same schema. Styles added with closures or expressions are not part of it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct StyleSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table_classes: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_precision: BTreeMap<String, u32>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_formats: BTreeMap<String, CellFormat>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dtype_formats: BTreeMap<DtypeKind, CellFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_format: Option<CellFormat>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub percent_of_total: BTreeMap<String, bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_rows: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_columns: Option<usize>,
+    #[serde(default, skip_serializing_if = "Interactivity::is_empty")]
+    pub interactivity: Interactivity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub print: Option<PrintOptions>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub legend: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rules_legend: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_header: Option<String>,
+    #[serde(default, skip_serializing_if = "is_ltr")]
+    pub direction: Direction,
+    #[serde(default, skip_serializing_if = "is_switch_text")]
+    pub text_contrast: TextContrast,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gradient_opacity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_numbers: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub summary_rows: Vec<SummaryRow>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sticky_footer: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_columns: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub table_styles: Vec<TableStyle>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_separators: BTreeMap<String, Border>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_meta: BTreeMap<String, ColumnMeta>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub clipped_columns: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub expandable_columns: BTreeMap<String, usize>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operations: Vec<StyleOp>,
+}
+
+/// Single recorded styling step, replayed in order when the spec is applied.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StyleOp {
+    BackgroundGradient {
+        column: String,
+        color: Color,
+        #[serde(default)]
+        vmin: Option<f64>,
+        #[serde(default)]
+        vmax: Option<f64>,
+    },
+    Bar {
+        column: String,
+        color: Color,
+        #[serde(default)]
+        vmin: Option<f64>,
+        #[serde(default)]
+        vmax: Option<f64>,
+    },
+    BarColormap {
+        column: String,
+        cmap: ColorMap,
+        #[serde(default)]
+        vmin: Option<f64>,
+        #[serde(default)]
+        vmax: Option<f64>,
+    },
+    Highlight {
+        column: String,
+        condition: Condition,
+        color: Color,
+    },
+    BackgroundGradientNorm {
+        column: String,
+        color: Color,
+        norm: Norm,
+    },
+    BackgroundColormap {
+        column: String,
+        cmap: ColorMap,
+        norm: Norm,
+    },
+    BarNorm {
+        column: String,
+        color: Color,
+        norm: Norm,
+    },
+    TextGradient {
+        column: String,
+        color: Color,
+        norm: Norm,
+    },
+    BackgroundGradientAxis {
+        columns: Vec<String>,
+        color: Color,
+        #[serde(default)]
+        axis: Axis,
+    },
+    BackgroundGradientShared {
+        columns: Vec<String>,
+        cmap: ColorMap,
+        #[serde(default)]
+        vmin: Option<f64>,
+        #[serde(default)]
+        vmax: Option<f64>,
+    },
+    BackgroundThreePoint {
+        column: String,
+        low: Color,
+        mid: Color,
+        high: Color,
+        #[serde(default)]
+        midpoint: Option<f64>,
+    },
+    BackgroundByCategoryAuto {
+        column: String,
+        palette: Vec<Color>,
+    },
+    BackgroundBins {
+        column: String,
+        bins: Bins,
+        palette: Vec<Color>,
+    },
+    IconSet {
+        column: String,
+        icons: IconSet,
+        bins: Bins,
+    },
+    MapIcons {
+        column: String,
+        icons: BTreeMap<String, String>,
+    },
+    Text {
+        columns: Vec<String>,
+        props: TextProps,
+    },
+    HighlightRows {
+        rows: Vec<usize>,
+        color: Color,
+    },
+    HighlightRowsWhereKey {
+        column: String,
+        values: Vec<String>,
+        color: Color,
+    },
+    TrendArrows {
+        column: String,
+        compare: String,
+    },
+    HeatmapSymmetric {
+        cmap: ColorMap,
+        vmin: f64,
+        vmax: f64,
+        #[serde(default)]
+        mask_diagonal: bool,
+    },
+    /// Column added by `add_computed_column`; it's computed again from the frame the
+    /// spec is applied to.
+    ComputedColumn {
+        column: String,
+        expr: Expr,
+        #[serde(default)]
+        position: Option<usize>,
+    },
+    /// Rank column added by `add_rank_column`.
+    RankColumn {
+        column: String,
+        descending: bool,
+        medals: bool,
+    },
+}
+
+impl StyleOp {
+    /// One-line summary such as `background_gradient on a`.
+    pub fn summary(&self) -> String {
+        let value = serde_json::to_value(self).expect("operations are always serializable");
+        let columns = match (&value["column"], &value["columns"]) {
+            (serde_json::Value::String(column), _) => column.clone(),
+            (_, serde_json::Value::Array(columns)) => columns
+                .iter()
+                .filter_map(|c| c.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => String::new(),
+        };
+        let name = value["type"].as_str().unwrap_or_default();
+        if columns.is_empty() {
+            return name.to_string();
+        }
+        format!("{} on {}", name, columns)
+    }
+}
+
+fn is_ltr(direction: &Direction) -> bool {
+    direction == &Direction::Ltr
+}
+
+fn is_switch_text(contrast: &TextContrast) -> bool {
+    contrast == &TextContrast::SwitchText
+}
+
+impl StyleSpec {
+    /// Parses a spec from a JSON or YAML document.
+    pub fn parse(document: &str) -> Result<Self, SpecError> {
+        if document.trim_start().starts_with('{') {
+            return serde_json::from_str(document).map_err(SpecError::Json);
+        }
+        serde_yaml::from_str(document).map_err(SpecError::Yaml)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("spec is always serializable")
+    }
+
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(self).expect("spec is always serializable")
+    }
+
+    /// Combines two specs, e.g. a shared theme with per-report styling, the same way
+    /// `Styler::merge` combines stylers: settings of `other` win where it sets them,
+    /// lists and maps are joined and its operations are replayed after these.
+    pub fn compose(mut self, other: &StyleSpec) -> StyleSpec {
+        self.precision = other.precision.or(self.precision);
+        if let Some(classes) = &other.table_classes {
+            let own = self.table_classes.get_or_insert_with(Vec::new);
+            own.extend(classes.clone());
+        }
+        self.column_precision.extend(other.column_precision.clone());
+        self.column_formats.extend(other.column_formats.clone());
+        self.dtype_formats.extend(other.dtype_formats.clone());
+        self.default_format = other.default_format.clone().or(self.default_format);
+        self.percent_of_total.extend(other.percent_of_total.clone());
+        self.max_rows = other.max_rows.or(self.max_rows);
+        self.max_columns = other.max_columns.or(self.max_columns);
+        if !other.interactivity.is_empty() {
+            self.interactivity = other.interactivity.clone();
+        }
+        self.print = other.print.clone().or(self.print);
+        self.legend |= other.legend;
+        self.rules_legend |= other.rules_legend;
+        self.caption = other.caption.clone().or(self.caption);
+        self.row_header = other.row_header.clone().or(self.row_header);
+        if !is_ltr(&other.direction) {
+            self.direction = other.direction;
+        }
+        if !is_switch_text(&other.text_contrast) {
+            self.text_contrast = other.text_contrast;
+        }
+        self.gradient_opacity = other.gradient_opacity.or(self.gradient_opacity);
+        self.row_numbers = other.row_numbers.or(self.row_numbers);
+        self.summary_rows.extend(other.summary_rows.clone());
+        self.sticky_footer |= other.sticky_footer;
+        self.hidden_columns.extend(other.hidden_columns.clone());
+        self.table_styles.extend(other.table_styles.clone());
+        self.column_separators
+            .extend(other.column_separators.clone());
+        self.column_meta.extend(other.column_meta.clone());
+        self.clipped_columns.extend(other.clipped_columns.clone());
+        self.expandable_columns
+            .extend(other.expandable_columns.clone());
+        self.labels.extend(other.labels.clone());
+        self.operations.extend(other.operations.clone());
+        self
+    }
+}
+
+#[derive(Debug)]
+pub enum SpecError {
+    Json(serde_json::Error),
+    Yaml(serde_yaml::Error),
+}
+
+impl Display for SpecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecError::Json(e) => write!(f, "invalid JSON style spec: {}", e),
+            SpecError::Yaml(e) => write!(f, "invalid YAML style spec: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_yaml() {
+        let document = r##"
+precision: 2
+labels:
+  price: Price
+column_precision:
+  price: 1
+operations:
+  - type: background_gradient
+    column: price
+    color: notacolor
+  - type: highlight
+    column: qty
+    condition: {gt: 10}
+    color: "#ffff00"
+"##;
+        let result = StyleSpec::parse(document);
+        assert!(result.is_err(), "unknown color name");
+
+        let spec = StyleSpec::parse(&document.replace("notacolor", "blue")).unwrap();
+        assert_eq!(spec.precision, Some(2));
+        assert_eq!(spec.column_precision["price"], 1);
+        assert_eq!(spec.operations.len(), 2);
+        assert_eq!(
+            spec.operations[1],
+            StyleOp::Highlight {
+                column: "qty".to_string(),
+                condition: Condition::Gt(10.0),
+                color: Color::new(255, 255, 0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let spec = StyleSpec {
+            precision: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(StyleSpec::parse(&spec.to_json()).unwrap(), spec);
+        assert_eq!(StyleSpec::parse(&spec.to_yaml()).unwrap(), spec);
+
+        let spec = StyleSpec::parse("{\"print\": {\"grayscale_safe\": false}}").unwrap();
+        let print = spec.print.unwrap();
+        assert!(print.repeat_headers);
+        assert!(!print.grayscale_safe);
+    }
+
+    #[test]
+    fn test_compose() {
+        let theme =
+            StyleSpec::parse("precision: 2\ntable_classes: [theme]\ncaption: Theme").unwrap();
+        let report = StyleSpec::parse("precision: 1\ntable_classes: [wide]").unwrap();
+        let spec = theme.compose(&report);
+        assert_eq!(spec.precision, Some(1));
+        assert_eq!(spec.caption.as_deref(), Some("Theme"));
+        assert_eq!(
+            spec.table_classes,
+            Some(vec!["theme".to_string(), "wide".to_string()])
+        );
+    }
+}
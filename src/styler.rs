@@ -1,11 +1,25 @@
-use crate::renderer::Renderer;
+use crate::renderer::{
+    ColumnValues, Direction, Interactivity, Legend, PrintOptions, Renderer, RuleLegend, RuleMarker,
+    SearchMode, TableContext, TableStyle, HIGHLIGHT_PROPERTY,
+};
 
-use crate::colors::Color;
+use crate::colors::{contrast_ratio, Color, ColorMap, WCAG_AA};
+use crate::config::StylerConfig;
+use crate::format::{recommended_dtype_formats, CellFormat, DtypeKind};
+use crate::norm::{check_numeric, to_float, Norm};
+use crate::snapshot::{normalize_html, SNAPSHOT_UID};
+use crate::spec::{SpecError, StyleOp, StyleSpec};
+use crate::text::TextProps;
+use itertools::Itertools;
 use polars::prelude::*;
 use polars_lazy::prelude::*;
-use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::{DefaultHasher, RandomState};
 use std::collections::HashMap;
-
+use std::fmt::{Display, Formatter};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 pub trait StylerExt {
     fn style(&self) -> Styler;
@@ -17,52 +31,1043 @@ impl StylerExt for DataFrame {
     }
 }
 
-#[derive(Clone)]
+/// Styles of a DataFrame, added by chained calls and rendered to HTML.
+///
+/// The styler is `Send + Sync`, so a configured one can be kept in shared state of a
+/// server and cloned for each request; clones share the data.
+#[derive(Clone, Default)]
 pub struct Styler {
-    df: DataFrame,
+    df: Arc<DataFrame>,
     params: StylerParams,
     applied_styles: Vec<Vec<HashMap<String, String>>>, // (col, row) => (attribute => value)
     labels: HashMap<String, String>,
+    operations: Vec<StyleOp>,
+    generation: u64, // changes with `operations`, see `push_operation`
+    tooltips: HashMap<(usize, usize), String>, // (col, row) => title of the cell
+    prefixes: HashMap<(usize, usize), String>, // (col, row) => HTML put before the value
+    suffixes: HashMap<(usize, usize), String>, // (col, row) => HTML appended to the value
+    details: Option<Details>,
+    selections: SelectionCache,
+}
+
+/// The last row selection restyled by `set_normalize_on_selection`, so that rendering the
+/// same selection again, e.g. on every request of a server, doesn't recompute the styles.
+/// Shared by the clones of a styler.
+type SelectionCache = Arc<Mutex<Option<(SelectionKey, Styler)>>>;
+
+/// Generation of the operations, settings of the spec, columns and rows of a selection.
+type SelectionKey = (u64, String, Vec<String>, Vec<usize>);
+
+/// Detail rows rendered as nested tables under the rows with the same key.
+#[derive(Clone)]
+struct Details {
+    key: String,
+    styler: Box<Styler>,
+    collapsible: bool,
 }
 
 #[derive(Default, Clone)]
 pub struct StylerParams {
     precision: Option<u32>,
+    column_precision: HashMap<String, u32>,
     table_classes: Option<Vec<String>>,
+    max_rows: Option<usize>,
+    max_columns: Option<usize>,
+    normalize_on_selection: bool,
+    interactivity: Interactivity,
+    uid: Option<String>,
+    print: Option<PrintOptions>,
+    direction: Direction,
+    caption: Option<String>,
+    row_header: Option<String>,
+    legend: bool,
+    rules_legend: bool,
+    text_contrast: TextContrast,
+    gradient_opacity: Option<f64>,
+    formatters: HashMap<String, ValueFormatter>,
+    column_formats: HashMap<String, CellFormat>,
+    dtype_formats: HashMap<DtypeKind, CellFormat>,
+    default_format: Option<CellFormat>, // of columns not formatted otherwise, see `StylerConfig`
+    hidden_columns: Vec<String>,
+    table_styles: Vec<TableStyle>,
+    column_separators: HashMap<String, Border>,
+    column_meta: HashMap<String, ColumnMeta>,
+    clipped_columns: HashMap<String, String>, // column => max width of its cells
+    expandable_columns: HashMap<String, usize>, // column => characters shown before "more"
+    row_numbers: Option<usize>,
+    summary_rows: Vec<SummaryRow>,
+    sticky_footer: bool,
+    percent_of_total: HashMap<String, bool>, // column => whether to keep the value
+}
+
+type FormatFn = dyn Fn(&AnyValue) -> String + Send + Sync;
+
+/// Closure turning a cell value into its displayed text; see `Styler::set_column_formatter`.
+#[derive(Clone)]
+pub struct ValueFormatter(Arc<FormatFn>);
+
+/// Predicate on a single cell value used by conditional styles.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    Gt(f64),
+    Ge(f64),
+    Lt(f64),
+    Le(f64),
+    Between(f64, f64),
+    Equals(String),
+    IsNull,
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Gt(t) => write!(f, "> {}", t),
+            Condition::Ge(t) => write!(f, "≥ {}", t),
+            Condition::Lt(t) => write!(f, "< {}", t),
+            Condition::Le(t) => write!(f, "≤ {}", t),
+            Condition::Between(lo, hi) => write!(f, "between {} and {}", lo, hi),
+            Condition::Equals(v) => write!(f, "is {}", v),
+            Condition::IsNull => write!(f, "is null"),
+        }
+    }
+}
+
+impl Condition {
+    pub fn matches(&self, v: &AnyValue) -> bool {
+        match self {
+            Condition::Equals(expected) => match v {
+                AnyValue::Utf8(s) => s == expected,
+                AnyValue::Null => false,
+                _ => &v.to_string() == expected,
+            },
+            Condition::IsNull => matches!(v, AnyValue::Null),
+            _ => {
+                let Some(x) = v.extract::<f64>() else {
+                    return false;
+                };
+                match self {
+                    Condition::Gt(t) => x > *t,
+                    Condition::Ge(t) => x >= *t,
+                    Condition::Lt(t) => x < *t,
+                    Condition::Le(t) => x <= *t,
+                    Condition::Between(lo, hi) => *lo <= x && x <= *hi,
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+}
+
+/// Styling step or rendering that can't be done with the DataFrame; returned by the
+/// `try_*` methods, while their counterparts without the prefix panic with the message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StylerError {
+    /// Column missing in the DataFrame, with the columns it has.
+    UnknownColumn {
+        column: String,
+        available: Vec<String>,
+    },
+    NotNumeric {
+        column: String,
+        dtype: String,
+    },
+    /// Setting that may be set only once, e.g. `precision`.
+    AlreadySet(&'static str),
+    /// Values of a series don't match the rows of the DataFrame.
+    LengthMismatch {
+        column: String,
+        expected: usize,
+        got: usize,
+    },
+    /// Column added to the table under a name the DataFrame already has.
+    DuplicateColumn(String),
+    /// Row index past the end of the DataFrame.
+    RowOutOfBounds {
+        row: usize,
+        height: usize,
+    },
+    /// The DataFrame has no columns to render.
+    NoColumns,
+    /// Argument a styling step can't use, e.g. a palette without a color for every bin.
+    InvalidArgument(String),
+    /// Error from Polars, e.g. when evaluating an expression.
+    Polars(String),
+}
+
+impl Display for StylerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StylerError::UnknownColumn { column, available } => {
+                write!(f, "Unknown column `{}`", column)?;
+                if let Some(closest) = closest_match(column, available) {
+                    write!(f, ", did you mean `{}`?", closest)?;
+                }
+                write!(f, " Available columns: {}", available.join(", "))
+            }
+            StylerError::NotNumeric { column, dtype } => {
+                write!(f, "Column {} of type {} is not numeric", column, dtype)
+            }
+            StylerError::AlreadySet(setting) => write!(f, "{} can only be set once", setting),
+            StylerError::LengthMismatch {
+                column,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Series {} has {} values but the DataFrame has {} rows",
+                column, got, expected
+            ),
+            StylerError::DuplicateColumn(column) => {
+                write!(f, "Column `{}` already exists", column)
+            }
+            StylerError::NoColumns => {
+                write!(
+                    f,
+                    "No data to render; there are no columns in the DataFrame."
+                )
+            }
+            StylerError::RowOutOfBounds { row, height } => {
+                write!(
+                    f,
+                    "Row {} is out of bounds of a DataFrame with {} rows",
+                    row, height
+                )
+            }
+            StylerError::InvalidArgument(message) => write!(f, "{}", message),
+            StylerError::Polars(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<PolarsError> for StylerError {
+    fn from(e: PolarsError) -> Self {
+        StylerError::Polars(e.to_string())
+    }
+}
+
+impl std::error::Error for StylerError {}
+
+/// Values that a gradient over several columns is normalized against.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Axis {
+    /// Each column separately.
+    #[default]
+    Column,
+    /// Each row separately, comparing the columns within a row.
+    Row,
+    /// All the cells together.
+    Table,
+}
+
+/// How styled backgrounds keep the cell text readable (WCAG AA, assuming dark text).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextContrast {
+    /// Switches the text to white on dark backgrounds.
+    #[default]
+    SwitchText,
+    /// Keeps the text and lightens backgrounds that are too dark for it.
+    AdjustBackground,
+    /// Leaves text and backgrounds as they are.
+    Off,
+    /// Switches the text to white on backgrounds with a relative luminance below the
+    /// threshold, like `text_color_threshold` of pandas (0.408 there).
+    Threshold(f64),
+}
+
+/// Colors of the cells marked by `Styler::diff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffStyle {
+    /// Cells that are new: rows past the end of the old frame and nulls that got a value.
+    pub added: Color,
+    /// Cells whose value became null.
+    pub removed: Color,
+    /// Cells with a different value.
+    pub modified: Color,
+    /// Shows `old → new` when hovering a changed cell of a row that was in the old frame.
+    pub tooltips: bool,
+}
+
+impl Default for DiffStyle {
+    fn default() -> Self {
+        DiffStyle {
+            added: Color::new(198, 239, 206),
+            removed: Color::new(255, 199, 206),
+            modified: Color::new(255, 235, 156),
+            tooltips: true,
+        }
+    }
+}
+
+/// How `background_bins` splits values into bins.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Bins {
+    /// Equal-width bins between the column minimum and maximum.
+    Count(usize),
+    /// Thresholds between bins; `n` edges make `n + 1` bins and a value equal to an edge
+    /// falls into the upper one.
+    Edges(Vec<f64>),
+}
+
+/// Text emphasis applied by `Styler::emphasize_when`; see `TextProps` for the others.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextStyle {
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+}
+
+/// Aggregate of the values of a column, see `Styler::add_summary_row`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// Number of values that are not null.
+    Count,
+}
+
+impl Aggregation {
+    fn apply(&self, s: &Series) -> Series {
+        match self {
+            Aggregation::Sum => s.sum_as_series(),
+            Aggregation::Mean => s.mean_as_series(),
+            Aggregation::Min => s.min_as_series(),
+            Aggregation::Max => s.max_as_series(),
+            Aggregation::Count => Series::new(s.name(), &[(s.len() - s.null_count()) as u32]),
+        }
+    }
+}
+
+/// Row of aggregates of all rows rendered in the table footer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SummaryRow {
+    pub label: String,
+    pub aggregation: Aggregation,
+    pub columns: Vec<String>,
+}
+
+/// Rows of a table that changed since it was rendered, see `Styler::render_changed_rows`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowUpdates {
+    /// `<tr>` elements of the new and changed rows, each replacing the element with its id.
+    pub rows: Vec<String>,
+    /// Ids of the `<tr>` elements of rows that are no longer rendered.
+    pub removed: Vec<String>,
+    /// Cell styles of the new table, see `Styler::render_css`; changed rows may refer to
+    /// rules that the page doesn't have yet.
+    pub css: String,
+}
+
+/// Unit and description of a column, see `Styler::set_column_meta`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ColumnMeta {
+    /// Unit of the values, e.g. `kg` or `USD`, shown in a header row under the labels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Shown when hovering the column header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Appends the unit to the numbers instead of showing it in the header.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub unit_in_values: bool,
+}
+
+impl ColumnMeta {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn unit(mut self, unit: &str) -> Self {
+        self.unit = Some(unit.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn unit_in_values(mut self) -> Self {
+        self.unit_in_values = true;
+        self
+    }
+
+    /// Unit shown in the header, unless it's appended to the values.
+    fn header_unit(&self) -> Option<&String> {
+        self.unit.as_ref().filter(|_| !self.unit_in_values)
+    }
+}
+
+/// Line drawn by `Styler::border_when`, e.g. `2px solid red`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Border {
+    /// Width in pixels.
+    pub width: u32,
+    /// CSS line style such as `solid`, `dashed` or `dotted`.
+    pub style: String,
+    pub color: Color,
+}
+
+impl Border {
+    /// Solid line of the given width in pixels.
+    pub fn new(width: u32, color: &Color) -> Self {
+        Border {
+            width,
+            style: "solid".to_string(),
+            color: color.clone(),
+        }
+    }
+
+    pub fn style(mut self, style: &str) -> Self {
+        self.style = style.to_string();
+        self
+    }
+
+    fn css(&self) -> String {
+        format!("{}px {} {}", self.width, self.style, self.color.to_hex())
+    }
+}
+
+/// Three glyphs marking low, middle and high values, like Excel icon sets.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IconSet {
+    /// Red ▼, amber ▶ and green ▲.
+    ThreeArrows,
+    /// Red, amber and green ●.
+    ThreeTrafficLights,
+}
+
+impl IconSet {
+    /// Glyph and its color for each band, from the lowest.
+    fn icons(&self) -> [(&'static str, &'static str); 3] {
+        let glyphs = match self {
+            IconSet::ThreeArrows => ["▼", "▶", "▲"],
+            IconSet::ThreeTrafficLights => ["●", "●", "●"],
+        };
+        [
+            (glyphs[0], TREND_DOWN),
+            (glyphs[1], "#f4a000"),
+            (glyphs[2], TREND_UP),
+        ]
+    }
+}
+
+impl Bins {
+    /// Returns an error for no bins or for edges that are not finite and increasing.
+    fn check(&self) -> Result<(), StylerError> {
+        let valid = match self {
+            Bins::Count(n) => *n > 0,
+            Bins::Edges(edges) => {
+                edges.iter().all(|e| e.is_finite()) && edges.windows(2).all(|w| w[0] < w[1])
+            }
+        };
+        if !valid {
+            return Err(StylerError::InvalidArgument(format!(
+                "Invalid bins {:?}; they need at least one bin and increasing edges",
+                self
+            )));
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Bins::Count(n) => *n,
+            Bins::Edges(edges) => edges.len() + 1,
+        }
+    }
+
+    /// Bin index of every value; `None` for nulls.
+    fn assign(&self, s: &Series) -> Vec<Option<usize>> {
+        let s = to_float(s);
+        let values = s.f64().unwrap();
+        match self {
+            Bins::Count(n) => {
+                let (min, max) = Norm::linear().range(&s).unwrap_or_default();
+                let width = (max - min) / *n as f64;
+                values
+                    .into_iter()
+                    .map(|v| {
+                        let v = v?;
+                        let bin = if width > 0.0 {
+                            ((v - min) / width) as usize
+                        } else {
+                            0
+                        };
+                        Some(bin.min(n - 1))
+                    })
+                    .collect()
+            }
+            Bins::Edges(edges) => values
+                .into_iter()
+                .map(|v| v.map(|v| edges.partition_point(|e| *e <= v)))
+                .collect(),
+        }
+    }
 }
 
 impl Styler {
+    /// Styles a shallow copy of `df`: its columns are reference counted, so the data
+    /// buffers are shared rather than copied.
     pub fn new(df: &DataFrame) -> Styler {
-        Styler {
-            df: df.clone(),
+        Styler::from_shared(Arc::new(df.clone()))
+    }
+
+    /// Styles a frame shared with the caller; neither the styler nor its clones copy it.
+    pub fn from_shared(df: Arc<DataFrame>) -> Styler {
+        let styler = Styler {
             params: StylerParams::default(),
             applied_styles: vec![vec![HashMap::new(); df.height()]; df.width()],
             labels: HashMap::new(),
+            operations: Vec::new(),
+            generation: 0,
+            tooltips: HashMap::new(),
+            prefixes: HashMap::new(),
+            suffixes: HashMap::new(),
+            details: None,
+            selections: SelectionCache::default(),
+            df,
+        };
+        match StylerConfig::global() {
+            Some(config) => styler.with_config(&config),
+            None => styler,
+        }
+    }
+
+    /// Applies application-wide defaults, see `StylerConfig`. Later settings of the
+    /// styler override them.
+    pub fn with_config(mut self, config: &StylerConfig) -> Self {
+        if let Some(theme) = &config.theme {
+            self = self.apply_settings(theme);
+        }
+        if let Some(align) = &config.text_align {
+            let style = TableStyle::new("td", &format!("text-align: {}", align));
+            self.params.table_styles.push(style);
+        }
+        if let Some(format) = config.format() {
+            self.params.default_format = Some(format);
+        }
+        self
+    }
+
+    /// Collects the query and styles the result. Styles are computed when they are applied,
+    /// so the frame is collected here; pass `columns` to read only the columns to render.
+    pub fn from_lazy(lf: LazyFrame, columns: Option<&[&str]>) -> PolarsResult<Styler> {
+        let lf = match columns {
+            Some(columns) => lf.select(columns.iter().map(|&c| col(c)).collect::<Vec<_>>()),
+            None => lf,
+        };
+        Ok(Styler::new(&lf.collect()?))
+    }
+
+    /// Reads a CSV file with a header row.
+    #[cfg(feature = "csv")]
+    pub fn from_csv(path: impl AsRef<std::path::Path>) -> PolarsResult<Styler> {
+        let df = CsvReader::from_path(path.as_ref())?
+            .has_header(true)
+            .finish()?;
+        Ok(Styler::new(&df))
+    }
+
+    /// Reads an Arrow IPC (Feather v2) file.
+    #[cfg(feature = "ipc")]
+    pub fn from_ipc(path: impl AsRef<std::path::Path>) -> PolarsResult<Styler> {
+        let file = std::fs::File::open(path)?;
+        let df = IpcReader::new(file).finish()?;
+        Ok(Styler::new(&df))
+    }
+
+    /// Reads a Parquet file.
+    #[cfg(feature = "parquet")]
+    pub fn from_parquet(path: impl AsRef<std::path::Path>) -> PolarsResult<Styler> {
+        let file = std::fs::File::open(path)?;
+        let df = ParquetReader::new(file).finish()?;
+        Ok(Styler::new(&df))
+    }
+
+    /// Builds a styler from a JSON or YAML style spec document.
+    pub fn from_spec(df: &DataFrame, spec: &str) -> Result<Styler, SpecError> {
+        let spec = StyleSpec::parse(spec)?;
+        Ok(Styler::new(df).apply_spec(&spec))
+    }
+
+    /// Returns the accumulated styling configuration, without the data.
+    pub fn spec(&self) -> StyleSpec {
+        StyleSpec {
+            operations: self.operations.clone(),
+            ..self.settings_spec()
+        }
+    }
+
+    /// The spec without its operations.
+    fn settings_spec(&self) -> StyleSpec {
+        StyleSpec {
+            precision: self.params.precision,
+            column_precision: self.params.column_precision.clone().into_iter().collect(),
+            column_formats: self.params.column_formats.clone().into_iter().collect(),
+            dtype_formats: self.params.dtype_formats.clone().into_iter().collect(),
+            default_format: self.params.default_format.clone(),
+            table_classes: self.params.table_classes.clone(),
+            max_rows: self.params.max_rows,
+            max_columns: self.params.max_columns,
+            interactivity: self.params.interactivity.clone(),
+            print: self.params.print.clone(),
+            direction: self.params.direction,
+            text_contrast: self.params.text_contrast,
+            gradient_opacity: self.params.gradient_opacity,
+            hidden_columns: self.params.hidden_columns.clone(),
+            table_styles: self.params.table_styles.clone(),
+            column_separators: self.params.column_separators.clone().into_iter().collect(),
+            column_meta: self.params.column_meta.clone().into_iter().collect(),
+            clipped_columns: self.params.clipped_columns.clone().into_iter().collect(),
+            expandable_columns: self.params.expandable_columns.clone().into_iter().collect(),
+            row_numbers: self.params.row_numbers,
+            summary_rows: self.params.summary_rows.clone(),
+            sticky_footer: self.params.sticky_footer,
+            percent_of_total: self.params.percent_of_total.clone().into_iter().collect(),
+            caption: self.params.caption.clone(),
+            row_header: self.params.row_header.clone(),
+            legend: self.params.legend,
+            rules_legend: self.params.rules_legend,
+            labels: self.labels.clone().into_iter().collect(),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Records an operation for the spec. Every change gets a new process-wide generation,
+    /// so that clones that diverged never share a key of the selection cache.
+    fn push_operation(&mut self, op: StyleOp) {
+        static GENERATION: AtomicU64 = AtomicU64::new(1);
+        self.operations.push(op);
+        self.generation = GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Re-applies a previously saved spec on top of the current styles.
+    pub fn apply_spec(self, spec: &StyleSpec) -> Self {
+        let s = self.apply_settings(spec);
+        spec.operations.iter().fold(s, |s, op| match op {
+            StyleOp::BackgroundGradient {
+                column,
+                color,
+                vmin,
+                vmax,
+            } => s.background_gradient(column, color, vmin, vmax),
+            StyleOp::Bar {
+                column,
+                color,
+                vmin,
+                vmax,
+            } => s.bar(column, color, vmin, vmax),
+            StyleOp::BarColormap {
+                column,
+                cmap,
+                vmin,
+                vmax,
+            } => s.bar_colormap(column, cmap, vmin, vmax),
+            StyleOp::Highlight {
+                column,
+                condition,
+                color,
+            } => s.highlight(column, condition, color),
+            StyleOp::BackgroundThreePoint {
+                column,
+                low,
+                mid,
+                high,
+                midpoint,
+            } => s.background_three_point(column, low, mid, high, midpoint),
+            StyleOp::BackgroundGradientNorm {
+                column,
+                color,
+                norm,
+            } => s.background_gradient_norm(column, color, norm),
+            StyleOp::BackgroundColormap { column, cmap, norm } => {
+                s.background_colormap(column, cmap, norm)
+            }
+            StyleOp::BarNorm {
+                column,
+                color,
+                norm,
+            } => s.bar_norm(column, color, norm),
+            StyleOp::TextGradient {
+                column,
+                color,
+                norm,
+            } => s.text_gradient(column, color, norm),
+            StyleOp::BackgroundGradientAxis {
+                columns,
+                color,
+                axis,
+            } => {
+                let columns = columns.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+                s.background_gradient_axis(&columns, color, *axis)
+            }
+            StyleOp::BackgroundGradientShared {
+                columns,
+                cmap,
+                vmin,
+                vmax,
+            } => {
+                let columns = columns.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+                s.background_gradient_shared(&columns, cmap, vmin, vmax)
+            }
+            StyleOp::BackgroundByCategoryAuto { column, palette } => {
+                s.background_by_category_auto(column, palette)
+            }
+            StyleOp::BackgroundBins {
+                column,
+                bins,
+                palette,
+            } => s.background_bins(column, bins, palette),
+            StyleOp::TrendArrows { column, compare } => s.trend_arrows(column, compare),
+            StyleOp::HighlightRows { rows, color } => s.highlight_rows_at(rows, color),
+            StyleOp::HighlightRowsWhereKey {
+                column,
+                values,
+                color,
+            } => {
+                let values = values.iter().map(|v| v.as_str()).collect::<Vec<_>>();
+                s.highlight_rows_where_key(column, &values, color)
+            }
+            StyleOp::Text { columns, props } => {
+                let columns = columns.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+                s.set_text(&columns, props)
+            }
+            StyleOp::MapIcons { column, icons } => {
+                s.map_icons(column, &icons.clone().into_iter().collect())
+            }
+            StyleOp::IconSet {
+                column,
+                icons,
+                bins,
+            } => s.icon_set(column, *icons, bins),
+            StyleOp::HeatmapSymmetric {
+                cmap,
+                vmin,
+                vmax,
+                mask_diagonal,
+            } => s.heatmap_symmetric(cmap, *vmin, *vmax, *mask_diagonal),
+            // Frames of row selections already have the added columns.
+            StyleOp::ComputedColumn {
+                column,
+                expr,
+                position,
+            } => match s.get_col_idx(column) {
+                Some(_) => {
+                    let mut s = s;
+                    s.push_operation(op.clone());
+                    s
+                }
+                None => s.add_computed_column(column, expr.clone(), *position),
+            },
+            StyleOp::RankColumn {
+                column,
+                descending,
+                medals,
+            } => match s.get_col_idx(&format!("{} rank", column)) {
+                Some(col) => {
+                    let mut s = s;
+                    if *medals {
+                        let ranks = s.df.get_columns()[col].cast(&DataType::UInt32).unwrap();
+                        s.add_medals(col, &ranks.u32().unwrap().into_iter().collect::<Vec<_>>());
+                    }
+                    s.push_operation(op.clone());
+                    s
+                }
+                None => s.add_rank_column(column, *descending, *medals),
+            },
+        })
+    }
+
+    /// Applies a styling function, so that shared styling steps can be packaged as
+    /// functions and chained like the built-in methods.
+    pub fn pipe(self, f: impl FnOnce(Styler) -> Styler) -> Self {
+        f(self)
+    }
+
+    /// Settings of the spec without its operations; the spec wins where it sets them.
+    fn apply_settings(mut self, spec: &StyleSpec) -> Self {
+        if spec.precision.is_some() {
+            self.params.precision = spec.precision;
+        }
+        self.params
+            .column_precision
+            .extend(spec.column_precision.clone());
+        self.params
+            .column_formats
+            .extend(spec.column_formats.clone());
+        self.params.dtype_formats.extend(spec.dtype_formats.clone());
+        if spec.default_format.is_some() {
+            self.params.default_format = spec.default_format.clone();
+        }
+        self.params
+            .percent_of_total
+            .extend(spec.percent_of_total.clone());
+        if spec.max_rows.is_some() {
+            self.params.max_rows = spec.max_rows;
+        }
+        if spec.max_columns.is_some() {
+            self.params.max_columns = spec.max_columns;
+        }
+        if !spec.interactivity.is_empty() {
+            self.params.interactivity = spec.interactivity.clone();
+        }
+        self.params.legend |= spec.legend;
+        self.params.rules_legend |= spec.rules_legend;
+        if spec.caption.is_some() {
+            self.params.caption = spec.caption.clone();
+        }
+        if spec.row_header.is_some() {
+            self.params.row_header = spec.row_header.clone();
+        }
+        if spec.direction != Direction::Ltr {
+            self.params.direction = spec.direction;
+        }
+        if spec.print.is_some() {
+            self.params.print = spec.print.clone();
+        }
+        if spec.gradient_opacity.is_some() {
+            self.params.gradient_opacity = spec.gradient_opacity;
+        }
+        self.params.summary_rows.extend(spec.summary_rows.clone());
+        self.params.sticky_footer |= spec.sticky_footer;
+        if spec.row_numbers.is_some() {
+            self.params.row_numbers = spec.row_numbers;
+        }
+        if spec.text_contrast != TextContrast::SwitchText {
+            self.params.text_contrast = spec.text_contrast;
+        }
+        self.params
+            .hidden_columns
+            .extend(spec.hidden_columns.clone());
+        self.params.table_styles.extend(spec.table_styles.clone());
+        self.params
+            .column_separators
+            .extend(spec.column_separators.clone());
+        self.params.column_meta.extend(spec.column_meta.clone());
+        self.params
+            .clipped_columns
+            .extend(spec.clipped_columns.clone());
+        self.params
+            .expandable_columns
+            .extend(spec.expandable_columns.clone());
+        if let Some(classes) = &spec.table_classes {
+            self = self.add_table_classes(classes.clone());
+        }
+        self.labels.extend(spec.labels.clone());
+        self
+    }
+
+    /// Combines two stylers of the same data, e.g. a shared "theme" styler with one
+    /// built for a single report. `other` takes precedence: for the same cell its CSS
+    /// properties override ones of `self`, and its settings (precision, caption, ...)
+    /// override ones of `self` where they are set. Lists such as table classes, hidden
+    /// columns and table styles are joined. Cell styles are matched by column name.
+    pub fn merge(self, other: &Styler) -> Self {
+        self.try_merge(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `merge`, but returns an error when the stylers differ in the number of rows.
+    pub fn try_merge(mut self, other: &Styler) -> Result<Self, StylerError> {
+        self.check_merge(other)?;
+        let spec = other.spec();
+        self = self.apply_settings(&spec);
+        self.params
+            .formatters
+            .extend(other.params.formatters.clone());
+        for (name, styles) in other
+            .df
+            .get_column_names()
+            .iter()
+            .zip(&other.applied_styles)
+        {
+            if let Some(col) = self.get_col_idx(name) {
+                self.applied_styles[col]
+                    .iter_mut()
+                    .zip(styles)
+                    .for_each(|(a, b)| a.extend(b.clone()));
+            }
+        }
+        let names = other.df.get_column_names();
+        for ((c, r), title) in &other.tooltips {
+            if let Some(col) = self.get_col_idx(names[*c]) {
+                self.tooltips.insert((col, *r), title.clone());
+            }
+        }
+        for ((c, r), prefix) in &other.prefixes {
+            if let Some(col) = self.get_col_idx(names[*c]) {
+                self.prefixes.insert((col, *r), prefix.clone());
+            }
+        }
+        for ((c, r), suffix) in &other.suffixes {
+            if let Some(col) = self.get_col_idx(names[*c]) {
+                self.suffixes.insert((col, *r), suffix.clone());
+            }
+        }
+        for op in spec.operations {
+            self.push_operation(op);
+        }
+        Ok(self)
+    }
+
+    /// Adds a column computed from the other columns, e.g. a delta or a ratio, to the
+    /// rendered table without changing the caller's DataFrame. The column is inserted at
+    /// `position` or appended when it is `None`, and can be styled like the other ones.
+    /// The expression is part of the spec, so the column is computed again when the spec
+    /// is applied to another frame.
+    pub fn add_computed_column(self, name: &str, e: Expr, position: Option<usize>) -> Self {
+        self.try_add_computed_column(name, e, position)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `add_computed_column`, but returns an error if the expression fails or the
+    /// name is already taken.
+    pub fn try_add_computed_column(
+        self,
+        name: &str,
+        e: Expr,
+        position: Option<usize>,
+    ) -> Result<Self, StylerError> {
+        let s = evaluate_expr(e.clone().alias(name), &self.df)?;
+        let mut styler = self.try_add_computed_series(&s, position)?;
+        styler.push_operation(StyleOp::ComputedColumn {
+            column: name.to_string(),
+            expr: e,
+            position,
+        });
+        Ok(styler)
+    }
+
+    /// Adds the series as a column named like it, e.g. values computed outside of Rust;
+    /// see `add_computed_column`. Unlike an expression, the series isn't part of the spec.
+    pub fn add_computed_series(self, s: &Series, position: Option<usize>) -> Self {
+        self.try_add_computed_series(s, position)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `add_computed_series`, but returns an error if the series has another length
+    /// or its name is already taken.
+    pub fn try_add_computed_series(
+        mut self,
+        s: &Series,
+        position: Option<usize>,
+    ) -> Result<Self, StylerError> {
+        self.check_computed_series(s)?;
+        let col = position.map_or(self.df.width(), |p| p.min(self.df.width()));
+        Arc::make_mut(&mut self.df).insert_at_idx(col, s.clone())?;
+        self.applied_styles
+            .insert(col, vec![HashMap::new(); self.df.height()]);
+        for cells in [&mut self.tooltips, &mut self.prefixes, &mut self.suffixes] {
+            *cells = cells
+                .drain()
+                .map(|((c, r), v)| ((if c >= col { c + 1 } else { c }, r), v))
+                .collect();
+        }
+        Ok(self)
+    }
+
+    /// Adds a column ranking the values of `by_column` 1, 2, 3, ..., largest first when
+    /// `descending`, right after it and labeled "Rank". Equal values share the better rank
+    /// and nulls are not ranked. With `medals`, ranks 1 to 3 get gold, silver and bronze
+    /// backgrounds.
+    pub fn add_rank_column(self, by_column: &str, descending: bool, medals: bool) -> Self {
+        self.try_add_rank_column(by_column, descending, medals)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `add_rank_column`, but returns an error if the column is missing or not
+    /// numeric, or if the rank column already exists.
+    pub fn try_add_rank_column(
+        mut self,
+        by_column: &str,
+        descending: bool,
+        medals: bool,
+    ) -> Result<Self, StylerError> {
+        self.check_rank_column(by_column)?;
+        let (col, s) = self.icolumn(by_column).unwrap();
+        let values = to_float(s).f64()?.into_iter().collect::<Vec<_>>();
+        let ranks = competition_ranks(&values, descending);
+        let name = format!("{} rank", by_column);
+        self = self.try_add_computed_series(&Series::new(&name, ranks.clone()), Some(col + 1))?;
+        self.labels.insert(name, "Rank".to_string());
+        if medals {
+            self.add_medals(col + 1, &ranks);
+        }
+        self.push_operation(StyleOp::RankColumn {
+            column: by_column.to_string(),
+            descending,
+            medals,
+        });
+        Ok(self)
+    }
+
+    /// Gold, silver and bronze backgrounds of ranks 1 to 3 in column `col`.
+    fn add_medals(&mut self, col: usize, ranks: &[Option<u32>]) {
+        let contrast = self.params.text_contrast;
+        for (row, rank) in ranks.iter().enumerate() {
+            if let Some(color) = rank.and_then(|r| MEDALS.get(r as usize - 1)) {
+                let color = Color::from_hex(color).unwrap();
+                self.applied_styles[col][row].extend(background_styles(
+                    color.to_hex(),
+                    &color,
+                    contrast,
+                ));
+            }
         }
     }
 
-    pub fn apply(
+    pub fn apply(self, column: &str, f: impl Fn(&Series) -> Vec<HashMap<String, String>>) -> Self {
+        self.try_apply(column, f)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `apply`, but returns an error for an unknown column instead of panicking.
+    pub fn try_apply(
         mut self,
         column: &str,
         f: impl Fn(&Series) -> Vec<HashMap<String, String>>,
-    ) -> Self {
+    ) -> Result<Self, StylerError> {
         let (col, series) = self
             .icolumn(column)
-            .unwrap_or_else(|| panic!("Unknown column {}", &column));
+            .ok_or_else(|| self.unknown_column(column))?;
         let new_styles = f(series);
         self.applied_styles[col]
             .iter_mut()
             .zip(new_styles)
             .for_each(|(a, b)| a.extend(b));
-        self
+        Ok(self)
+    }
+
+    /// Sets a CSS property on every cell of the column, e.g. `text-align` or `width`.
+    /// Styles shared by a whole column are rendered as a single rule.
+    pub fn set_column_style(self, column: &str, property: &str, value: &str) -> Self {
+        self.apply(column, |s| {
+            vec![HashMap::from([(property.to_string(), value.to_string())]); s.len()]
+        })
+    }
+
+    pub fn set_table_classes(self, classes: Vec<String>) -> Self {
+        self.try_set_table_classes(classes)
+            .unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn set_table_classes(mut self, classes: Vec<String>) -> Self {
+    /// Like `set_table_classes`, but returns an error if the classes were already set.
+    pub fn try_set_table_classes(mut self, classes: Vec<String>) -> Result<Self, StylerError> {
         if self.params.table_classes.is_some() {
-            panic!("table_classes can only be set once");
+            return Err(StylerError::AlreadySet("table_classes"));
         }
         self.params.table_classes = Some(classes);
-        self
+        Ok(self)
     }
 
     pub fn add_table_classes(mut self, classes: Vec<String>) -> Self {
@@ -97,297 +1102,4405 @@ impl Styler {
         self
     }
 
-    pub fn set_precision(mut self, precision: u32) -> Self {
+    pub fn set_precision(self, precision: u32) -> Self {
+        self.try_set_precision(precision)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `set_precision`, but returns an error if the precision was already set.
+    pub fn try_set_precision(mut self, precision: u32) -> Result<Self, StylerError> {
         if self.params.precision.is_some() {
-            panic!("precision can only be set once");
+            return Err(StylerError::AlreadySet("precision"));
         }
         self.params.precision = Some(precision);
-        self
+        Ok(self)
     }
 
-    pub fn background_gradient(
-        self,
+    /// Displays the values of the column as returned by `f` (nulls included), instead of
+    /// the default formatting with the precision. Not saved in the spec.
+    pub fn set_column_formatter(
+        mut self,
         column: &str,
-        color: &Color,
-        vmin: &Option<f64>,
-        vmax: &Option<f64>,
+        f: impl Fn(&AnyValue) -> String + Send + Sync + 'static,
     ) -> Self {
-        self.apply(column, |s| {
-            normalize_series(s, vmin, vmax)
-                .iter()
-                .map(|v| {
-                    let AnyValue::Float64(v) = v else {
-                        panic!("values should have been casted to float64")
-                    };
-                    HashMap::from([("background-color".to_string(), color.to_rgba(v))])
-                })
-                .collect()
-        })
+        self.params
+            .formatters
+            .insert(column.to_string(), ValueFormatter(Arc::new(f)));
+        self
     }
 
-    pub fn bar(self, column: &str, color: &Color, vmin: &Option<f64>, vmax: &Option<f64>) -> Self {
-        self.apply(column, |s| {
-            normalize_series(s, vmin, vmax)
-                .iter()
-                .map(|v| {
-                    let AnyValue::Float64(v) = v else {
-                        panic!("values should have been casted to float64")
-                    };
-                    let bg = format!(
-                        "linear-gradient(90deg, {} {}%, transparent {}%, transparent 100%)",
-                        color.to_hex(),
-                        (v * 100.0) as u32,
-                        (v * 100.0) as u32
-                    );
-                    HashMap::from([("background".to_string(), bg)])
-                })
-                .collect()
-        })
+    /// Displays the values of the column with separators, currency or percent signs,
+    /// missing value text or a date pattern. The precision of the format takes precedence
+    /// over `set_column_precision`; a column formatter takes precedence over the format.
+    pub fn set_column_format(mut self, column: &str, format: CellFormat) -> Self {
+        self.params
+            .column_formats
+            .insert(column.to_string(), format);
+        self
     }
 
-    pub fn background_gradient_expr(self, e: Expr, color: &Color) -> Self {
-        let s = evaluate_expr(e, &self.df);
-        self.background_gradient_series(&s, color)
+    /// Sets the number of decimals of a single column, taking precedence over `set_precision`.
+    pub fn set_column_precision(mut self, column: &str, precision: u32) -> Self {
+        self.params
+            .column_precision
+            .insert(column.to_string(), precision);
+        self
     }
 
-    fn background_gradient_series(mut self, s: &Series, color: &Color) -> Self {
-        let c = self.get_col_idx(s.name()).unwrap();
-        normalize_series(s, &None, &None)
-            .iter()
-            .map(|v| {
-                let AnyValue::Float64(v) = v else {
-                    panic!("values should have been casted to float64")
-                };
-                color.to_rgba(v)
-            })
-            .enumerate()
-            .for_each(|(i, v)| {
-                self.applied_styles[c][i].insert("background-color".to_string(), v);
-            });
+    /// Sets the format of the columns of a kind of dtype that have no format of their own,
+    /// e.g. `CellFormat::new().booleans("yes", "no")` for booleans. The precision of the
+    /// format is used only when the styler doesn't set one.
+    pub fn set_dtype_format(mut self, kind: DtypeKind, format: CellFormat) -> Self {
+        self.params.dtype_formats.insert(kind, format);
         self
     }
 
-    pub fn render(self) -> String {
-        let data = self
-            .df
-            .iter()
-            .map(|row| format_row(row, &self.params))
+    /// Formats columns by their dtype with commonly used defaults: floats with 2 decimals,
+    /// ISO 8601 dates and booleans as ✓ and ✗. Use `set_dtype_format` to override them.
+    pub fn with_recommended_dtype_formats(mut self) -> Self {
+        self.params
+            .dtype_formats
+            .extend(recommended_dtype_formats());
+        self
+    }
+
+    /// Displays the values of the column as their share of the column sum, e.g. `12.3%`,
+    /// or after the value, e.g. `1,234 (12.3%)`, with `with_value`. The sum is of all
+    /// rows, including the ones left out by `set_max_rows`.
+    pub fn format_percent_of_total(mut self, column: &str, with_value: bool) -> Self {
+        self.params
+            .percent_of_total
+            .insert(column.to_string(), with_value);
+        self
+    }
+
+    /// Renders only the first and last `max_rows / 2` rows with a row in between
+    /// saying how many rows were left out. Styles are still computed on all rows.
+    pub fn set_max_rows(mut self, max_rows: usize) -> Self {
+        self.params.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Renders only the first and last `max_columns / 2` columns with an ellipsis column in between.
+    pub fn set_max_columns(mut self, max_columns: usize) -> Self {
+        self.params.max_columns = Some(max_columns);
+        self
+    }
+
+    /// Sets background color of cells matching the condition.
+    pub fn highlight(self, column: &str, condition: &Condition, color: &Color) -> Self {
+        self.try_highlight(column, condition, color)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `highlight`, but returns an error for an unknown column.
+    pub fn try_highlight(
+        mut self,
+        column: &str,
+        condition: &Condition,
+        color: &Color,
+    ) -> Result<Self, StylerError> {
+        let contrast = self.params.text_contrast;
+        if self.get_col_idx(column).is_none() {
+            return Err(self.unknown_column(column));
+        }
+        self.push_operation(StyleOp::Highlight {
+            column: column.to_string(),
+            condition: condition.clone(),
+            color: color.clone(),
+        });
+        self.try_apply(column, |s| {
+            s.iter()
+                .map(|v| {
+                    if condition.matches(&v) {
+                        let mut styles = background_styles(color.to_hex(), color, contrast);
+                        styles.insert(HIGHLIGHT_PROPERTY.to_string(), "1".to_string());
+                        styles
+                    } else {
+                        HashMap::new()
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// Sets background color from a fixed palette by the bin of the value, like a stepped
+    /// conditional format. The palette needs exactly one color per bin.
+    pub fn background_bins(self, column: &str, bins: &Bins, palette: &[Color]) -> Self {
+        self.try_background_bins(column, bins, palette)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_bins`, but returns an error for an unknown or non-numeric column,
+    /// invalid bins or a palette without one color per bin; nothing is styled then.
+    pub fn try_background_bins(
+        mut self,
+        column: &str,
+        bins: &Bins,
+        palette: &[Color],
+    ) -> Result<Self, StylerError> {
+        let contrast = self.params.text_contrast;
+        self.check_columns(&[column], true)?;
+        bins.check()?;
+        if bins.len() != palette.len() {
+            return Err(StylerError::InvalidArgument(format!(
+                "The palette has {} colors for {} bins; it needs one color per bin",
+                palette.len(),
+                bins.len()
+            )));
+        }
+        self.push_operation(StyleOp::BackgroundBins {
+            column: column.to_string(),
+            bins: bins.clone(),
+            palette: palette.to_vec(),
+        });
+        self.try_apply(column, |s| {
+            bins.assign(s)
+                .into_iter()
+                .map(|bin| match bin {
+                    Some(bin) => background_styles(palette[bin].to_hex(), &palette[bin], contrast),
+                    None => HashMap::new(),
+                })
+                .collect()
+        })
+    }
+
+    /// Sets background color by the category (distinct value) of the cell. Each value gets
+    /// the palette color at its FNV hash, so its color depends only on the value and the
+    /// palette: it's the same across renders and frames. Values may share a color.
+    pub fn background_by_category_auto(self, column: &str, palette: &[Color]) -> Self {
+        self.try_background_by_category_auto(column, palette)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_by_category_auto`, but returns an error for an unknown column or
+    /// an empty palette.
+    pub fn try_background_by_category_auto(
+        mut self,
+        column: &str,
+        palette: &[Color],
+    ) -> Result<Self, StylerError> {
+        self.check_columns(&[column], false)?;
+        if palette.is_empty() {
+            return Err(StylerError::InvalidArgument(
+                "The palette is empty".to_string(),
+            ));
+        }
+        let contrast = self.params.text_contrast;
+        self.push_operation(StyleOp::BackgroundByCategoryAuto {
+            column: column.to_string(),
+            palette: palette.to_vec(),
+        });
+        self.try_apply(column, |s| {
+            s.iter()
+                .map(|v| match category_key(v) {
+                    Some(key) => {
+                        let color = &palette[(fnv1a(&key) % palette.len() as u64) as usize];
+                        background_styles(color.to_hex(), color, contrast)
+                    }
+                    None => HashMap::new(),
+                })
+                .collect()
+        })
+    }
+
+    /// Sets background color on a low/mid/high scale. The middle color sits at `midpoint`,
+    /// or at the median of the column if not given; each half is scaled separately.
+    pub fn background_three_point(
+        self,
+        column: &str,
+        low: &Color,
+        mid: &Color,
+        high: &Color,
+        midpoint: &Option<f64>,
+    ) -> Self {
+        self.try_background_three_point(column, low, mid, high, midpoint)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_three_point`, but returns an error for an unknown or non-numeric
+    /// column.
+    pub fn try_background_three_point(
+        mut self,
+        column: &str,
+        low: &Color,
+        mid: &Color,
+        high: &Color,
+        midpoint: &Option<f64>,
+    ) -> Result<Self, StylerError> {
+        self.check_columns(&[column], true)?;
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        self.push_operation(StyleOp::BackgroundThreePoint {
+            column: column.to_string(),
+            low: low.clone(),
+            mid: mid.clone(),
+            high: high.clone(),
+            midpoint: *midpoint,
+        });
+        let cmap = ColorMap::three_point(low.clone(), mid.clone(), high.clone());
+        self.try_apply(column, |s| {
+            let s = to_float(s);
+            let (min, max) = Norm::linear().range(&s).unwrap_or_default();
+            let midpoint = midpoint.or_else(|| s.median()).unwrap_or(min);
+            s.f64()
+                .unwrap()
+                .into_iter()
+                .map(|v| {
+                    let Some(v) = v else {
+                        return HashMap::new();
+                    };
+                    let a = if v < midpoint {
+                        0.5 * (v - min) / (midpoint - min)
+                    } else if v > midpoint {
+                        0.5 + 0.5 * (v - midpoint) / (max - midpoint)
+                    } else {
+                        0.5
+                    };
+                    let color = cmap.get(a.clamp(0.0, 1.0)).unwrap();
+                    colormap_cell(&color, opacity, contrast)
+                })
+                .collect()
+        })
+    }
+
+    pub fn background_gradient(
+        self,
+        column: &str,
+        color: &Color,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Self {
+        self.try_background_gradient(column, color, vmin, vmax)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_gradient`, but returns an error for an unknown or non-numeric
+    /// column instead of panicking. Constant columns get the middle color and nulls
+    /// are left unstyled.
+    pub fn try_background_gradient(
+        self,
+        column: &str,
+        color: &Color,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Result<Self, StylerError> {
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        let op = StyleOp::BackgroundGradient {
+            column: column.to_string(),
+            color: color.clone(),
+            vmin: *vmin,
+            vmax: *vmax,
+        };
+        let norm = Norm::Linear {
+            vmin: *vmin,
+            vmax: *vmax,
+        };
+        self.try_encode(column, &norm, op, |a| {
+            gradient_cell(color, a * opacity, contrast)
+        })
+    }
+
+    pub fn bar(self, column: &str, color: &Color, vmin: &Option<f64>, vmax: &Option<f64>) -> Self {
+        self.try_bar(column, color, vmin, vmax)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `bar`, but returns an error for an unknown or non-numeric column.
+    pub fn try_bar(
+        self,
+        column: &str,
+        color: &Color,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Result<Self, StylerError> {
+        let op = StyleOp::Bar {
+            column: column.to_string(),
+            color: color.clone(),
+            vmin: *vmin,
+            vmax: *vmax,
+        };
+        let norm = Norm::Linear {
+            vmin: *vmin,
+            vmax: *vmax,
+        };
+        self.try_encode(column, &norm, op, |a| bar_cell(color, a))
+    }
+
+    /// Bars colored from a colormap by their length, e.g. red for short and green for long.
+    pub fn bar_colormap(
+        self,
+        column: &str,
+        cmap: &ColorMap,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Self {
+        self.try_bar_colormap(column, cmap, vmin, vmax)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `bar_colormap`, but returns an error for an unknown or non-numeric column
+    /// or a colormap without colors for part of the range, see `check_colormap`.
+    pub fn try_bar_colormap(
+        self,
+        column: &str,
+        cmap: &ColorMap,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Result<Self, StylerError> {
+        self.check_colormap(cmap)?;
+        let op = StyleOp::BarColormap {
+            column: column.to_string(),
+            cmap: cmap.clone(),
+            vmin: *vmin,
+            vmax: *vmax,
+        };
+        let norm = Norm::Linear {
+            vmin: *vmin,
+            vmax: *vmax,
+        };
+        self.try_encode(column, &norm, op, |a| bar_cell(&cmap.get(a).unwrap(), a))
+    }
+
+    /// Normalizes the column and styles every non-null cell with `cell`; the operation
+    /// is recorded in the spec unless the norm is a custom closure.
+    fn try_encode(
+        self,
+        column: &str,
+        norm: &Norm,
+        op: StyleOp,
+        cell: impl Fn(f64) -> HashMap<String, String> + Sync,
+    ) -> Result<Self, StylerError> {
+        let mut op = Some(op);
+        self.try_encode_columns(&[column], norm, |_| op.take().unwrap(), cell)
+    }
+
+    /// Like `try_encode` for several columns, each normalized on its own. With the `rayon`
+    /// feature the columns and their cells are styled in parallel.
+    fn try_encode_columns(
+        self,
+        columns: &[&str],
+        norm: &Norm,
+        op: impl FnMut(&str) -> StyleOp,
+        cell: impl Fn(f64) -> HashMap<String, String> + Sync,
+    ) -> Result<Self, StylerError> {
+        let mut styler = self.try_style_columns(columns, norm, cell)?;
+        if !norm.is_custom() {
+            for op in columns.iter().copied().map(op) {
+                styler.push_operation(op);
+            }
+        }
+        Ok(styler)
+    }
+
+    /// Styles the values of each column scaled by `norm` without recording an operation.
+    fn try_style_columns(
+        mut self,
+        columns: &[&str],
+        norm: &Norm,
+        cell: impl Fn(f64) -> HashMap<String, String> + Sync,
+    ) -> Result<Self, StylerError> {
+        let encoded = par_map(columns, |&column| {
+            let (col, s) = self
+                .icolumn(column)
+                .ok_or_else(|| self.unknown_column(column))?;
+            let normalized = norm.normalize(s)?;
+            let styles = par_map(&normalized, |a| a.map(&cell).unwrap_or_default());
+            Ok((col, styles))
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, StylerError>>()?;
+        for (col, styles) in encoded {
+            self.applied_styles[col]
+                .iter_mut()
+                .zip(styles)
+                .for_each(|(a, b)| a.extend(b));
+        }
+        Ok(self)
+    }
+
+    /// Sets background gradient on several columns, each scaled by its own values like
+    /// `background_gradient`. Wide frames are styled in parallel with the `rayon` feature.
+    pub fn background_gradient_columns(
+        self,
+        columns: &[&str],
+        color: &Color,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Self {
+        self.try_background_gradient_columns(columns, color, vmin, vmax)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_gradient_columns`, but returns an error for an unknown or
+    /// non-numeric column; nothing is styled then.
+    pub fn try_background_gradient_columns(
+        self,
+        columns: &[&str],
+        color: &Color,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Result<Self, StylerError> {
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        let op = |column: &str| StyleOp::BackgroundGradient {
+            column: column.to_string(),
+            color: color.clone(),
+            vmin: *vmin,
+            vmax: *vmax,
+        };
+        let norm = Norm::Linear {
+            vmin: *vmin,
+            vmax: *vmax,
+        };
+        self.try_encode_columns(columns, &norm, op, |a| {
+            gradient_cell(color, a * opacity, contrast)
+        })
+    }
+
+    /// Sets background color of several columns from a colormap, each scaled by `norm` on
+    /// its own values.
+    pub fn background_colormap_columns(
+        self,
+        columns: &[&str],
+        cmap: &ColorMap,
+        norm: &Norm,
+    ) -> Self {
+        self.try_background_colormap_columns(columns, cmap, norm)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_colormap_columns`, but returns an error for an unknown or
+    /// non-numeric column or an incomplete colormap; nothing is styled then.
+    pub fn try_background_colormap_columns(
+        self,
+        columns: &[&str],
+        cmap: &ColorMap,
+        norm: &Norm,
+    ) -> Result<Self, StylerError> {
+        self.check_colormap(cmap)?;
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        let op = |column: &str| StyleOp::BackgroundColormap {
+            column: column.to_string(),
+            cmap: cmap.clone(),
+            norm: norm.clone(),
+        };
+        self.try_encode_columns(columns, norm, op, |a| {
+            let color = cmap.get(a).unwrap();
+            colormap_cell(&color, opacity, contrast)
+        })
+    }
+
+    /// Sets background color of several columns from a colormap on one scale, so that equal
+    /// values get equal colors in all of them: `vmin` and `vmax` default to the minimum and
+    /// maximum across the columns.
+    pub fn background_gradient_shared(
+        self,
+        columns: &[&str],
+        cmap: &ColorMap,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Self {
+        self.try_background_gradient_shared(columns, cmap, vmin, vmax)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_gradient_shared`, but returns an error for an unknown or
+    /// non-numeric column or an incomplete colormap; nothing is styled then.
+    pub fn try_background_gradient_shared(
+        self,
+        columns: &[&str],
+        cmap: &ColorMap,
+        vmin: &Option<f64>,
+        vmax: &Option<f64>,
+    ) -> Result<Self, StylerError> {
+        self.check_columns(columns, true)?;
+        self.check_colormap(cmap)?;
+        let ranges = columns
+            .iter()
+            .filter_map(|&column| Norm::linear().range(self.icolumn(column).unwrap().1))
+            .collect::<Vec<_>>();
+        let norm = Norm::Linear {
+            vmin: vmin.or_else(|| ranges.iter().map(|r| r.0).reduce(f64::min)),
+            vmax: vmax.or_else(|| ranges.iter().map(|r| r.1).reduce(f64::max)),
+        };
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        let mut styler = self.try_style_columns(columns, &norm, |a| {
+            let color = cmap.get(a).unwrap();
+            colormap_cell(&color, opacity, contrast)
+        })?;
+        styler.push_operation(StyleOp::BackgroundGradientShared {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            cmap: cmap.clone(),
+            vmin: *vmin,
+            vmax: *vmax,
+        });
+        Ok(styler)
+    }
+
+    /// Sets background gradient with values scaled by `norm`, e.g. between quantiles.
+    pub fn background_gradient_norm(self, column: &str, color: &Color, norm: &Norm) -> Self {
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        let op = StyleOp::BackgroundGradientNorm {
+            column: column.to_string(),
+            color: color.clone(),
+            norm: norm.clone(),
+        };
+        self.try_encode(column, norm, op, |a| {
+            gradient_cell(color, a * opacity, contrast)
+        })
+        .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Sets background color from a colormap, e.g. `ColorMap::named("viridis")`, with
+    /// values scaled by `norm`.
+    pub fn background_colormap(self, column: &str, cmap: &ColorMap, norm: &Norm) -> Self {
+        self.try_background_colormap(column, cmap, norm)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_colormap`, but returns an error for an unknown or non-numeric
+    /// column or a colormap without colors for part of the range, see `check_colormap`.
+    pub fn try_background_colormap(
+        self,
+        column: &str,
+        cmap: &ColorMap,
+        norm: &Norm,
+    ) -> Result<Self, StylerError> {
+        self.check_colormap(cmap)?;
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        let op = StyleOp::BackgroundColormap {
+            column: column.to_string(),
+            cmap: cmap.clone(),
+            norm: norm.clone(),
+        };
+        self.try_encode(column, norm, op, |a| {
+            let color = cmap.get(a).unwrap();
+            colormap_cell(&color, opacity, contrast)
+        })
+    }
+
+    /// Draws bars with lengths scaled by `norm`.
+    pub fn bar_norm(self, column: &str, color: &Color, norm: &Norm) -> Self {
+        let op = StyleOp::BarNorm {
+            column: column.to_string(),
+            color: color.clone(),
+            norm: norm.clone(),
+        };
+        self.try_encode(column, norm, op, |a| bar_cell(color, a))
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Sets text color from a light tint of `color` (lowest values) to `color` itself.
+    pub fn text_gradient(self, column: &str, color: &Color, norm: &Norm) -> Self {
+        let op = StyleOp::TextGradient {
+            column: column.to_string(),
+            color: color.clone(),
+            norm: norm.clone(),
+        };
+        self.try_encode(column, norm, op, |a| {
+            let text = color.over_white(0.3 + 0.7 * a);
+            HashMap::from([("color".to_string(), text.to_hex())])
+        })
+        .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Heatmap of a correlation matrix: every numeric column is colored with a diverging
+    /// `cmap` whose middle is at 0 (`vmin` and `vmax` are typically -1 and 1) and shown
+    /// with 2 decimals unless its precision is set. `mask_diagonal` hides the diagonal,
+    /// the cells where the n-th numeric column meets the n-th row.
+    pub fn heatmap_symmetric(
+        self,
+        cmap: &ColorMap,
+        vmin: f64,
+        vmax: f64,
+        mask_diagonal: bool,
+    ) -> Self {
+        self.try_heatmap_symmetric(cmap, vmin, vmax, mask_diagonal)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `heatmap_symmetric`, but returns an error for a colormap without colors for
+    /// part of the range, see `check_colormap`.
+    pub fn try_heatmap_symmetric(
+        mut self,
+        cmap: &ColorMap,
+        vmin: f64,
+        vmax: f64,
+        mask_diagonal: bool,
+    ) -> Result<Self, StylerError> {
+        self.check_colormap(cmap)?;
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        self.push_operation(StyleOp::HeatmapSymmetric {
+            cmap: cmap.clone(),
+            vmin,
+            vmax,
+            mask_diagonal,
+        });
+        let columns = self
+            .df
+            .get_columns()
+            .iter()
+            .filter(|s| s.dtype().is_numeric())
+            .map(|s| s.name().to_string())
+            .collect::<Vec<_>>();
+        let styler = columns
+            .iter()
+            .enumerate()
+            .fold(self, |mut styler, (i, column)| {
+                styler
+                    .params
+                    .column_precision
+                    .entry(column.clone())
+                    .or_insert(2);
+                styler.apply(column, |s| {
+                    let s = to_float(s);
+                    s.f64()
+                        .unwrap()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(r, v)| match v {
+                            _ if mask_diagonal && r == i => {
+                                HashMap::from([("visibility".to_string(), "hidden".to_string())])
+                            }
+                            Some(v) if !v.is_nan() => {
+                                let color = cmap.get(centered(v, vmin, vmax)).unwrap();
+                                colormap_cell(&color, opacity, contrast)
+                            }
+                            _ => HashMap::new(),
+                        })
+                        .collect()
+                })
+            });
+        Ok(styler)
+    }
+
+    /// Highlights whole rows given by their positions, e.g. records to call out.
+    pub fn highlight_rows_at(self, rows: &[usize], color: &Color) -> Self {
+        self.try_highlight_rows_at(rows, color)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `highlight_rows_at`, but returns an error for a row past the end.
+    pub fn try_highlight_rows_at(
+        mut self,
+        rows: &[usize],
+        color: &Color,
+    ) -> Result<Self, StylerError> {
+        let height = self.df.height();
+        if let Some(&row) = rows.iter().find(|&&row| row >= height) {
+            return Err(StylerError::RowOutOfBounds { row, height });
+        }
+        self.push_operation(StyleOp::HighlightRows {
+            rows: rows.to_vec(),
+            color: color.clone(),
+        });
+        Ok(self.highlight_rows(rows, color))
+    }
+
+    /// Highlights whole rows whose value in `column` is one of `values`, compared as
+    /// displayed without formatting, e.g. `["ORD-1042", "ORD-1077"]`.
+    pub fn highlight_rows_where_key(self, column: &str, values: &[&str], color: &Color) -> Self {
+        self.try_highlight_rows_where_key(column, values, color)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `highlight_rows_where_key`, but returns an error for an unknown column.
+    pub fn try_highlight_rows_where_key(
+        mut self,
+        column: &str,
+        values: &[&str],
+        color: &Color,
+    ) -> Result<Self, StylerError> {
+        let (_, s) = self
+            .icolumn(column)
+            .ok_or_else(|| self.unknown_column(column))?;
+        let rows = s
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| values.contains(&format_value(v, None).as_str()))
+            .map(|(row, _)| row)
+            .collect::<Vec<_>>();
+        self.push_operation(StyleOp::HighlightRowsWhereKey {
+            column: column.to_string(),
+            values: values.iter().map(|v| v.to_string()).collect(),
+            color: color.clone(),
+        });
+        Ok(self.highlight_rows(&rows, color))
+    }
+
+    fn highlight_rows(mut self, rows: &[usize], color: &Color) -> Self {
+        let mut styles = background_styles(color.to_hex(), color, self.params.text_contrast);
+        styles.insert(HIGHLIGHT_PROPERTY.to_string(), "1".to_string());
+        for column in self.applied_styles.iter_mut() {
+            for &row in rows {
+                column[row].extend(styles.clone());
+            }
+        }
+        self
+    }
+
+    /// Puts an icon of the set in front of the values, chosen by the band the value falls
+    /// into; `bins` must split the column into three bands, e.g. `Bins::Count(3)` or
+    /// `Bins::Edges(vec![0.0, 100.0])`.
+    pub fn icon_set(self, column: &str, icons: IconSet, bins: &Bins) -> Self {
+        self.try_icon_set(column, icons, bins)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `icon_set`, but returns an error for an unknown or non-numeric column or bins
+    /// that are not three bands; nothing is styled then.
+    pub fn try_icon_set(
+        mut self,
+        column: &str,
+        icons: IconSet,
+        bins: &Bins,
+    ) -> Result<Self, StylerError> {
+        self.check_icon_set(column, bins)?;
+        self.push_operation(StyleOp::IconSet {
+            column: column.to_string(),
+            icons,
+            bins: bins.clone(),
+        });
+        let (col, s) = self.icolumn(column).unwrap();
+        let glyphs = icons.icons();
+        for (row, bin) in bins.assign(s).into_iter().enumerate() {
+            let Some(bin) = bin else { continue };
+            let (glyph, color) = glyphs[bin];
+            let icon = format!("<span style=\"color: {}\">{}</span> ", color, glyph);
+            self.prefixes.insert((col, row), icon);
+        }
+        Ok(self)
+    }
+
+    /// Emphasizes the cells of `column` in the rows where the boolean `predicate` holds,
+    /// e.g. bold values above a threshold with `col("latency").gt(lit(500))`.
+    pub fn emphasize_when(self, column: &str, predicate: Expr, style: TextStyle) -> Self {
+        self.try_emphasize_when(column, predicate, style)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `emphasize_when`, but returns an error if the predicate fails or the column
+    /// is unknown.
+    pub fn try_emphasize_when(
+        self,
+        column: &str,
+        predicate: Expr,
+        style: TextStyle,
+    ) -> Result<Self, StylerError> {
+        let mask = evaluate_expr(predicate, &self.df)?;
+        self.try_emphasize_series(column, &mask, style)
+    }
+
+    /// Like `emphasize_when` with a boolean mask computed outside of the styler.
+    pub fn emphasize_series(self, column: &str, mask: &Series, style: TextStyle) -> Self {
+        self.try_emphasize_series(column, mask, style)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `emphasize_series`, but returns an error unless the mask is boolean with a
+    /// value for every row.
+    pub fn try_emphasize_series(
+        self,
+        column: &str,
+        mask: &Series,
+        style: TextStyle,
+    ) -> Result<Self, StylerError> {
+        self.try_set_text_series(column, mask, &style.into())
+    }
+
+    /// Renders a leading header column with the row numbers counted from `start`, so that
+    /// readers can refer to rows. The numbers are of the rows in the data, so they stay
+    /// with their rows when the table is sorted or truncated.
+    pub fn show_row_numbers(mut self, start: usize) -> Self {
+        self.params.row_numbers = Some(start);
+        self
+    }
+
+    /// Adds a row to the table footer with the aggregate of all rows of each of `columns`,
+    /// formatted like the column, e.g. the totals of a report. The label is put in the
+    /// first column when it isn't aggregated itself.
+    pub fn add_summary_row(self, label: &str, aggregation: Aggregation, columns: &[&str]) -> Self {
+        self.try_add_summary_row(label, aggregation, columns)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `add_summary_row`, but returns an error for an unknown column or, for the sum
+    /// and the mean, a column that isn't numeric.
+    pub fn try_add_summary_row(
+        mut self,
+        label: &str,
+        aggregation: Aggregation,
+        columns: &[&str],
+    ) -> Result<Self, StylerError> {
+        let numeric = matches!(aggregation, Aggregation::Sum | Aggregation::Mean);
+        self.check_columns(columns, numeric)?;
+        self.params.summary_rows.push(SummaryRow {
+            label: label.to_string(),
+            aggregation,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+        });
+        Ok(self)
+    }
+
+    /// Keeps the summary rows pinned at the bottom of the scroll container. They are in
+    /// a `<tfoot>`, so printing with `repeat_headers` repeats them on every page.
+    pub fn sticky_footer(mut self) -> Self {
+        self.params.sticky_footer = true;
+        self
+    }
+
+    /// Draws a vertical line after the column, e.g. between the dimensions and the metrics
+    /// of a report; the border spans the header and all body rows.
+    pub fn add_column_separator(self, after_column: &str, border: &Border) -> Self {
+        self.try_add_column_separator(after_column, border)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `add_column_separator`, but returns an error for an unknown column.
+    pub fn try_add_column_separator(
+        mut self,
+        after_column: &str,
+        border: &Border,
+    ) -> Result<Self, StylerError> {
+        self.check_columns(&[after_column], false)?;
+        self.params
+            .column_separators
+            .insert(after_column.to_string(), border.clone());
+        Ok(self)
+    }
+
+    /// Limits the width of the cells of the column to `max_width`, e.g. `"20em"`, so that
+    /// one long free-text value doesn't stretch the table. Overflowing text is cut with
+    /// an ellipsis and the full value is shown when hovering the cell.
+    pub fn clip_column(self, column: &str, max_width: &str) -> Self {
+        self.try_clip_column(column, max_width)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `clip_column`, but returns an error for an unknown column.
+    pub fn try_clip_column(mut self, column: &str, max_width: &str) -> Result<Self, StylerError> {
+        self.check_columns(&[column], false)?;
+        self.params
+            .clipped_columns
+            .insert(column.to_string(), max_width.to_string());
+        Ok(self)
+    }
+
+    /// Shows only the first `max_chars` characters of longer values of a text column,
+    /// followed by a "more" toggle revealing the rest, so that long messages don't blow
+    /// up row heights. The toggle is a `<details>` element and needs no script.
+    pub fn expandable_text(self, column: &str, max_chars: usize) -> Self {
+        self.try_expandable_text(column, max_chars)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `expandable_text`, but returns an error for an unknown column.
+    pub fn try_expandable_text(
+        mut self,
+        column: &str,
+        max_chars: usize,
+    ) -> Result<Self, StylerError> {
+        self.check_columns(&[column], false)?;
+        self.params
+            .expandable_columns
+            .insert(column.to_string(), max_chars);
+        Ok(self)
+    }
+
+    /// Sets the unit and description of a column in one place: the unit is shown in a
+    /// header row under the labels, or after the numbers with `unit_in_values`, and the
+    /// description when hovering the header.
+    pub fn set_column_meta(self, column: &str, meta: ColumnMeta) -> Self {
+        self.try_set_column_meta(column, meta)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `set_column_meta`, but returns an error for an unknown column.
+    pub fn try_set_column_meta(
+        mut self,
+        column: &str,
+        meta: ColumnMeta,
+    ) -> Result<Self, StylerError> {
+        self.check_columns(&[column], false)?;
+        self.params.column_meta.insert(column.to_string(), meta);
+        Ok(self)
+    }
+
+    /// Draws a border around the cells of `columns` in the rows where the boolean
+    /// `predicate` holds, e.g. failed checks. Adjacent matching cells are outlined
+    /// together: only the outer edges of each block of cells get the border. Borders of
+    /// cells look best with `border-collapse: collapse` on the table.
+    pub fn border_when(self, columns: &[&str], predicate: Expr, border: &Border) -> Self {
+        self.try_border_when(columns, predicate, border)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `border_when`, outlining whole rows.
+    pub fn border_rows_when(self, predicate: Expr, border: &Border) -> Self {
+        let columns = self.column_names();
+        let columns = columns.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+        self.border_when(&columns, predicate, border)
+    }
+
+    /// Like `border_when`, but returns an error if the predicate fails or a column is
+    /// unknown.
+    pub fn try_border_when(
+        self,
+        columns: &[&str],
+        predicate: Expr,
+        border: &Border,
+    ) -> Result<Self, StylerError> {
+        let mask = evaluate_expr(predicate, &self.df)?;
+        self.try_border_series(columns, &mask, border)
+    }
+
+    /// Like `border_when` with a boolean mask computed outside of the styler.
+    pub fn border_series(self, columns: &[&str], mask: &Series, border: &Border) -> Self {
+        self.try_border_series(columns, mask, border)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `border_series`, but returns an error unless the mask is boolean with a value
+    /// for every row.
+    pub fn try_border_series(
+        mut self,
+        columns: &[&str],
+        mask: &Series,
+        border: &Border,
+    ) -> Result<Self, StylerError> {
+        self.check_columns(columns, false)?;
+        self.check_mask(mask)?;
+        let rows = mask
+            .bool()?
+            .into_iter()
+            .map(|m| m == Some(true))
+            .collect::<Vec<_>>();
+        let mut cols = columns
+            .iter()
+            .filter_map(|c| self.get_col_idx(c))
+            .collect::<Vec<_>>();
+        cols.sort_unstable();
+        cols.dedup();
+        let css = border.css();
+        for (i, &col) in cols.iter().enumerate() {
+            let left = i == 0 || cols[i - 1] + 1 != col;
+            let right = i + 1 == cols.len() || cols[i + 1] != col + 1;
+            for (row, _) in rows.iter().enumerate().filter(|(_, &m)| m) {
+                let top = row == 0 || !rows[row - 1];
+                let bottom = row + 1 == rows.len() || !rows[row + 1];
+                let sides = [
+                    ("top", top),
+                    ("right", right),
+                    ("bottom", bottom),
+                    ("left", left),
+                ];
+                let styles = &mut self.applied_styles[col][row];
+                for (side, _) in sides.iter().filter(|(_, edge)| *edge) {
+                    styles.insert(format!("border-{}", side), css.clone());
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Sets text properties (weight, style, decoration, color, font family) of every cell
+    /// of the columns.
+    pub fn set_text(self, columns: &[&str], props: &TextProps) -> Self {
+        self.try_set_text(columns, props)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `set_text`, but returns an error for an unknown column.
+    pub fn try_set_text(
+        mut self,
+        columns: &[&str],
+        props: &TextProps,
+    ) -> Result<Self, StylerError> {
+        self.check_columns(columns, false)?;
+        self.push_operation(StyleOp::Text {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            props: props.clone(),
+        });
+        let css = props.css();
+        columns.iter().try_fold(self, |s, column| {
+            s.try_apply(column, |s| vec![css.clone(); s.len()])
+        })
+    }
+
+    /// Sets text properties of the cells of `column` in the rows where the boolean
+    /// `predicate` holds.
+    pub fn set_text_when(self, column: &str, predicate: Expr, props: &TextProps) -> Self {
+        self.try_set_text_when(column, predicate, props)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `set_text_when`, but returns an error if the predicate fails.
+    pub fn try_set_text_when(
+        self,
+        column: &str,
+        predicate: Expr,
+        props: &TextProps,
+    ) -> Result<Self, StylerError> {
+        let mask = evaluate_expr(predicate, &self.df)?;
+        self.try_set_text_series(column, &mask, props)
+    }
+
+    /// Like `set_text_when` with a boolean mask computed outside of the styler.
+    pub fn set_text_series(self, column: &str, mask: &Series, props: &TextProps) -> Self {
+        self.try_set_text_series(column, mask, props)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `set_text_series`, but returns an error unless the mask is boolean with a
+    /// value for every row.
+    pub fn try_set_text_series(
+        self,
+        column: &str,
+        mask: &Series,
+        props: &TextProps,
+    ) -> Result<Self, StylerError> {
+        self.check_mask(mask)?;
+        let mask = mask.bool()?.clone();
+        let css = props.css();
+        self.try_apply(column, |_| {
+            mask.into_iter()
+                .map(|m| match m {
+                    Some(true) => css.clone(),
+                    _ => HashMap::new(),
+                })
+                .collect()
+        })
+    }
+
+    /// Displays values of the column as icons, e.g. `"ok"` as `✅`; `icons` maps the
+    /// displayed value to HTML such as an emoji or an inline SVG. Hovering an icon shows
+    /// the original value, and values without an icon are displayed as they are.
+    pub fn map_icons(mut self, column: &str, icons: &HashMap<String, String>) -> Self {
+        self.push_operation(StyleOp::MapIcons {
+            column: column.to_string(),
+            icons: icons.clone().into_iter().collect(),
+        });
+        let (col, s) = self
+            .icolumn(column)
+            .unwrap_or_else(|| panic!("{}", self.unknown_column(column)));
+        let titles = s
+            .iter()
+            .enumerate()
+            .map(|(row, v)| (row, format_value(&v, None)))
+            .filter(|(_, text)| icons.contains_key(text))
+            .collect::<Vec<_>>();
+        for (row, text) in titles {
+            self.tooltips.insert((col, row), text);
+        }
+        let icons = icons.clone();
+        self.set_column_formatter(column, move |v| {
+            let text = format_value(v, None);
+            icons.get(&text).cloned().unwrap_or(text)
+        })
+    }
+
+    /// Appends a colored ▲, ▼ or ▬ to the values of `column` depending on whether they are
+    /// above, below or equal to the values of the `compare` column, e.g. last month's.
+    pub fn trend_arrows(self, column: &str, compare: &str) -> Self {
+        self.try_trend_arrows(column, compare)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `trend_arrows`, but returns an error for an unknown or non-numeric column.
+    pub fn try_trend_arrows(mut self, column: &str, compare: &str) -> Result<Self, StylerError> {
+        self.check_columns(&[compare], true)?;
+        let s = self.df.column(compare)?.clone();
+        self = self.try_trend_arrows_series(column, &s)?;
+        self.push_operation(StyleOp::TrendArrows {
+            column: column.to_string(),
+            compare: compare.to_string(),
+        });
+        Ok(self)
+    }
+
+    /// Like `trend_arrows`, comparing with the result of an expression, e.g.
+    /// `col("sales").shift(1)` for the previous period.
+    pub fn trend_arrows_expr(self, column: &str, e: Expr) -> Self {
+        self.try_trend_arrows_expr(column, e)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `trend_arrows_expr`, but returns an error if the expression fails.
+    pub fn try_trend_arrows_expr(self, column: &str, e: Expr) -> Result<Self, StylerError> {
+        let s = evaluate_expr(e, &self.df)?;
+        self.try_trend_arrows_series(column, &s)
+    }
+
+    /// Like `trend_arrows`, comparing with values computed outside of the styler.
+    pub fn trend_arrows_series(self, column: &str, compare: &Series) -> Self {
+        self.try_trend_arrows_series(column, compare)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `trend_arrows_series`, but returns an error if the values can't be compared.
+    pub fn try_trend_arrows_series(
+        mut self,
+        column: &str,
+        compare: &Series,
+    ) -> Result<Self, StylerError> {
+        self.check_trend_arrows(column, compare)?;
+        let (col, s) = self.icolumn(column).unwrap();
+        let (values, compare) = (to_float(s), to_float(compare));
+        let values = values.f64().unwrap().into_iter();
+        for (row, pair) in values.zip(compare.f64().unwrap()).enumerate() {
+            let (Some(v), Some(c)) = pair else { continue };
+            let (glyph, color) = match v.partial_cmp(&c) {
+                Some(std::cmp::Ordering::Greater) => ("▲", TREND_UP),
+                Some(std::cmp::Ordering::Less) => ("▼", TREND_DOWN),
+                Some(std::cmp::Ordering::Equal) => ("▬", TREND_FLAT),
+                None => continue,
+            };
+            let arrow = format!(" <span style=\"color: {}\">{}</span>", color, glyph);
+            self.suffixes.insert((col, row), arrow);
+        }
+        Ok(self)
+    }
+
+    /// Master-detail report: under each row, renders the rows of `detail` with the same
+    /// value in the `key` column (e.g. order lines of an order) as an indented nested
+    /// table with the styles of `detail`. With `collapsible`, the nested tables are folded
+    /// in a `<details>` element showing the number of rows.
+    pub fn set_details(self, key: &str, detail: Styler, collapsible: bool) -> Self {
+        self.try_set_details(key, detail, collapsible)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `set_details`, but returns an error when either frame lacks the `key` column.
+    pub fn try_set_details(
+        mut self,
+        key: &str,
+        detail: Styler,
+        collapsible: bool,
+    ) -> Result<Self, StylerError> {
+        self.check_columns(&[key], false)?;
+        detail.check_columns(&[key], false)?;
+        self.details = Some(Details {
+            key: key.to_string(),
+            styler: Box::new(detail),
+            collapsible,
+        });
+        Ok(self)
+    }
+
+    /// Highlights cells that differ from `old`, a baseline with the same columns, e.g.
+    /// yesterday's version of a report. Rows are compared by position.
+    pub fn diff(self, old: &DataFrame) -> Self {
+        self.diff_with(old, &DiffStyle::default())
+    }
+
+    /// Like `diff` with custom colors.
+    pub fn diff_with(self, old: &DataFrame, style: &DiffStyle) -> Self {
+        self.try_diff_with(old, style)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `diff_with`, but returns an error when `old` lacks a column.
+    pub fn try_diff_with(
+        mut self,
+        old: &DataFrame,
+        style: &DiffStyle,
+    ) -> Result<Self, StylerError> {
+        self.check_diff(old)?;
+        let contrast = self.params.text_contrast;
+        for (c, new) in self.df.clone().get_columns().iter().enumerate() {
+            let old = old.column(new.name()).unwrap();
+            for (r, v) in new.iter().enumerate() {
+                let before = match old.get(r) {
+                    Ok(before) if before == v => continue,
+                    Ok(before) => Some(before),
+                    Err(_) => None,
+                };
+                let color = match (&before, &v) {
+                    (None, _) | (Some(AnyValue::Null), _) => &style.added,
+                    (_, AnyValue::Null) => &style.removed,
+                    _ => &style.modified,
+                };
+                self.applied_styles[c][r].extend(background_styles(
+                    color.to_hex(),
+                    color,
+                    contrast,
+                ));
+                if let (Some(before), true) = (before, style.tooltips) {
+                    let (before, after) = (format_value(&before, None), format_value(&v, None));
+                    self.tooltips
+                        .insert((c, r), format!("{} → {}", before, after));
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Sets background gradient on several columns, normalized per column, per row or
+    /// across all of them (like `axis` of pandas `background_gradient`).
+    pub fn background_gradient_axis(self, columns: &[&str], color: &Color, axis: Axis) -> Self {
+        self.try_background_gradient_axis(columns, color, axis)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_gradient_axis`, but returns an error for an unknown or non-numeric
+    /// column; nothing is styled then.
+    pub fn try_background_gradient_axis(
+        mut self,
+        columns: &[&str],
+        color: &Color,
+        axis: Axis,
+    ) -> Result<Self, StylerError> {
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        self.check_columns(columns, true)?;
+        self.push_operation(StyleOp::BackgroundGradientAxis {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            color: color.clone(),
+            axis,
+        });
+        let values = columns
+            .iter()
+            .map(|&column| {
+                let s = to_float(self.df.column(column).unwrap());
+                s.f64().unwrap().into_iter().collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let normalized = normalize_axis(&values, axis);
+        Ok(columns
+            .iter()
+            .zip(normalized)
+            .fold(self, |styler, (column, alphas)| {
+                styler.apply(column, |_| {
+                    alphas
+                        .iter()
+                        .map(|a| match a {
+                            Some(a) => gradient_cell(color, a * opacity, contrast),
+                            None => HashMap::new(),
+                        })
+                        .collect()
+                })
+            }))
+    }
+
+    pub fn background_gradient_expr(self, e: Expr, color: &Color) -> Self {
+        self.try_background_gradient_expr(e, color)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_gradient_expr`, but returns an error if the expression fails or
+    /// doesn't give a numeric column of the DataFrame.
+    pub fn try_background_gradient_expr(self, e: Expr, color: &Color) -> Result<Self, StylerError> {
+        let s = evaluate_expr(e, &self.df)?;
+        self.try_background_gradient_series(&s, color)
+    }
+
+    /// Sets background gradient on the column named like the series, scaled by the values
+    /// of the series, e.g. an expression evaluated outside of Rust.
+    pub fn background_gradient_series(self, s: &Series, color: &Color) -> Self {
+        self.try_background_gradient_series(s, color)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `background_gradient_series`, but returns an error if the series isn't named
+    /// after a column, has another length or isn't numeric.
+    pub fn try_background_gradient_series(
+        mut self,
+        s: &Series,
+        color: &Color,
+    ) -> Result<Self, StylerError> {
+        let (contrast, opacity) = (self.params.text_contrast, self.opacity());
+        self.check_series(s)?;
+        let c = self.get_col_idx(s.name()).unwrap();
+        Norm::linear()
+            .normalize(s)?
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, v)| {
+                if let Some(v) = v {
+                    self.applied_styles[c][i].extend(gradient_cell(color, v * opacity, contrast));
+                }
+            });
+        Ok(self)
+    }
+
+    pub fn render(self) -> String {
+        self.try_render().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render`, but returns an error for a DataFrame without columns.
+    pub fn try_render(self) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        Ok(self.renderer().render())
+    }
+
+    fn check_has_columns(&self) -> Result<(), StylerError> {
+        match self.df.width() {
+            0 => Err(StylerError::NoColumns),
+            _ => Ok(()),
+        }
+    }
+
+    /// Renders HTML for snapshot tests: the uid is fixed unless set with `set_uid`, and the
+    /// whitespace is normalized with `snapshot::normalize_html`. Rules and declarations are
+    /// always written in a stable order. Compare the output with `snapshot::assert_snapshot`.
+    pub fn render_deterministic(mut self) -> String {
+        if self.params.uid.is_none() {
+            self.params.uid = Some(SNAPSHOT_UID.to_string());
+        }
+        normalize_html(&self.render())
+    }
+
+    /// Renders HTML that is the same on the server and in the browser, for frameworks that
+    /// hydrate server-rendered markup: unless set with `set_uid`, the uid is derived from the
+    /// table instead of being random. The styles are scoped to the table by its id.
+    pub fn render_hydratable(mut self) -> String {
+        if self.params.uid.is_none() {
+            let mut hasher = DefaultHasher::new();
+            self.clone().set_uid("").render().hash(&mut hasher);
+            self.params.uid = Some(format!("{:06x}", hasher.finish() & 0xff_ffff));
+        }
+        self.render()
+    }
+
+    /// Renders the table as a `<polars-table>` web component; its styles live in a shadow root
+    /// and are isolated from the host page.
+    pub fn render_web_component(self) -> String {
+        self.try_render_web_component()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_web_component`, but returns an error for a DataFrame without columns.
+    pub fn try_render_web_component(self) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        Ok(self.renderer().render_web_component())
+    }
+
+    pub fn render_markdown(self) -> String {
+        self.try_render_markdown()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_markdown`, but returns an error for a DataFrame without columns.
+    pub fn try_render_markdown(self) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        Ok(self.renderer().render_markdown())
+    }
+
+    pub fn render_latex(self) -> String {
+        self.try_render_latex().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_latex`, but returns an error for a DataFrame without columns.
+    pub fn try_render_latex(self) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        Ok(self.renderer().render_latex())
+    }
+
+    /// Renders only the `<tr>` elements of the body, for swapping into a table rendered
+    /// earlier with the same uid (see `set_uid`).
+    pub fn render_rows_only(self) -> String {
+        self.try_render_rows_only()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_rows_only`, but returns an error for a DataFrame without columns.
+    pub fn try_render_rows_only(self) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        Ok(self.renderer().render_rows())
+    }
+
+    /// Renders only the rows that differ from the table rendered from `previous`, e.g. the
+    /// styler of the previous state of a live dashboard, so that a server can push small
+    /// DOM updates. Rows are matched by their id, i.e. by position in the data; both tables
+    /// must share the uid, which is taken from `previous` if this styler doesn't set one.
+    pub fn render_changed_rows(mut self, previous: Styler) -> RowUpdates {
+        let uid = self
+            .params
+            .uid
+            .take()
+            .or_else(|| previous.params.uid.clone());
+        let uid = uid.unwrap_or_else(random_hash);
+        let mut old = previous
+            .set_uid(&uid)
+            .renderer()
+            .row_fragments()
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let renderer = self.set_uid(&uid).renderer();
+        let rows = renderer
+            .row_fragments()
+            .into_iter()
+            .filter_map(|(id, html)| (old.remove(&id).as_ref() != Some(&html)).then_some(html))
+            .collect();
+        let removed = old.into_keys().sorted().collect();
+        RowUpdates {
+            rows,
+            removed,
+            css: renderer.render_css(),
+        }
+    }
+
+    /// Renders the `<tbody>` element (with id `T_{uid}_body`) without header, styles or scripts.
+    pub fn render_body(self) -> String {
+        self.try_render_body().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_body`, but returns an error for a DataFrame without columns.
+    pub fn try_render_body(self) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        Ok(self.renderer().render_body())
+    }
+
+    /// Renders the cell styles as plain CSS rules, to be served or swapped separately from the rows.
+    pub fn render_css(self) -> String {
+        self.try_render_css().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_css`, but returns an error for a DataFrame without columns.
+    pub fn try_render_css(self) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        Ok(self.renderer().render_css())
+    }
+
+    /// Returns the formatted values and styles without rendering them,
+    /// e.g. to be passed to a template engine that owns the markup.
+    pub fn to_context(self) -> TableContext {
+        self.try_to_context().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `to_context`, but returns an error for a DataFrame without columns.
+    pub fn try_to_context(self) -> Result<TableContext, StylerError> {
+        self.check_has_columns()?;
+        Ok(self.renderer().context())
+    }
+
+    /// Renders the first `n` rows; styles are computed as configured by `set_normalize_on_selection`.
+    pub fn render_head(self, n: usize) -> String {
+        self.try_render_head(n).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_head`, but returns an error for a DataFrame without columns.
+    pub fn try_render_head(self, n: usize) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        let n = n.min(self.df.height());
+        Ok(self.selection_renderer((0..n).collect()).render())
+    }
+
+    /// Renders the last `n` rows.
+    pub fn render_tail(self, n: usize) -> String {
+        self.try_render_tail(n).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_tail`, but returns an error for a DataFrame without columns.
+    pub fn try_render_tail(self, n: usize) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        let height = self.df.height();
+        let n = n.min(height);
+        Ok(self
+            .selection_renderer((height - n..height).collect())
+            .render())
+    }
+
+    /// Renders `n` rows sampled without replacement, in their original order.
+    /// The same seed always selects the same rows.
+    pub fn render_sample(self, n: usize, seed: u64) -> String {
+        self.try_render_sample(n, seed)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `render_sample`, but returns an error for a DataFrame without columns.
+    pub fn try_render_sample(self, n: usize, seed: u64) -> Result<String, StylerError> {
+        self.check_has_columns()?;
+        let rows = sample_rows(self.df.height(), n, seed);
+        Ok(self.selection_renderer(rows).render())
+    }
+
+    /// Shows `page_size` rows at a time with previous/next controls (rendered by embedded JS)
+    /// once the table has more rows than that.
+    pub fn set_pagination(mut self, page_size: usize) -> Self {
+        self.params.interactivity.page_size = Some(page_size);
+        self
+    }
+
+    /// Makes the headers sort the table when clicked (rendered by embedded JS).
+    pub fn set_sortable(mut self, sortable: bool) -> Self {
+        self.params.interactivity.sortable = sortable;
+        self
+    }
+
+    /// Adds a row of filter inputs under the headers (rendered by embedded JS).
+    /// Queries like `>5` or `1..5` filter numerically, others by substring.
+    pub fn set_filterable(mut self, filterable: bool) -> Self {
+        self.params.interactivity.filterable = filterable;
+        self
+    }
+
+    /// Adds a search box above the table that outlines matching cells and,
+    /// with `SearchMode::Filter`, hides rows without a match (rendered by embedded JS).
+    pub fn set_search(mut self, mode: SearchMode) -> Self {
+        self.params.interactivity.search = Some(mode);
+        self
+    }
+
+    /// Adds a "Download CSV" button above the table (rendered by embedded JS).
+    /// The exported values are the raw ones, not the formatted ones.
+    pub fn set_csv_download(mut self, filename: &str) -> Self {
+        self.params.interactivity.csv_download = Some(filename.to_string());
+        self
+    }
+
+    /// Renders a color bar with its value range below the table for every `background_gradient`.
+    pub fn with_legend(mut self) -> Self {
+        self.params.legend = true;
+        self
+    }
+
+    /// Lists the conditional rules below the table with the color or icon marking their
+    /// cells, e.g. a red swatch for "Price > 100", generated from the highlight, icon and
+    /// trend arrow rules.
+    pub fn with_rules_legend(mut self) -> Self {
+        self.params.rules_legend = true;
+        self
+    }
+
+    /// Sets the table caption. Tables should have one: it names the table for screen readers
+    /// and makes the wrapper a labelled ARIA region.
+    pub fn set_caption(mut self, caption: &str) -> Self {
+        self.params.caption = Some(caption.to_string());
+        self
+    }
+
+    /// Leaves the columns out of the rendered table; they can still drive styles.
+    pub fn hide_columns(mut self, columns: &[&str]) -> Self {
+        self.params
+            .hidden_columns
+            .extend(columns.iter().map(|c| c.to_string()));
+        self
+    }
+
+    /// Replaces the CSS rules scoped to the table, e.g. `TableStyle::new("th", "color: red")`.
+    pub fn set_table_styles(mut self, styles: Vec<TableStyle>) -> Self {
+        self.params.table_styles = styles;
+        self
+    }
+
+    /// Renders the cells of `column` as row headers (`<th scope="row">`).
+    pub fn set_row_header(mut self, column: &str) -> Self {
+        self.params.row_header = Some(column.to_string());
+        self
+    }
+
+    /// Caps the opacity of gradient and colormap backgrounds styled after this call, so
+    /// they are subtle tints rather than full-saturation colors.
+    pub fn set_gradient_opacity(mut self, opacity: f64) -> Self {
+        self.params.gradient_opacity = Some(opacity.clamp(0.0, 1.0));
+        self
+    }
+
+    fn opacity(&self) -> f64 {
+        self.params.gradient_opacity.unwrap_or(1.0)
+    }
+
+    /// Sets how backgrounds styled after this call keep the text readable; by default
+    /// the text turns white on dark backgrounds.
+    pub fn set_text_contrast(mut self, contrast: TextContrast) -> Self {
+        self.params.text_contrast = contrast;
+        self
+    }
+
+    pub fn text_contrast(&self) -> TextContrast {
+        self.params.text_contrast
+    }
+
+    /// Sets the text direction; `Direction::Rtl` adds `dir="rtl"` to the table, so the first
+    /// column is on the right, and right-aligns LaTeX columns.
+    pub fn set_direction(mut self, direction: Direction) -> Self {
+        self.params.direction = direction;
+        self
+    }
+
+    /// Adds `@media print` rules; `PrintOptions::default()` enables all of them.
+    pub fn set_print_options(mut self, options: PrintOptions) -> Self {
+        self.params.print = Some(options);
+        self
+    }
+
+    /// Fixes the table uid used in element ids (`T_{uid}`, `T_{uid}_row{r}`, ...) instead of
+    /// a random one, so that partial renders target the table rendered before.
+    pub fn set_uid(mut self, uid: &str) -> Self {
+        self.params.uid = Some(uid.to_string());
+        self
+    }
+
+    /// If set, gradients and other value-dependent styles of `render_head`, `render_tail`
+    /// and `render_sample` are recomputed on the selected rows only, instead of the
+    /// full columns. Styles added by `apply` closures or expressions are dropped in that case.
+    pub fn set_normalize_on_selection(mut self, normalize_on_selection: bool) -> Self {
+        self.params.normalize_on_selection = normalize_on_selection;
+        self
+    }
+
+    fn selection_renderer(self, rows: Vec<usize>) -> Renderer {
+        if !self.params.normalize_on_selection {
+            return self.rows_renderer(rows);
+        }
+        let key = (
+            self.generation,
+            self.settings_spec().to_json(),
+            self.column_names(),
+            rows.clone(),
+        );
+        let mut cache = self.selections.lock().unwrap();
+        let selection = match cache.as_ref() {
+            Some((cached, selection)) if *cached == key => selection.clone(),
+            _ => {
+                let idx = IdxCa::from_vec("", rows.iter().map(|&r| r as IdxSize).collect());
+                let df = self.df.take(&idx).unwrap();
+                let selection = Styler::new(&df).apply_spec(&self.spec());
+                *cache = Some((key, selection.clone()));
+                selection
+            }
+        };
+        drop(cache);
+        let n = selection.df.height();
+        let mut renderer = selection.rows_renderer((0..n).collect());
+        renderer.row_ids = renderer.row_ids.iter().map(|&r| rows[r]).collect();
+        renderer
+    }
+
+    fn renderer(self) -> Renderer {
+        let height = self.df.height();
+        self.rows_renderer((0..height).collect())
+    }
+
+    fn rows_renderer(&self, rows: Vec<usize>) -> Renderer {
+        let hash = self.params.uid.clone().unwrap_or_else(random_hash);
+        self.hashed_rows_renderer(rows, hash)
+    }
+
+    /// Renderer of the rows with table id `T_{hash}`. Only the rendered cells are copied,
+    /// so that a shared styler, e.g. of detail rows, renders any number of row subsets.
+    fn hashed_rows_renderer(&self, rows: Vec<usize>, hash: String) -> Renderer {
+        let nrow = rows.len();
+        let (head, tail) = match self.params.max_rows {
+            Some(n) if n < nrow => (n - n / 2, n / 2),
+            _ => (nrow, 0),
+        };
+        let row_ids = [&rows[..head], &rows[nrow - tail..]].concat();
+        let hidden_rows = (head + tail < nrow).then_some((head, nrow - head - tail));
+        let idx = IdxCa::from_vec("", row_ids.iter().map(|&r| r as IdxSize).collect());
+
+        let column_names = self.column_names();
+        let visible = (0..column_names.len())
+            .filter(|&c| !self.params.hidden_columns.contains(&column_names[c]))
+            .collect::<Vec<_>>();
+        let width = visible.len();
+        let (left, right) = match self.params.max_columns {
+            Some(n) if n < width => (n - n / 2, n / 2),
+            _ => (width, 0),
+        };
+        let col_ids = [&visible[..left], &visible[width - right..]].concat();
+        let hidden_columns = (left + right < width).then_some((left, width - left - right));
+
+        // Rendering all rows in order shares the columns instead of gathering a copy.
+        let all_rows = row_ids.iter().copied().eq(0..self.df.height());
+        let columns = col_ids
+            .iter()
+            .map(|&c| {
+                let s = &self.df.get_columns()[c];
+                if all_rows {
+                    s.clone()
+                } else {
+                    s.take(&idx).unwrap()
+                }
+            })
+            .collect::<Vec<_>>();
+        let data = columns
+            .iter()
+            .map(|s| {
+                let values = format_row(s, &self.params);
+                let values = match self.params.expandable_columns.get(s.name()) {
+                    Some(&max_chars) => expandable(values, max_chars),
+                    None => values,
+                };
+                match self.params.percent_of_total.get(s.name()) {
+                    Some(&with_value) => {
+                        let total = to_float(self.df.column(s.name()).unwrap())
+                            .f64()
+                            .unwrap()
+                            .sum();
+                        percent_of_total(s, values, total.unwrap_or(0.0), with_value)
+                    }
+                    None => values,
+                }
+            })
+            .collect::<Vec<_>>();
+        let (sort_values, sort_types) = if self.params.interactivity.needs_raw_values() {
+            columns.iter().map(sort_column).unzip()
+        } else {
+            (vec![], vec![])
+        };
+
+        let mut cell_styles: HashMap<(usize, usize), HashMap<String, String>> = HashMap::new();
+        for (c, &col_id) in col_ids.iter().enumerate() {
+            for (r, &row_id) in row_ids.iter().enumerate() {
+                let map = &self.applied_styles[col_id][row_id];
+                if map.is_empty() {
+                    continue;
+                }
+                cell_styles.insert((r, c), map.clone());
+            }
+        }
+
+        let column_labels = col_ids
+            .iter()
+            .map(|&c| {
+                let col = &column_names[c];
+                self.labels.get(col).unwrap_or(col).to_owned()
+            })
+            .collect::<Vec<String>>();
+        let column_separators = col_ids
+            .iter()
+            .enumerate()
+            .filter_map(|(c, &id)| {
+                let border = self.params.column_separators.get(&column_names[id])?;
+                Some((c, border.css()))
+            })
+            .collect();
+        let column_meta = |f: fn(&ColumnMeta) -> Option<&String>| {
+            col_ids
+                .iter()
+                .enumerate()
+                .filter_map(|(c, &id)| {
+                    let meta = self.params.column_meta.get(&column_names[id])?;
+                    Some((c, f(meta)?.clone()))
+                })
+                .collect::<HashMap<_, _>>()
+        };
+        let footer_rows = self
+            .params
+            .summary_rows
+            .iter()
+            .map(|summary| {
+                col_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(c, &id)| {
+                        let s = &self.df.get_columns()[id];
+                        if summary.columns.iter().any(|column| column == s.name()) {
+                            let value = format_row(&summary.aggregation.apply(s), &self.params);
+                            value.get(0).to_string()
+                        } else if c == 0 {
+                            summary.label.clone()
+                        } else {
+                            String::new()
+                        }
+                    })
+                    .collect()
+            })
             .collect();
+        let column_units = column_meta(ColumnMeta::header_unit);
+        let column_descriptions = column_meta(|meta| meta.description.as_ref());
+        let mut table_styles = self.params.table_styles.clone();
+        if col_ids.iter().any(|&id| {
+            self.params
+                .expandable_columns
+                .contains_key(&column_names[id])
+        }) {
+            table_styles.extend([
+                TableStyle::new(".ps-more", "display: inline"),
+                TableStyle::new(".ps-more > summary", "display: inline; cursor: pointer"),
+                TableStyle::new(".ps-more[open] > summary", "display: none"),
+            ]);
+        }
+        let mut cell_tooltips = rendered_cells(&self.tooltips, &row_ids, &col_ids);
+        for (c, &id) in col_ids.iter().enumerate() {
+            let Some(max_width) = self.params.clipped_columns.get(&column_names[id]) else {
+                continue;
+            };
+            let clip = HashMap::from([
+                ("max-width".to_string(), max_width.clone()),
+                ("overflow".to_string(), "hidden".to_string()),
+                ("text-overflow".to_string(), "ellipsis".to_string()),
+                ("white-space".to_string(), "nowrap".to_string()),
+            ]);
+            for r in 0..row_ids.len() {
+                cell_styles.entry((r, c)).or_default().extend(clip.clone());
+                cell_tooltips
+                    .entry((r, c))
+                    .or_insert_with(|| data[c].get(r).to_string());
+            }
+        }
+        let cell_prefixes = rendered_cells(&self.prefixes, &row_ids, &col_ids);
+        let cell_suffixes = rendered_cells(&self.suffixes, &row_ids, &col_ids);
+        let detail_rows = match &self.details {
+            Some(details) => self.detail_rows(details, &row_ids, &hash),
+            None => HashMap::new(),
+        };
+        let legends = self.legends();
+        let rules = self.rules_legend();
+        let row_header = self
+            .params
+            .row_header
+            .as_ref()
+            .and_then(|c| self.get_col_idx(c));
+
+        Renderer {
+            column_labels,
+            cell_values: data,
+            cell_styles,
+            cell_tooltips,
+            cell_prefixes,
+            cell_suffixes,
+            hash,
+            detail_rows,
+            classes: self.params.table_classes.clone().unwrap_or_default(),
+            row_ids,
+            hidden_rows,
+            col_ids,
+            hidden_columns,
+            interactivity: self.params.interactivity.clone(),
+            sort_values,
+            sort_types,
+            print: self.params.print.clone(),
+            direction: self.params.direction,
+            caption: self.params.caption.clone(),
+            row_header,
+            legends,
+            rules,
+            table_styles,
+            column_separators,
+            column_units,
+            column_descriptions,
+            footer_rows,
+            sticky_footer: self.params.sticky_footer,
+            row_numbers: self.params.row_numbers,
+            ..Default::default()
+        }
+    }
+
+    /// Nested tables of the rendered rows that have detail rows, keyed by rendered row.
+    /// Keys are compared as values of their common type, e.g. an Int32 key matches an
+    /// Int64 one; null keys have no detail rows.
+    fn detail_rows(
+        &self,
+        details: &Details,
+        row_ids: &[usize],
+        hash: &str,
+    ) -> HashMap<usize, String> {
+        let key = self.df.column(&details.key).unwrap();
+        let detail_key = details.styler.df.column(&details.key).unwrap();
+        let dtype = match (key.dtype(), detail_key.dtype()) {
+            (a, b) if a == b => a.clone(),
+            (a, b) if a.is_integer() && b.is_integer() => DataType::Int64,
+            (a, b) if a.is_numeric() && b.is_numeric() => DataType::Float64,
+            _ => DataType::Utf8,
+        };
+        let key_values = |s: &Series| match s.cast(&dtype) {
+            Ok(s) => s
+                .iter()
+                .map(|v| (!matches!(v, AnyValue::Null)).then(|| v.to_string()))
+                .collect::<Vec<_>>(),
+            Err(_) => vec![None; s.len()],
+        };
+        let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+        for (row, key) in key_values(detail_key).into_iter().enumerate() {
+            if let Some(key) = key {
+                groups.entry(key).or_default().push(row);
+            }
+        }
+        let keys = key_values(key);
+        row_ids
+            .iter()
+            .enumerate()
+            .filter_map(|(r, &row_id)| {
+                let rows = groups.get(keys[row_id].as_ref()?)?;
+                let table = details
+                    .styler
+                    .hashed_rows_renderer(rows.clone(), format!("{}_d{}", hash, row_id))
+                    .render();
+                let html = match details.collapsible {
+                    true => format!(
+                        "<details><summary>{} row{}</summary>{}</details>",
+                        rows.len(),
+                        if rows.len() == 1 { "" } else { "s" },
+                        table
+                    ),
+                    false => table,
+                };
+                Some((r, html))
+            })
+            .collect()
+    }
+
+    fn legends(&self) -> Vec<Legend> {
+        if !self.params.legend {
+            return vec![];
+        }
+        self.operations
+            .iter()
+            .filter_map(|op| {
+                let (column, stops, (min, max)) = match op {
+                    StyleOp::BackgroundGradient {
+                        column,
+                        color,
+                        vmin,
+                        vmax,
+                    } => {
+                        let norm = Norm::Linear {
+                            vmin: *vmin,
+                            vmax: *vmax,
+                        };
+                        let stops = vec![color.to_rgba(0.0), color.to_rgba(self.opacity())];
+                        (column, stops, norm.range(self.df.column(column).ok()?)?)
+                    }
+                    StyleOp::BackgroundGradientNorm {
+                        column,
+                        color,
+                        norm,
+                    } => {
+                        let stops = vec![color.to_rgba(0.0), color.to_rgba(self.opacity())];
+                        (column, stops, norm.range(self.df.column(column).ok()?)?)
+                    }
+                    StyleOp::BackgroundColormap { column, cmap, norm } => (
+                        column,
+                        cmap.css_stops_with_opacity(self.opacity()),
+                        norm.range(self.df.column(column).ok()?)?,
+                    ),
+                    _ => return None,
+                };
+                let precision = self
+                    .params
+                    .column_precision
+                    .get(column)
+                    .or(self.params.precision.as_ref());
+                let format = self.params.column_formats.get(column);
+                let dtype = self.df.column(column).ok()?.dtype();
+                Some(Legend {
+                    label: self.labels.get(column).unwrap_or(column).to_owned(),
+                    stops,
+                    min: format_bound(min, dtype, precision, format),
+                    max: format_bound(max, dtype, precision, format),
+                })
+            })
+            .collect()
+    }
+
+    fn rules_legend(&self) -> Vec<RuleLegend> {
+        if !self.params.rules_legend {
+            return vec![];
+        }
+        let label = |column: &String| self.labels.get(column).unwrap_or(column).to_owned();
+        let icon = |glyph: &str, color: &str| RuleMarker::Icon {
+            glyph: glyph.to_string(),
+            color: Some(color.to_string()),
+        };
+        self.operations
+            .iter()
+            .flat_map(|op| match op {
+                StyleOp::Highlight {
+                    column,
+                    condition,
+                    color,
+                } => vec![RuleLegend {
+                    marker: RuleMarker::Background(color.to_hex()),
+                    label: format!("{} {}", label(column), condition),
+                }],
+                StyleOp::HighlightRows { rows, color } => {
+                    let first = self.params.row_numbers.unwrap_or(1);
+                    let rows = rows.iter().map(|r| (r + first).to_string()).join(", ");
+                    let noun = if rows.contains(',') { "Rows" } else { "Row" };
+                    vec![RuleLegend {
+                        marker: RuleMarker::Background(color.to_hex()),
+                        label: format!("{} {}", noun, rows),
+                    }]
+                }
+                StyleOp::HighlightRowsWhereKey {
+                    column,
+                    values,
+                    color,
+                } => vec![RuleLegend {
+                    marker: RuleMarker::Background(color.to_hex()),
+                    label: format!("{} is {}", label(column), values.join(", ")),
+                }],
+                StyleOp::TrendArrows { column, compare } => {
+                    let (column, compare) = (label(column), label(compare));
+                    vec![
+                        RuleLegend {
+                            marker: icon("▲", TREND_UP),
+                            label: format!("{} above {}", column, compare),
+                        },
+                        RuleLegend {
+                            marker: icon("▼", TREND_DOWN),
+                            label: format!("{} below {}", column, compare),
+                        },
+                    ]
+                }
+                StyleOp::IconSet {
+                    column,
+                    icons,
+                    bins,
+                } => {
+                    let column = label(column);
+                    let bands = match bins {
+                        Bins::Edges(edges) => vec![
+                            format!("{} < {}", column, edges[0]),
+                            format!("{} {} to {}", column, edges[0], edges[1]),
+                            format!("{} ≥ {}", column, edges[1]),
+                        ],
+                        Bins::Count(_) => ["Low", "Medium", "High"]
+                            .iter()
+                            .map(|band| format!("{} {}", band, column))
+                            .collect(),
+                    };
+                    icons
+                        .icons()
+                        .iter()
+                        .zip(bands)
+                        .rev()
+                        .map(|((glyph, color), label)| RuleLegend {
+                            marker: icon(glyph, color),
+                            label,
+                        })
+                        .collect()
+                }
+                StyleOp::MapIcons { column, icons } => icons
+                    .iter()
+                    .map(|(value, glyph)| RuleLegend {
+                        marker: RuleMarker::Icon {
+                            glyph: glyph.clone(),
+                            color: None,
+                        },
+                        label: format!("{} is {}", label(column), value),
+                    })
+                    .collect(),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    /// Human-readable summary of the data and the styling that will be rendered.
+    pub fn describe(&self) -> String {
+        let (height, width) = self.df.shape();
+        let mut lines = vec![format!(
+            "Styler: {} rows x {} columns ({})",
+            height,
+            width,
+            self.column_names().join(", ")
+        )];
+        if let Some(precision) = self.params.precision {
+            lines.push(format!("  precision: {}", precision));
+        }
+        if !self.params.column_precision.is_empty() {
+            let precision = self
+                .params
+                .column_precision
+                .iter()
+                .sorted()
+                .map(|(c, p)| format!("{}={}", c, p));
+            lines.push(format!("  column precision: {}", precision.format(", ")));
+        }
+        if !self.params.column_formats.is_empty() {
+            let columns = self.params.column_formats.keys().sorted();
+            lines.push(format!("  formats: {}", columns.format(", ")));
+        }
+        if !self.params.formatters.is_empty() {
+            let columns = self.params.formatters.keys().sorted();
+            lines.push(format!("  formatters: {}", columns.format(", ")));
+        }
+        if let Some(classes) = &self.params.table_classes {
+            lines.push(format!("  classes: {}", classes.join(" ")));
+        }
+        if !self.labels.is_empty() {
+            let labels = self
+                .labels
+                .iter()
+                .sorted()
+                .map(|(c, l)| format!("{} -> {}", c, l));
+            lines.push(format!("  labels: {}", labels.format(", ")));
+        }
+        if !self.params.hidden_columns.is_empty() {
+            let hidden = self.params.hidden_columns.join(", ");
+            lines.push(format!("  hidden columns: {}", hidden));
+        }
+        if let Some(caption) = &self.params.caption {
+            lines.push(format!("  caption: {}", caption));
+        }
+        if !self.operations.is_empty() {
+            lines.push("  operations:".to_string());
+            lines.extend(
+                self.operations
+                    .iter()
+                    .map(|op| format!("    {}", op.summary())),
+            );
+        }
+        let styled = self
+            .applied_styles
+            .iter()
+            .flatten()
+            .filter(|styles| !styles.is_empty())
+            .count();
+        lines.push(format!("  styled cells: {}", styled));
+        lines.join("\n")
+    }
+
+    /// The styled data.
+    pub fn data(&self) -> &DataFrame {
+        &self.df
+    }
+
+    /// The styled data without copying it, e.g. to style it again with `from_shared`.
+    pub fn shared_data(&self) -> Arc<DataFrame> {
+        self.df.clone()
+    }
+
+    pub fn precision(&self) -> Option<u32> {
+        self.params.precision
+    }
+
+    pub fn table_classes(&self) -> Option<&[String]> {
+        self.params.table_classes.as_deref()
+    }
+
+    /// Returns an error unless every column exists and, with `numeric`, can be scaled by
+    /// a norm. Lets callers validate a step before applying it.
+    pub fn check_columns(&self, columns: &[&str], numeric: bool) -> Result<(), StylerError> {
+        for &column in columns {
+            let (_, s) = self
+                .icolumn(column)
+                .ok_or_else(|| self.unknown_column(column))?;
+            if numeric {
+                check_numeric(s)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `cmap` has a color for every value in `0..=1`, which normalized values
+    /// are looked up with; it hasn't if its break points don't cover that range and values
+    /// outside are errors (`OutOfRange::Error`).
+    pub fn check_colormap(&self, cmap: &ColorMap) -> Result<(), StylerError> {
+        if cmap.get(0.0).is_err() || cmap.get(1.0).is_err() {
+            return Err(StylerError::InvalidArgument(
+                "Colormap has no color for part of 0..=1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the series is numeric and matches a column of the DataFrame by name
+    /// and length.
+    pub fn check_series(&self, s: &Series) -> Result<(), StylerError> {
+        if self.get_col_idx(s.name()).is_none() {
+            return Err(self.unknown_column(s.name()));
+        }
+        if s.len() != self.df.height() {
+            return Err(StylerError::LengthMismatch {
+                column: s.name().to_string(),
+                expected: self.df.height(),
+                got: s.len(),
+            });
+        }
+        check_numeric(s)
+    }
+
+    /// Checks that the series can be added as a new column, see `add_computed_series`.
+    pub fn check_computed_series(&self, s: &Series) -> Result<(), StylerError> {
+        if self.get_col_idx(s.name()).is_some() {
+            return Err(StylerError::DuplicateColumn(s.name().to_string()));
+        }
+        if s.len() != self.df.height() {
+            return Err(StylerError::LengthMismatch {
+                column: s.name().to_string(),
+                expected: self.df.height(),
+                got: s.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that `column` is numeric and `bins` split it into three bands, see `icon_set`.
+    pub fn check_icon_set(&self, column: &str, bins: &Bins) -> Result<(), StylerError> {
+        self.check_columns(&[column], true)?;
+        bins.check()?;
+        if bins.len() != 3 {
+            return Err(StylerError::InvalidArgument(format!(
+                "Icon sets need three bins, got {}",
+                bins.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that `by_column` is numeric and not ranked yet, see `add_rank_column`.
+    pub fn check_rank_column(&self, by_column: &str) -> Result<(), StylerError> {
+        self.check_columns(&[by_column], true)?;
+        let name = format!("{} rank", by_column);
+        if self.get_col_idx(&name).is_some() {
+            return Err(StylerError::DuplicateColumn(name));
+        }
+        Ok(())
+    }
+
+    /// Checks that `column` is numeric and can be compared with `compare`, see
+    /// `trend_arrows_series`.
+    pub fn check_trend_arrows(&self, column: &str, compare: &Series) -> Result<(), StylerError> {
+        self.check_columns(&[column], true)?;
+        check_numeric(compare)?;
+        if compare.len() != self.df.height() {
+            return Err(StylerError::LengthMismatch {
+                column: compare.name().to_string(),
+                expected: self.df.height(),
+                got: compare.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the series is a boolean mask with a value for every row.
+    pub fn check_mask(&self, mask: &Series) -> Result<(), StylerError> {
+        mask.bool()?;
+        if mask.len() != self.df.height() {
+            return Err(StylerError::LengthMismatch {
+                column: mask.name().to_string(),
+                expected: self.df.height(),
+                got: mask.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that `other` can be merged into this styler, see `merge`.
+    pub fn check_merge(&self, other: &Styler) -> Result<(), StylerError> {
+        if other.df.height() != self.df.height() {
+            return Err(StylerError::LengthMismatch {
+                column: other.column_names().join(", "),
+                expected: self.df.height(),
+                got: other.df.height(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that `old` has every column of the data, see `diff`.
+    pub fn check_diff(&self, old: &DataFrame) -> Result<(), StylerError> {
+        let available = old.get_column_names();
+        match self
+            .df
+            .get_column_names()
+            .into_iter()
+            .find(|c| !available.contains(c))
+        {
+            Some(column) => Err(StylerError::UnknownColumn {
+                column: column.to_string(),
+                available: available.iter().map(|c| c.to_string()).collect(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn column_names(&self) -> Vec<String> {
+        self.df
+            .get_column_names()
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+    }
+
+    fn icolumn(&self, column: &str) -> Option<(usize, &Series)> {
+        let col = self.get_col_idx(column)?;
+        Some((col, self.df.column(column).unwrap()))
+    }
+
+    fn get_col_idx(&self, column: &str) -> Option<usize> {
+        self.df.get_column_names().iter().position(|v| v == &column)
+    }
+
+    fn unknown_column(&self, column: &str) -> StylerError {
+        StylerError::UnknownColumn {
+            column: column.to_string(),
+            available: self.column_names(),
+        }
+    }
+}
+
+/// Name of `available` closest to `column`, if it is close enough to be a typo.
+fn closest_match<'a>(column: &str, available: &'a [String]) -> Option<&'a str> {
+    let column = column.to_lowercase();
+    available
+        .iter()
+        .map(|name| (edit_distance(&column, &name.to_lowercase()), name))
+        .filter(|(d, name)| *d <= (name.chars().count() / 3).max(1))
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, name)| name.as_str())
+}
+
+/// Edit distance of two strings, counting a swap of adjacent characters as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    let mut d = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut v = (d[i - 1][j - 1] + cost)
+                .min(d[i - 1][j] + 1)
+                .min(row[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                v = v.min(d[i - 2][j - 2] + 1);
+            }
+            row.push(v);
+        }
+        d.push(row);
+    }
+    d[a.len()][b.len()]
+}
+
+fn evaluate_expr(e: Expr, df: &DataFrame) -> Result<Series, StylerError> {
+    let result = df.clone().lazy().select(vec![e]).collect()?;
+    Ok(result.get_columns()[0].clone())
+}
+
+fn gradient_cell(color: &Color, a: f64, contrast: TextContrast) -> HashMap<String, String> {
+    background_styles(color.to_rgba(a), &color.over_white(a), contrast)
+}
+
+/// Colors of the arrows of rising, falling and unchanged values.
+const TREND_UP: &str = "#1a9850";
+const TREND_DOWN: &str = "#d73027";
+const TREND_FLAT: &str = "#878787";
+
+/// Backgrounds of the gold, silver and bronze ranks.
+const MEDALS: [&str; 3] = ["#ffd700", "#c0c0c0", "#cd7f32"];
+
+/// Ranks starting at 1 where equal values share the better rank, e.g. 1, 2, 2, 4.
+fn competition_ranks(values: &[Option<f64>], descending: bool) -> Vec<Option<u32>> {
+    let mut order = values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| Some((i, (*v)?)))
+        .collect::<Vec<_>>();
+    if descending {
+        order.sort_by(|a, b| b.1.total_cmp(&a.1));
+    } else {
+        order.sort_by(|a, b| a.1.total_cmp(&b.1));
+    }
+    let mut result = vec![None; values.len()];
+    let mut rank = 0;
+    for (position, &(i, v)) in order.iter().enumerate() {
+        if position == 0 || v != order[position - 1].1 {
+            rank = position as u32 + 1;
+        }
+        result[i] = Some(rank);
+    }
+    result
+}
+
+/// Re-keys cells from (original column, original row) to the (row, column) rendered,
+/// dropping the ones that are not rendered.
+fn rendered_cells<T: Clone>(
+    cells: &HashMap<(usize, usize), T>,
+    row_ids: &[usize],
+    col_ids: &[usize],
+) -> HashMap<(usize, usize), T> {
+    if cells.is_empty() {
+        return HashMap::new();
+    }
+    let rows = row_ids
+        .iter()
+        .enumerate()
+        .map(|(r, &id)| (id, r))
+        .collect::<HashMap<_, _>>();
+    let cols = col_ids
+        .iter()
+        .enumerate()
+        .map(|(c, &id)| (id, c))
+        .collect::<HashMap<_, _>>();
+    cells
+        .iter()
+        .filter_map(|((col, row), v)| Some(((*rows.get(row)?, *cols.get(col)?), v.clone())))
+        .collect()
+}
+
+/// Position of `v` on a diverging scale: `vmin` maps to 0, zero to 0.5 and `vmax` to 1.
+fn centered(v: f64, vmin: f64, vmax: f64) -> f64 {
+    let a = if vmin < 0.0 && vmax > 0.0 {
+        match v < 0.0 {
+            true => 0.5 - 0.5 * v / vmin,
+            false => 0.5 + 0.5 * v / vmax,
+        }
+    } else {
+        (v - vmin) / (vmax - vmin)
+    };
+    a.clamp(0.0, 1.0)
+}
+
+/// Background of a colormap color, as a tint when `opacity` is below 1.
+fn colormap_cell(color: &Color, opacity: f64, contrast: TextContrast) -> HashMap<String, String> {
+    if opacity < 1.0 {
+        background_styles(color.to_rgba(opacity), &color.over_white(opacity), contrast)
+    } else {
+        background_styles(color.to_hex(), color, contrast)
+    }
+}
+
+fn bar_cell(color: &Color, a: f64) -> HashMap<String, String> {
+    let bg = format!(
+        "linear-gradient(90deg, {} {}%, transparent {}%, transparent 100%)",
+        color.to_hex(),
+        (a * 100.0) as u32,
+        (a * 100.0) as u32
+    );
+    HashMap::from([("background".to_string(), bg)])
+}
+
+/// Text identifying a category; `None` for nulls.
+fn category_key(v: AnyValue) -> Option<String> {
+    match v {
+        AnyValue::Null => None,
+        AnyValue::Utf8(s) => Some(s.to_string()),
+        _ => Some(v.to_string()),
+    }
+}
+
+/// 64-bit FNV-1a, stable across processes and platforms unlike the std hasher.
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// `background-color` declaration; backgrounds `seen_as` dark get white text or are
+/// lightened, as `contrast` says, to keep WCAG-AA contrast (the default text color is
+/// assumed to be dark).
+fn background_styles(
+    background: String,
+    seen_as: &Color,
+    contrast: TextContrast,
+) -> HashMap<String, String> {
+    let black = Color::new(0, 0, 0);
+    match contrast {
+        TextContrast::SwitchText => {
+            let mut styles = HashMap::from([("background-color".to_string(), background)]);
+            let text = seen_as.text_color();
+            if text != black {
+                styles.insert("color".to_string(), text.to_hex());
+            }
+            styles
+        }
+        TextContrast::Threshold(t) if seen_as.relative_luminance() < t => HashMap::from([
+            ("background-color".to_string(), background),
+            ("color".to_string(), "#ffffff".to_string()),
+        ]),
+        TextContrast::AdjustBackground if contrast_ratio(seen_as, &black) < WCAG_AA => {
+            let background = seen_as.with_contrast(&black, WCAG_AA).to_hex();
+            HashMap::from([("background-color".to_string(), background)])
+        }
+        _ => HashMap::from([("background-color".to_string(), background)]),
+    }
+}
+
+/// Raw values used by the client-side sorter, and whether they compare as numbers or text.
+fn sort_column(s: &Series) -> (Vec<String>, String) {
+    let dtype = s.dtype();
+    let numeric = dtype.is_numeric() || dtype.is_temporal() || dtype == &DataType::Boolean;
+    let values = s
+        .iter()
+        .map(|v| match v {
+            AnyValue::Null => String::new(),
+            AnyValue::Utf8(s) => s.to_string(),
+            AnyValue::Boolean(b) => (b as u8).to_string(),
+            AnyValue::Date(d) => d.to_string(),
+            AnyValue::Datetime(t, _, _) => t.to_string(),
+            AnyValue::Duration(t, _) => t.to_string(),
+            AnyValue::Time(t) => t.to_string(),
+            _ => v.to_string(),
+        })
+        .collect();
+    let sort_type = if numeric { "number" } else { "text" };
+    (values, sort_type.to_string())
+}
+
+/// Maps the items in parallel with the `rayon` feature, sequentially otherwise.
+#[cfg(feature = "rayon")]
+fn par_map<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> U + Sync + Send) -> Vec<U> {
+    use rayon::prelude::*;
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn par_map<T, U>(items: &[T], f: impl Fn(&T) -> U) -> Vec<U> {
+    items.iter().map(f).collect()
+}
+
+fn format_row(s: &Series, params: &StylerParams) -> ColumnValues {
+    if let Some(ValueFormatter(f)) = params.formatters.get(s.name()) {
+        return s.iter().map(|v| f(&v)).collect::<Vec<_>>().into();
+    }
+    // Formats of the dtype and of the whole table only fill in what the column doesn't set.
+    let unit = params
+        .column_meta
+        .get(s.name())
+        .filter(|meta| meta.unit_in_values)
+        .and_then(|meta| meta.unit.as_ref())
+        .map(|unit| CellFormat::new().suffix(&format!(" {}", unit)));
+    let defaults = unit
+        .iter()
+        .chain(DtypeKind::of(s.dtype()).and_then(|kind| params.dtype_formats.get(&kind)))
+        .chain(params.default_format.as_ref())
+        .collect::<Vec<_>>();
+    let precision = params
+        .column_precision
+        .get(s.name())
+        .or(params.precision.as_ref())
+        .or(defaults.iter().find_map(|d| d.precision.as_ref()));
+    let format = defaults
+        .iter()
+        .fold(params.column_formats.get(s.name()).cloned(), |format, d| {
+            Some(format.unwrap_or_default().with_defaults(d))
+        });
+    if let Some(format) = format {
+        let values = s.iter().map(|v| format.format(&v, precision.copied()));
+        return values.collect::<Vec<_>>().into();
+    }
+    // Strings are displayed as they are, so repeated values are kept only once.
+    if let Ok(ca) = s.utf8() {
+        return ColumnValues::interned(ca.into_iter().map(|v| v.unwrap_or("null")));
+    }
+    let values = s.iter().map(|v| format_value(&v, precision));
+    values.collect::<Vec<_>>().into()
+}
+
+/// Values longer than `max_chars` characters cut with the rest in a "more" toggle.
+fn expandable(values: ColumnValues, max_chars: usize) -> ColumnValues {
+    let values = (0..values.len()).map(|row| {
+        let text = values.get(row);
+        match text.char_indices().nth(max_chars) {
+            Some((cut, _)) => format!(
+                "{}<details class=\"ps-more\"><summary>… more</summary>{}</details>",
+                &text[..cut],
+                &text[cut..]
+            ),
+            None => text.to_string(),
+        }
+    });
+    values.collect::<Vec<_>>().into()
+}
+
+/// Shares of `total` of the values, alone or after the displayed values.
+fn percent_of_total(
+    s: &Series,
+    values: ColumnValues,
+    total: f64,
+    with_value: bool,
+) -> ColumnValues {
+    let s = to_float(s);
+    let shares = s.f64().unwrap().into_iter().enumerate().map(|(row, v)| {
+        let text = values.get(row);
+        match v.map(|v| 100.0 * v / total) {
+            Some(share) if share.is_finite() && with_value => format!("{} ({:.1}%)", text, share),
+            Some(share) if share.is_finite() => format!("{:.1}%", share),
+            _ => text.to_string(),
+        }
+    });
+    shares.collect::<Vec<_>>().into()
+}
+
+/// Formats a gradient end; temporal values are converted back from their integer representation.
+fn format_bound(
+    v: f64,
+    dtype: &DataType,
+    precision: Option<&u32>,
+    format: Option<&CellFormat>,
+) -> String {
+    let display = |v: &AnyValue| match format {
+        Some(format) => format.format(v, precision.copied()),
+        None => format_value(v, precision),
+    };
+    if !dtype.is_temporal() {
+        return display(&AnyValue::Float64(v));
+    }
+    Series::new("", &[v.round() as i64])
+        .cast(&dtype.to_physical())
+        .and_then(|s| s.cast(dtype))
+        .map(|s| display(&s.get(0).unwrap()))
+        .unwrap_or_else(|_| v.to_string())
+}
+
+fn format_value(v: &AnyValue, precision: Option<&u32>) -> String {
+    match v {
+        AnyValue::Float64(f) => {
+            let Some(precision) = precision else {
+                return f.to_string();
+            };
+            format!("{:.1$}", f, *precision as usize)
+        }
+        AnyValue::Float32(f) => {
+            let Some(precision) = precision else {
+                return f.to_string();
+            };
+            format!("{:.1$}", f, *precision as usize)
+        }
+        AnyValue::Utf8(s) => s.to_string(),
+        _ => v.to_string(),
+    }
+}
+
+/// Table uid; a process-wide counter keeps it unique even on targets without
+/// an entropy source (e.g. wasm32-unknown-unknown) where hasher keys are fixed.
+fn random_hash() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    format!("{:06x}", hasher.finish() & 0xff_ffff)
+}
+
+/// Picks `n` distinct sorted row indices using a seeded SplitMix64 generator.
+fn sample_rows(height: usize, n: usize, seed: u64) -> Vec<usize> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    };
+    let mut rows = (0..height).collect::<Vec<_>>();
+    let n = n.min(height);
+    for i in 0..n {
+        let j = i + (next() % (height - i) as u64) as usize;
+        rows.swap(i, j);
+    }
+    rows.truncate(n);
+    rows.sort_unstable();
+    rows
+}
+
+/// Scales `values` (by column) to [0, 1] along `axis`; constant groups map to 0.5, like
+/// `Norm::normalize`.
+fn normalize_axis(values: &[Vec<Option<f64>>], axis: Axis) -> Vec<Vec<Option<f64>>> {
+    let range = |group: &mut dyn Iterator<Item = f64>| {
+        group.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        })
+    };
+    let scale = |v: Option<f64>, (lo, hi): (f64, f64)| {
+        v.map(|v| if hi > lo { (v - lo) / (hi - lo) } else { 0.5 })
+    };
+    let nrow = values.first().map_or(0, |c| c.len());
+    match axis {
+        Axis::Column => values
+            .iter()
+            .map(|c| {
+                let r = range(&mut c.iter().flatten().copied());
+                c.iter().map(|v| scale(*v, r)).collect()
+            })
+            .collect(),
+        Axis::Row => {
+            let ranges = (0..nrow)
+                .map(|i| range(&mut values.iter().filter_map(|c| c[i])))
+                .collect::<Vec<_>>();
+            values
+                .iter()
+                .map(|c| c.iter().zip(&ranges).map(|(v, r)| scale(*v, *r)).collect())
+                .collect()
+        }
+        Axis::Table => {
+            let r = range(&mut values.iter().flatten().flatten().copied());
+            values
+                .iter()
+                .map(|c| c.iter().map(|v| scale(*v, r)).collect())
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::colors::OutOfRange;
+
+    #[test]
+    fn test_styler() {
+        let df = DataFrame::new(vec![
+            Series::new("a", &[1, 222, 3]),
+            Series::new("b", &["fooo", "b", "c"]),
+        ])
+        .unwrap();
+
+        let styler = df.style();
+        let html = styler.render();
+        println!("{}", html);
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<div>"));
+        assert!(html.contains("</div>"));
+        assert!(html.find("fooo").unwrap() < html.find("222").unwrap());
+    }
+
+    #[test]
+    fn test_precision() {
+        let x = 1.123456789;
+        let df = DataFrame::new(vec![Series::new("a", &[x, 2.123456789, 3.123456789])]).unwrap();
+
+        let styler = df.style().set_precision(2);
+        let html = styler.render();
+        println!("{}", html);
+        assert!(html.contains(format!("{:.2}", x).as_str()));
+        assert!(!html.contains(format!("{:.3}", x).as_str()));
+    }
+
+    #[test]
+    fn test_set_labels_all() {
+        let df = DataFrame::new(vec![Series::new("a", &[0])]).unwrap();
+        let styler = df.style().set_labels(vec!["Foo".to_string()]);
+        let html = styler.render();
+        assert!(html.contains("Foo"));
+    }
+
+    #[test]
+    fn test_set_labels_map() {
+        let df = DataFrame::new(vec![Series::new("a", &[0])]).unwrap();
+        let mapping = HashMap::from([("a".to_string(), "Foo".to_string())]);
+        let styler = df.style().relabel(&mapping);
+        let html = styler.render();
+        assert!(html.contains("Foo"));
+    }
+
+    #[test]
+    fn test_apply() {
+        let df = DataFrame::new(vec![
+            Series::new("a", &[1, 222, 3]),
+            Series::new("b", &["fooo", "b", "c"]),
+        ])
+        .unwrap();
+
+        let styler = df.style().apply("a", |s| {
+            s.iter()
+                .map(|v| {
+                    if v == AnyValue::Int32(222) {
+                        vec![("background-color".to_string(), "red".to_string())]
+                    } else {
+                        vec![]
+                    }
+                })
+                .map(|v| v.into_iter().collect::<HashMap<_, _>>())
+                .collect::<Vec<_>>()
+        });
+        assert!(styler
+            .applied_styles
+            .iter()
+            .any(|v| { v.iter().any(|hm| !hm.is_empty()) }));
+    }
+
+    #[test]
+    fn test_background_gradient() {
+        let df = DataFrame::new(vec![
+            Series::new("a", &[1, 222, 3]),
+            Series::new("b", &["fooo", "b", "c"]),
+        ])
+        .unwrap();
+
+        let styler = df
+            .style()
+            .background_gradient_expr(col("a").log(2.0), &Color::new(0, 0, 0));
+        assert!(styler
+            .applied_styles
+            .iter()
+            .any(|v| { v.iter().any(|hm| !hm.is_empty()) }));
+    }
+
+    #[test]
+    fn test_to_context() {
+        let df = DataFrame::new(vec![Series::new("a", &[1.0, 2.0])]).unwrap();
+        let context = df
+            .style()
+            .relabel_column("a", "Foo")
+            .background_gradient("a", &Color::new(255, 0, 0), &None, &None)
+            .to_context();
+        assert_eq!(context.headers, vec!["Foo"]);
+        assert_eq!(context.rows[1][0].value, "2");
+        assert!(context.rows[1][0].style.contains("background-color"));
+    }
+
+    #[test]
+    fn test_spec_roundtrip() {
+        let df = DataFrame::new(vec![Series::new("a", &[1.0, 2.0, 3.0])]).unwrap();
+        let styler = df.style().set_precision(1).relabel_column("a", "Foo").bar(
+            "a",
+            &Color::new(255, 0, 0),
+            &None,
+            &Some(2.0),
+        );
+        let spec = styler.spec();
+        let json = serde_json::to_string(&spec).unwrap();
+        assert!(json.contains("\"type\":\"bar\""));
+        assert!(json.contains("#ff0000"));
+        let parsed: StyleSpec = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, spec);
+
+        let reapplied = df.style().apply_spec(&parsed);
+        assert_eq!(reapplied.spec(), spec);
+        assert_eq!(reapplied.applied_styles, styler.applied_styles);
+    }
+
+    #[test]
+    fn test_column_formatter() {
+        let df = df!("a" => &[Some(0.25), None], "b" => &[1.5, 2.5]).unwrap();
+        let html = df
+            .style()
+            .set_precision(1)
+            .set_column_formatter("a", |v| match v.extract::<f64>() {
+                Some(x) => format!("{:.0}%", x * 100.0),
+                None => "-".to_string(),
+            })
+            .render();
+        assert!(html.contains(">25%<"));
+        assert!(html.contains(">-<"));
+        assert!(html.contains(">2.5<"));
+    }
+
+    #[test]
+    fn test_column_precision() {
+        let df = DataFrame::new(vec![
+            Series::new("a", &[1.23456]),
+            Series::new("b", &[1.23456]),
+        ])
+        .unwrap();
+        let html = df
+            .style()
+            .set_precision(3)
+            .set_column_precision("b", 1)
+            .render();
+        assert!(html.contains(">1.235<"));
+        assert!(html.contains(">1.2<"));
+    }
+
+    #[test]
+    fn test_background_gradient_columns() {
+        let df = DataFrame::new(vec![
+            Series::new("a", &[1.0, 2.0, 3.0]),
+            Series::new("b", &[10.0, 20.0, 30.0]),
+        ])
+        .unwrap();
+        let red = Color::new(255, 0, 0);
+        let styler = df
+            .style()
+            .background_gradient_columns(&["a", "b"], &red, &None, &None);
+        let expected = df
+            .style()
+            .background_gradient("a", &red, &None, &None)
+            .background_gradient("b", &red, &None, &None);
+        assert_eq!(styler.applied_styles, expected.applied_styles);
+        assert_eq!(styler.spec(), expected.spec());
+    }
+
+    #[test]
+    fn test_background_gradient_shared() {
+        let df = DataFrame::new(vec![
+            Series::new("a", &[0.0, 5.0]),
+            Series::new("b", &[5.0, 10.0]),
+        ])
+        .unwrap();
+        let cmap = ColorMap::named("viridis").unwrap();
+        let styler = df
+            .style()
+            .background_gradient_shared(&["a", "b"], &cmap, &None, &None);
+        assert_eq!(styler.applied_styles[0][1], styler.applied_styles[1][0]);
+        let expected = df.style().background_colormap_columns(
+            &["a", "b"],
+            &cmap,
+            &Norm::Linear {
+                vmin: Some(0.0),
+                vmax: Some(10.0),
+            },
+        );
+        assert_eq!(styler.applied_styles, expected.applied_styles);
+        let replayed = df.style().apply_spec(&styler.spec());
+        assert_eq!(replayed.applied_styles, styler.applied_styles);
+
+        let clipped = df
+            .style()
+            .background_gradient_shared(&["a", "b"], &cmap, &Some(5.0), &None);
+        assert_eq!(clipped.applied_styles[0][1], clipped.applied_styles[0][0]);
+        assert!(df
+            .style()
+            .try_background_gradient_shared(&["a", "c"], &cmap, &None, &None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_interned_strings() {
+        let df = DataFrame::new(vec![Series::new("a", &["x", "y", "x", "x"])]).unwrap();
+        let values = format_row(df.column("a").unwrap(), &StylerParams::default());
+        match values {
+            ColumnValues::Interned { values, index } => {
+                assert_eq!(values, vec!["x", "y"]);
+                assert_eq!(index, vec![0, 1, 0, 0]);
+            }
+            ColumnValues::Plain(_) => panic!("string column is not interned"),
+        }
+    }
+
+    #[test]
+    fn test_from_shared() {
+        let df = Arc::new(DataFrame::new(vec![Series::new("a", &[1, 2])]).unwrap());
+        let styler = Styler::from_shared(df.clone()).set_precision(1);
+        assert!(std::ptr::eq(styler.clone().data(), df.as_ref()));
+        assert!(styler.render().contains(">2<"));
+    }
+
+    #[test]
+    fn test_new_shares_buffers() {
+        let df = DataFrame::new(vec![Series::new("a", &[1, 2])]).unwrap();
+        let values = |df: &DataFrame| {
+            df.column("a")
+                .unwrap()
+                .i32()
+                .unwrap()
+                .cont_slice()
+                .unwrap()
+                .as_ptr()
+        };
+        assert_eq!(values(Styler::new(&df).data()), values(&df));
+    }
+
+    #[test]
+    fn test_column_format() {
+        let df = DataFrame::new(vec![Series::new("price", &[1234.5, -0.84])]).unwrap();
+        let styler = df
+            .style()
+            .set_precision(1)
+            .set_column_format("price", CellFormat::new().currency("$").thousands(","));
+        let html = styler.clone().render();
+        assert!(html.contains(">$1,234.5<"));
+        assert!(html.contains(">-$0.8<"));
+        let restored = Styler::new(&df).apply_spec(&styler.spec());
+        assert!(restored.render().contains(">$1,234.5<"));
+    }
+
+    #[test]
+    fn test_highlight() {
+        let df = DataFrame::new(vec![Series::new("a", &[1, 5, 10])]).unwrap();
+        let styler = df
+            .style()
+            .highlight("a", &Condition::Ge(5.0), &Color::new(255, 255, 0));
+        let highlighted = styler.applied_styles[0]
+            .iter()
+            .map(|hm| hm.contains_key("background-color"))
+            .collect::<Vec<_>>();
+        assert_eq!(highlighted, vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_from_spec() {
+        let df = DataFrame::new(vec![Series::new("price", &[1.0, 2.0])]).unwrap();
+        let spec =
+            "labels: {price: Price}\noperations:\n  - {type: bar, column: price, color: red}";
+        let styler = Styler::from_spec(&df, spec).unwrap();
+        assert_eq!(styler.operations.len(), 1);
+        assert!(styler.render().contains("Price"));
+        assert!(Styler::from_spec(&df, "{\"precision\": \"two\"}").is_err());
+    }
+
+    #[test]
+    fn test_from_lazy() {
+        let lf = df!("a" => &[1, 2], "b" => &[3, 4]).unwrap().lazy();
+        let styler = Styler::from_lazy(lf.clone().filter(col("a").gt(lit(1))), None).unwrap();
+        assert_eq!(styler.data().height(), 1);
+        let styler = Styler::from_lazy(lf, Some(&["b"])).unwrap();
+        assert_eq!(styler.column_names(), vec!["b"]);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_from_csv() {
+        let path = std::env::temp_dir().join("polars_styler_test_from_csv.csv");
+        std::fs::write(&path, "a,b\n1,x\n2,y\n").unwrap();
+        let styler = Styler::from_csv(&path).unwrap();
+        assert_eq!(styler.column_names(), vec!["a", "b"]);
+        assert!(Styler::from_csv(path.with_extension("missing")).is_err());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_from_parquet() {
+        let path = std::env::temp_dir().join("polars_styler_test_from_parquet.parquet");
+        let mut df = df!("a" => &[1, 2], "b" => &["x", "y"]).unwrap();
+        ParquetWriter::new(std::fs::File::create(&path).unwrap())
+            .finish(&mut df)
+            .unwrap();
+        let styler = Styler::from_parquet(&path).unwrap();
+        assert_eq!(styler.column_names(), vec!["a", "b"]);
+        assert!(Styler::from_parquet(path.with_extension("missing")).is_err());
+    }
+
+    #[test]
+    fn test_max_rows() {
+        let df = DataFrame::new(vec![Series::new("a", (0..100).collect::<Vec<i32>>())]).unwrap();
+        let html = df
+            .style()
+            .highlight("a", &Condition::Ge(99.0), &Color::new(255, 0, 0))
+            .set_max_rows(5)
+            .render();
+        assert!(html.contains(">2<"));
+        assert!(!html.contains(">3<"));
+        assert!(!html.contains(">97<"));
+        assert!(html.contains(">98<"));
+        assert!(html.contains("… 95 more rows …"));
+        assert!(html.contains("_row99_col0 {background-color: #ff0000}"));
+
+        let html = df.style().set_max_rows(100).render();
+        assert!(!html.contains("more rows"));
+    }
+
+    #[test]
+    fn test_max_columns() {
+        let columns = (0..10)
+            .map(|i| Series::new(&format!("c{}", i), &[i]))
+            .collect::<Vec<_>>();
+        let df = DataFrame::new(columns).unwrap();
+        let html = df
+            .style()
+            .relabel_column("c9", "Last")
+            .highlight("c9", &Condition::Gt(0.0), &Color::new(255, 0, 0))
+            .set_max_columns(4)
+            .render();
+        assert!(html.contains("<th scope=\"col\">c1</th><th class=\"ellipsis\">…</th><th scope=\"col\">c8</th><th scope=\"col\">Last</th>"));
+        assert!(!html.contains(">c2<"));
+        assert!(html.contains("_row0_col9 {background-color: #ff0000}"));
+    }
+
+    #[test]
+    fn test_render_head_tail() {
+        let df = DataFrame::new(vec![Series::new("a", &[1, 2, 3, 4])]).unwrap();
+        let styler = df
+            .style()
+            .background_gradient("a", &Color::new(255, 0, 0), &None, &None);
+
+        let html = styler.clone().render_head(2);
+        assert!(html.contains(">2<") && !html.contains(">3<"));
+        assert!(html.contains("rgba(255, 0, 0, 0.3333"));
+
+        let html = styler.clone().render_tail(2);
+        assert!(html.contains(">3<") && !html.contains(">2<"));
+        assert!(html.contains("_row3_col0 {background-color: rgba(255, 0, 0, 1)}"));
+
+        let html = styler.set_normalize_on_selection(true).render_head(2);
+        assert!(html.contains("_row1_col0 {background-color: rgba(255, 0, 0, 1)}"));
+    }
+
+    #[test]
+    fn test_sample_rows() {
+        let rows = sample_rows(100, 10, 42);
+        assert_eq!(rows.len(), 10);
+        assert!(rows.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(rows, sample_rows(100, 10, 42));
+        assert_ne!(rows, sample_rows(100, 10, 43));
+        assert_eq!(sample_rows(3, 10, 0), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_sortable() {
+        let df = DataFrame::new(vec![
+            Series::new("a", &[2.5, 10.0]),
+            Series::new("b", &["x", "y"]),
+        ])
+        .unwrap();
+        let html = df.style().set_precision(0).set_sortable(true).render();
+        assert!(html.contains("<th scope=\"col\" data-sort-type=\"number\">a</th>"));
+        assert!(html.contains("<th scope=\"col\" data-sort-type=\"text\">b</th>"));
+        assert!(html.contains("data-sort-value=\"2.5\">2</td>"));
+        assert!(html.contains("data-sort-value=\"x\">x</td>"));
+    }
+
+    #[test]
+    fn test_random_hash_unique() {
+        let a = random_hash();
+        let b = random_hash();
+        assert_eq!(a.len(), 6);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_render_body_stable_ids() {
+        let df = df!("a" => &[1, 2]).unwrap();
+        let styler =
+            df.style()
+                .set_uid("page")
+                .highlight("a", &Condition::Gt(1.0), &Color::new(255, 0, 0));
+        assert!(styler
+            .clone()
+            .render()
+            .contains("<tbody id=\"T_page_body\">"));
+        let body = styler.clone().render_body();
+        assert!(body.starts_with("<tbody id=\"T_page_body\"><tr id=\"T_page_row0\">"));
+        assert!(body.contains("<td id=\"T_page_row1_col0\">2</td>"));
+        assert!(!body.contains("<style>"));
+        assert!(!styler.clone().render_rows_only().contains("tbody"));
+        assert_eq!(
+            styler.render_css(),
+            "#T_page_row1_col0 {background-color: #ff0000}"
+        );
+    }
+
+    #[test]
+    fn test_render_web_component() {
+        let df = df!("a" => &[1, 2]).unwrap();
+        let html = df
+            .style()
+            .set_uid("wc")
+            .set_search(SearchMode::Highlight)
+            .render_web_component();
+        assert!(html
+            .starts_with("<polars-table id=\"T_wc_host\">\n  <template shadowrootmode=\"open\">"));
+        assert!(html.contains("customElements.define(\"polars-table\""));
+        let style = html.find("<style>").unwrap();
+        assert!(style < html.find("</template>").unwrap());
+        assert!(html.contains(
+            "(document.getElementById(\"T_wc_host\").shadowRoot.getElementById(\"T_wc\"), "
+        ));
+    }
+
+    #[test]
+    fn test_print_options() {
+        let df = df!("a" => &[1, 2]).unwrap();
+        let styler =
+            df.style()
+                .set_uid("p")
+                .highlight("a", &Condition::Gt(1.0), &Color::new(255, 0, 0));
+        assert!(!styler.clone().render().contains("@media print"));
+        let html = styler
+            .set_print_options(PrintOptions {
+                avoid_row_split: false,
+                ..Default::default()
+            })
+            .render();
+        assert!(html.contains("@media print {\n    #T_p thead {display: table-header-group}"));
+        assert!(!html.contains("break-inside"));
+        assert!(html.contains("#T_p_row1_col0 {font-weight: bold; text-decoration: underline}"));
+        assert!(!html.contains("#T_p_row0_col0 {font-weight"));
+        assert!(!html.contains("--ps-highlight"));
+    }
+
+    #[test]
+    fn test_direction_rtl() {
+        let df = df!("a" => &[1], "b" => &[2]).unwrap();
+        let styler = df.style().set_uid("rtl").set_direction(Direction::Rtl);
+        let html = styler.clone().render();
+        assert!(html.starts_with("<div dir=\"rtl\">"));
+        assert!(html.contains("id=\"T_rtl\" class=\"\" dir=\"rtl\""));
+        assert!(styler.render_latex().starts_with("\\begin{tabular}{rr}"));
+        assert!(!df.style().render().contains("dir="));
+    }
+
+    #[test]
+    fn test_accessibility() {
+        let df = df!("name" => &["a", "b"], "v" => &[0, 1]).unwrap();
+        let html = df
+            .style()
+            .set_uid("a11y")
+            .set_caption("Values & names")
+            .set_row_header("name")
+            .background_gradient("v", &Color::new(0, 0, 0), &None, &None)
+            .render();
+        assert!(html
+            .starts_with("<div role=\"region\" aria-label=\"Values &amp; names\" tabindex=\"0\">"));
+        assert!(html.contains("<caption>Values &amp; names</caption>"));
+        assert!(html.contains("<th id=\"T_a11y_row1_col0\" scope=\"row\">b</th>"));
+        assert!(html.contains("<th scope=\"col\">v</th>"));
+        // white text only on the dark end of the gradient
+        assert!(html.contains("color: #ffffff"));
+        assert!(!html.contains("#T_a11y_row0_col1 {color"));
+    }
+
+    #[test]
+    fn test_column_style() {
+        let df = df!("a" => &[1, 2, 3], "b" => &[4, 5, 6], "c" => &[7, 8, 9]).unwrap();
+        let css = df
+            .style()
+            .set_uid("cs")
+            .set_column_style("c", "text-align", "right")
+            .highlight("a", &Condition::Gt(2.0), &Color::new(255, 0, 0))
+            .set_max_columns(2)
+            .render_css();
+        assert_eq!(
+            css,
+            "#T_cs > tbody > tr:not(.ellipsis):not(.ps-detail) > :nth-child(3) {text-align: right}\n\
+             #T_cs_row2_col0 {background-color: #ff0000}"
+        );
+    }
+
+    #[test]
+    fn test_legend() {
+        let df = df!("a" => &[1.0, 2.0, 4.0], "b" => &[1, 2, 3]).unwrap();
+        let styler = df
+            .style()
+            .set_uid("lg")
+            .relabel_column("a", "Alpha")
+            .set_precision(1)
+            .background_gradient("a", &Color::new(255, 0, 0), &None, &Some(3.0))
+            .bar("b", &Color::new(0, 0, 255), &None, &None);
+        assert!(!styler.clone().render().contains("ps-legend"));
+        let html = styler.with_legend().render();
+        assert!(html.contains(
+            "<span class=\"ps-legend-label\">Alpha</span> <span>1.0</span>\
+             <span class=\"ps-legend-bar\" id=\"T_lg_legend0\"></span><span>3.0</span>"
+        ));
+        assert!(html.contains(
+            "#T_lg_legend0 {background: linear-gradient(to right, rgba(255, 0, 0, 0), rgba(255, 0, 0, 1))}"
+        ));
+        assert!(!html.contains("T_lg_legend1"));
+
+        let df = df!("<b> & c" => &[1.0, 2.0]).unwrap();
+        let html = df
+            .style()
+            .background_gradient("<b> & c", &Color::new(255, 0, 0), &None, &None)
+            .with_legend()
+            .render();
+        assert!(html.contains("<span class=\"ps-legend-label\">&lt;b&gt; &amp; c</span>"));
+
+        let df = df!("a" => &[1.0, 2.0]).unwrap();
+        let format = CellFormat {
+            suffix: Some(" <i>".to_string()),
+            ..Default::default()
+        };
+        let html = df
+            .style()
+            .set_column_format("a", format)
+            .background_gradient("a", &Color::new(255, 0, 0), &None, &None)
+            .with_legend()
+            .render();
+        assert!(html.contains("<span>1 &lt;i&gt;</span><span class=\"ps-legend-bar\" id=\"T_"));
+        assert!(html.contains("></span><span>2 &lt;i&gt;</span></div>"));
+    }
+
+    #[test]
+    fn test_background_three_point() {
+        let df = df!("a" => &[0, 1, 2, 10, 6]).unwrap();
+        let (red, white, blue) = (
+            Color::new(255, 0, 0),
+            Color::new(255, 255, 255),
+            Color::new(0, 0, 255),
+        );
+        let colors = |styler: Styler| {
+            styler.applied_styles[0]
+                .iter()
+                .map(|hm| hm["background-color"].clone())
+                .collect::<Vec<_>>()
+        };
+        let styler = df
+            .style()
+            .background_three_point("a", &red, &white, &blue, &None);
+        assert_eq!(
+            colors(styler),
+            ["#ff0000", "#ff8080", "#ffffff", "#0000ff", "#8080ff"]
+        );
+        let styler = df
+            .style()
+            .background_three_point("a", &red, &white, &blue, &Some(0.0));
+        assert_eq!(colors(styler)[0], "#ffffff");
+
+        let df = df!("a" => &[1, 2], "s" => &["x", "y"]).unwrap();
+        let result = df
+            .style()
+            .try_background_three_point("b", &red, &white, &blue, &None);
+        assert!(matches!(result, Err(StylerError::UnknownColumn { .. })));
+        let result = df
+            .style()
+            .try_background_three_point("s", &red, &white, &blue, &None);
+        assert!(matches!(result, Err(StylerError::NotNumeric { .. })));
+    }
+
+    #[test]
+    fn test_normalize_axis() {
+        let values = vec![vec![Some(0.0), Some(2.0)], vec![Some(4.0), None]];
+        assert_eq!(
+            normalize_axis(&values, Axis::Column),
+            vec![vec![Some(0.0), Some(1.0)], vec![Some(0.5), None]]
+        );
+        assert_eq!(
+            normalize_axis(&values, Axis::Row),
+            vec![vec![Some(0.0), Some(0.5)], vec![Some(1.0), None]]
+        );
+        assert_eq!(
+            normalize_axis(&values, Axis::Table),
+            vec![vec![Some(0.0), Some(0.5)], vec![Some(1.0), None]]
+        );
+        let constant = vec![vec![Some(3.0), Some(3.0)]];
+        assert_eq!(
+            normalize_axis(&constant, Axis::Column),
+            vec![vec![Some(0.5), Some(0.5)]]
+        );
+
+        let df = df!("a" => &[1, 2], "b" => &["x", "y"]).unwrap();
+        let blue = Color::new(0, 0, 255);
+        assert!(matches!(
+            df.style()
+                .try_background_gradient_axis(&["a", "c"], &blue, Axis::Table),
+            Err(StylerError::UnknownColumn { .. })
+        ));
+        assert!(matches!(
+            df.style()
+                .try_background_gradient_axis(&["a", "b"], &blue, Axis::Table),
+            Err(StylerError::NotNumeric { .. })
+        ));
+    }
+
+    #[test]
+    fn test_background_by_category_auto() {
+        let df = df!("a" => &[Some("x"), Some("y"), None, Some("x"), Some("z")]).unwrap();
+        let palette = [
+            Color::new(255, 0, 0),
+            Color::new(0, 255, 0),
+            Color::new(0, 0, 255),
+        ];
+        let styler = df.style().background_by_category_auto("a", &palette);
+        let colors = styler.applied_styles[0]
+            .iter()
+            .map(|hm| hm.get("background-color").cloned().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(colors[0], colors[3]);
+        assert_eq!(colors[2], "");
+        let expected = palette[(fnv1a("x") % 3) as usize].to_hex();
+        assert_eq!(colors[0], expected);
+        // The color of a value doesn't depend on the other values.
+        let df = df!("a" => &["x"]).unwrap();
+        let styler = df.style().background_by_category_auto("a", &palette);
+        assert_eq!(styler.applied_styles[0][0]["background-color"], expected);
+        assert_eq!(fnv1a("a"), 0xaf63dc4c8601ec8c);
+        assert!(matches!(
+            df.style().try_background_by_category_auto("a", &[]),
+            Err(StylerError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            df.style().try_background_by_category_auto("b", &palette),
+            Err(StylerError::UnknownColumn { .. })
+        ));
+    }
+
+    #[test]
+    fn test_background_bins() {
+        let df = df!("a" => &[Some(0.0), Some(1.0), Some(5.0), Some(10.0), None]).unwrap();
+        let palette = [Color::new(255, 0, 0), Color::new(0, 255, 0)];
+        let colors = |styler: Styler| {
+            styler.applied_styles[0]
+                .iter()
+                .map(|hm| hm.get("background-color").cloned().unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+        let styler = df.style().background_bins("a", &Bins::Count(2), &palette);
+        assert_eq!(
+            colors(styler),
+            ["#ff0000", "#ff0000", "#00ff00", "#00ff00", ""]
+        );
+        let styler = df
+            .style()
+            .background_bins("a", &Bins::Edges(vec![1.0]), &palette);
+        assert_eq!(
+            colors(styler),
+            ["#ff0000", "#00ff00", "#00ff00", "#00ff00", ""]
+        );
+        for bins in [Bins::Count(0), Bins::Count(3), Bins::Edges(vec![2.0, 1.0])] {
+            assert!(matches!(
+                df.style().try_background_bins("a", &bins, &palette),
+                Err(StylerError::InvalidArgument(_))
+            ));
+        }
+        assert!(matches!(
+            df.style()
+                .try_background_bins("b", &Bins::Count(2), &palette),
+            Err(StylerError::UnknownColumn { .. })
+        ));
+    }
+
+    #[test]
+    fn test_describe() {
+        let df = df!("a" => &[1, 2], "b" => &[3.0, 4.0]).unwrap();
+        let description = df
+            .style()
+            .set_precision(1)
+            .relabel_column("a", "Alpha")
+            .background_gradient("b", &Color::new(255, 0, 0), &None, &None)
+            .background_gradient_axis(&["a", "b"], &Color::new(0, 0, 255), Axis::Table)
+            .describe();
+        assert_eq!(
+            description,
+            "Styler: 2 rows x 2 columns (a, b)\n  precision: 1\n  labels: a -> Alpha\n  \
+             operations:\n    background_gradient on b\n    background_gradient_axis on a, b\n  \
+             styled cells: 4"
+        );
+    }
+
+    #[test]
+    fn test_hide_columns_and_table_styles() {
+        let df = df!("a" => &[1, 2], "b" => &[3, 4], "c" => &[5, 6]).unwrap();
+        let styler = df
+            .style()
+            .set_uid("hide")
+            .hide_columns(&["b"])
+            .set_table_styles(vec![
+                TableStyle::new("th, td", "padding: 2px;"),
+                TableStyle::new("", "border-collapse: collapse"),
+            ]);
+        let spec = styler.spec();
+        assert_eq!(StyleSpec::parse(&spec.to_yaml()).unwrap(), spec);
+        let html = styler.render();
+        assert!(html.contains(">a<") && html.contains(">c<"));
+        assert!(!html.contains(">b<") && !html.contains(">3<"));
+        assert!(html.contains("id=\"T_hide_row0_col2\""));
+        assert!(html.contains("#T_hide th, #T_hide td {padding: 2px}"));
+        assert!(html.contains("#T_hide {border-collapse: collapse}"));
+    }
+
+    #[test]
+    fn test_gradient_opacity() {
+        let df = df!("a" => &[0, 5, 10]).unwrap();
+        let red = Color::new(255, 0, 0);
+        let styler = df
+            .style()
+            .set_gradient_opacity(0.4)
+            .background_gradient("a", &red, &None, &None)
+            .background_colormap("a", &ColorMap::named("Greys").unwrap(), &Norm::linear());
+        let cell = &styler.applied_styles[0][2];
+        assert_eq!(cell["background-color"], "rgba(0, 0, 0, 0.4)");
+        assert!(!cell.contains_key("color"));
+        let styler = df
+            .style()
+            .apply_spec(&styler.spec())
+            .background_gradient("a", &red, &None, &None);
+        assert_eq!(
+            styler.applied_styles[0][2]["background-color"],
+            "rgba(255, 0, 0, 0.4)"
+        );
+    }
 
-        let mut cell_styles: HashMap<(usize, usize), HashMap<String, String>> = HashMap::new();
-        for (c, vec) in self.applied_styles.iter().enumerate() {
-            for (r, map) in vec.iter().enumerate() {
-                if map.is_empty() {
-                    continue;
-                }
-                cell_styles.insert((r, c), map.clone());
-            }
-        }
+    #[test]
+    fn test_text_contrast() {
+        let df = df!("a" => &[0, 5, 10]).unwrap();
+        let cmap = ColorMap::named("Greys").unwrap();
+        let styler = df
+            .style()
+            .set_text_contrast(TextContrast::AdjustBackground)
+            .background_colormap("a", &cmap, &Norm::linear());
+        let cell = &styler.applied_styles[0][2];
+        assert!(!cell.contains_key("color"));
+        let background = Color::try_from(cell["background-color"].as_str()).unwrap();
+        assert!(contrast_ratio(&background, &Color::new(0, 0, 0)) >= WCAG_AA);
+        assert_eq!(styler.applied_styles[0][0]["background-color"], "#ffffff");
+        let spec = styler.spec();
+        assert_eq!(spec.text_contrast, TextContrast::AdjustBackground);
 
-        let column_labels = self
-            .column_names()
-            .iter()
-            .map(|col| {
-                let col = col.to_owned();
-                self.labels.get(&col).unwrap_or(&col).to_owned()
-            })
-            .collect::<Vec<String>>();
+        let styler = df
+            .style()
+            .set_text_contrast(TextContrast::Off)
+            .background_colormap("a", &cmap, &Norm::linear());
+        assert_eq!(styler.applied_styles[0][2].len(), 1);
 
-        let renderer = Renderer {
-            column_labels,
-            cell_values: data,
-            cell_styles,
-            hash: random_hash(),
-            classes: self.params.table_classes.unwrap_or_default(),
-        };
+        let styler = df
+            .style()
+            .set_text_contrast(TextContrast::Threshold(0.9))
+            .background_colormap("a", &cmap, &Norm::linear());
+        assert!(!styler.applied_styles[0][0].contains_key("color"));
+        assert_eq!(styler.applied_styles[0][1]["color"], "#ffffff");
+    }
 
-        renderer.render()
+    #[test]
+    fn test_bar_colormap() {
+        let df = df!("a" => &[0, 5, 10]).unwrap();
+        let cmap = ColorMap::named("viridis").unwrap();
+        let styler = df.style().bar_colormap("a", &cmap, &None, &None);
+        assert!(styler.applied_styles[0][0]["background"]
+            .starts_with("linear-gradient(90deg, #440154 0%"));
+        assert!(styler.applied_styles[0][2]["background"]
+            .starts_with("linear-gradient(90deg, #fde725 100%"));
+        let replayed = df.style().apply_spec(&styler.spec());
+        assert_eq!(replayed.applied_styles, styler.applied_styles);
     }
 
-    pub fn column_names(&self) -> Vec<String> {
-        self.df
-            .get_column_names()
+    #[test]
+    fn test_background_colormap() {
+        let df = df!("a" => &[0, 5, 10]).unwrap();
+        let cmap = ColorMap::named("Greys").unwrap();
+        let styler = df
+            .style()
+            .set_uid("cm")
+            .background_colormap("a", &cmap, &Norm::linear())
+            .with_legend();
+        assert_eq!(styler.applied_styles[0][2]["background-color"], "#000000");
+        assert_eq!(styler.applied_styles[0][2]["color"], "#ffffff");
+        let spec = styler.spec();
+        assert_eq!(StyleSpec::parse(&spec.to_yaml()).unwrap(), spec);
+        let html = styler.render();
+        assert!(html.contains("linear-gradient(to right, #ffffff 0%, #f0f0f0 12.5%"));
+    }
+
+    #[test]
+    fn test_text_gradient() {
+        let df = df!("a" => &[1, 2, 3]).unwrap();
+        let styler = df
+            .style()
+            .text_gradient("a", &Color::new(0, 0, 0), &Norm::Rank)
+            .bar_norm(
+                "a",
+                &Color::new(0, 0, 0),
+                &Norm::custom(|s| vec![Some(1.0); s.len()]),
+            );
+        let text = styler.applied_styles[0]
             .iter()
-            .map(|v| v.to_string())
-            .collect::<Vec<_>>()
+            .map(|hm| hm["color"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(text, ["#b3b3b3", "#595959", "#000000"]);
+        assert!(styler.applied_styles[0][0]["background"].contains("#000000 100%"));
+        // custom norms can't be saved
+        assert_eq!(styler.operations.len(), 1);
     }
 
-    fn icolumn(&self, column: &str) -> Option<(usize, &Series)> {
-        let col = self.get_col_idx(column)?;
-        Some((col, self.df.column(column).unwrap()))
+    #[test]
+    fn test_temporal_gradient() {
+        let dates = Series::new("d", &[0i32, 365, 730])
+            .cast(&DataType::Date)
+            .unwrap();
+        let df = DataFrame::new(vec![dates]).unwrap();
+        let html = df
+            .style()
+            .set_uid("td")
+            .background_gradient("d", &Color::new(255, 0, 0), &None, &None)
+            .with_legend()
+            .render();
+        assert!(html.contains("#T_td_row1_col0 {background-color: rgba(255, 0, 0, 0.5)}"));
+        assert!(html.contains("<span>1970-01-01</span>"));
+        assert!(html.contains("<span>1972-01-01</span>"));
     }
 
-    fn get_col_idx(&self, column: &str) -> Option<usize> {
-        self.df.get_column_names().iter().position(|v| v == &column)
+    #[test]
+    fn test_try_background_gradient() {
+        let df = df!("a" => &[None::<i32>, None], "b" => &["x", "y"]).unwrap();
+        let red = Color::new(255, 0, 0);
+        let styler = df
+            .style()
+            .try_background_gradient("a", &red, &None, &None)
+            .unwrap();
+        assert!(styler.applied_styles[0].iter().all(|hm| hm.is_empty()));
+        let result = styler.try_bar("b", &red, &None, &None);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Column b of type str is not numeric"
+        );
+        assert!(matches!(
+            df.style().try_background_gradient("c", &red, &None, &None),
+            Err(StylerError::UnknownColumn { .. })
+        ));
     }
-}
 
-fn evaluate_expr(e: Expr, df: &DataFrame) -> Series {
-    // LazyF
-    df.clone()
-        .lazy()
-        .select(vec![e])
-        .collect()
-        .unwrap()
-        .get_columns()
-        .iter()
-        .next()
-        .unwrap()
-        .clone()
-}
+    #[test]
+    fn test_styler_errors() {
+        let df = df!("a" => &[1, 2], "b" => &["x", "y"]).unwrap();
+        let red = Color::new(255, 0, 0);
+        assert!(matches!(
+            df.style().set_precision(2).try_set_precision(3),
+            Err(StylerError::AlreadySet("precision"))
+        ));
+        assert!(matches!(
+            df.style().try_highlight("c", &Condition::IsNull, &red),
+            Err(StylerError::UnknownColumn { .. })
+        ));
+        let short = Series::new("a", &[1, 2, 3]);
+        assert_eq!(
+            df.style()
+                .try_background_gradient_series(&short, &red)
+                .err()
+                .unwrap()
+                .to_string(),
+            "Series a has 3 values but the DataFrame has 2 rows"
+        );
+        assert!(matches!(
+            DataFrame::default().style().try_render(),
+            Err(StylerError::NoColumns)
+        ));
+    }
 
-fn format_row(s: &Series, params: &StylerParams) -> Vec<String> {
-    s.iter().map(|v| format_value(&v, params)).collect()
-}
+    #[test]
+    fn test_unknown_column_suggestion() {
+        let df = df!("price" => &[1.0], "quantity" => &[2]).unwrap();
+        let err = df
+            .style()
+            .try_highlight("Pirce", &Condition::IsNull, &Color::new(255, 0, 0))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Unknown column `Pirce`, did you mean `price`? Available columns: price, quantity"
+        );
+        assert_eq!(closest_match("total", &df.style().column_names()), None);
+    }
 
-fn format_value(v: &AnyValue, params: &StylerParams) -> String {
-    match v {
-        AnyValue::Float64(f) => {
-            let Some(precision) = &params.precision else {
-                return f.to_string();
-            };
-            format!("{:.1$}", f, *precision as usize)
-        }
-        AnyValue::Float32(f) => {
-            let Some(precision) = &params.precision else {
-                return f.to_string();
-            };
-            format!("{:.1$}", f, *precision as usize)
+    #[test]
+    fn test_merge() {
+        let df = df!("a" => &[1, 2], "b" => &[3, 4]).unwrap();
+        let (red, blue) = (Color::new(255, 0, 0), Color::new(0, 0, 255));
+        let theme = df.style().set_precision(2).set_caption("Theme").highlight(
+            "a",
+            &Condition::Between(0.0, 10.0),
+            &red,
+        );
+        let report =
+            df.style()
+                .set_precision(1)
+                .highlight("a", &Condition::Between(0.0, 1.0), &blue);
+        let merged = theme.merge(&report);
+        assert_eq!(merged.precision(), Some(1));
+        assert_eq!(merged.params.caption.as_deref(), Some("Theme"));
+        assert_eq!(merged.applied_styles[0][0]["background-color"], "#0000ff");
+        assert_eq!(merged.applied_styles[0][1]["background-color"], "#ff0000");
+        assert_eq!(merged.spec().operations.len(), 2);
+        assert!(matches!(
+            df.style().try_merge(&df.head(Some(1)).style()),
+            Err(StylerError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_pipe() {
+        fn house_style(s: Styler) -> Styler {
+            s.set_precision(2).set_caption("ACME")
         }
-        AnyValue::Utf8(s) => s.to_string(),
-        _ => v.to_string(),
+        let df = df!("a" => &[1.0, 2.0]).unwrap();
+        let styler = df
+            .style()
+            .pipe(house_style)
+            .pipe(|s| s.background_gradient("a", &Color::new(255, 0, 0), &None, &None));
+        assert_eq!(styler.precision(), Some(2));
+        assert_eq!(styler.spec().operations.len(), 1);
     }
-}
 
-fn random_hash() -> String {
-    let mut rng = rand::thread_rng();
-    let max_val: u32 = 16_u32.pow(6);
-    format!("{:x}", rng.gen_range(0..max_val))
-}
+    #[test]
+    fn test_diff() {
+        let old = df!("a" => &[Some(1), Some(2), None], "b" => &["x", "y", "z"]).unwrap();
+        let new =
+            df!("a" => &[Some(1), None, Some(3), Some(4)], "b" => &["x", "w", "z", "v"]).unwrap();
+        let style = DiffStyle::default();
+        let styler = new.style().diff(&old);
+        let background =
+            |c: usize, r: usize| styler.applied_styles[c][r].get("background-color").cloned();
+        assert_eq!(background(0, 0), None);
+        assert_eq!(background(0, 1), Some(style.removed.to_hex()));
+        assert_eq!(background(0, 2), Some(style.added.to_hex()));
+        assert_eq!(background(0, 3), Some(style.added.to_hex()));
+        assert_eq!(background(1, 1), Some(style.modified.to_hex()));
+        let html = styler.set_uid("d").render();
+        assert!(html.contains("title=\"y → w\""));
+        assert!(matches!(
+            new.style().try_diff_with(&old.drop("b").unwrap(), &style),
+            Err(StylerError::UnknownColumn { .. })
+        ));
+    }
 
-fn normalize_series(s: &Series, vmin: &Option<f64>, vmax: &Option<f64>) -> Series {
-    let mut s = s.cast(&DataType::Float64).unwrap();
-    if let Some(vmin) = vmin {
-        s = s.clip_min(AnyValue::Float64(*vmin)).unwrap();
+    #[test]
+    fn test_heatmap_symmetric() {
+        let df = df!(
+            "name" => &["x", "y"],
+            "x" => &[1.0, -0.5],
+            "y" => &[-0.5, 1.0]
+        )
+        .unwrap();
+        let cmap = ColorMap::named("RdBu").unwrap();
+        let styler = df.style().heatmap_symmetric(&cmap, -1.0, 1.0, true);
+        assert_eq!(styler.applied_styles[0][0], HashMap::new());
+        assert_eq!(styler.applied_styles[1][0]["visibility"], "hidden");
+        assert_eq!(
+            styler.applied_styles[1][1]["background-color"],
+            cmap.get(0.25).unwrap().to_hex()
+        );
+        assert_eq!(centered(0.0, -1.0, 3.0), 0.5);
+        assert!(styler.render().contains("-0.50"));
     }
-    if let Some(vmax) = vmax {
-        s = s.clip_max(AnyValue::Float64(*vmax)).unwrap();
+
+    #[test]
+    fn test_incomplete_colormap() {
+        let df = df!("a" => &[0.0, 5.0, 10.0], "b" => &[1.0, 2.0, 3.0]).unwrap();
+        let cmap = ColorMap::builder()
+            .point(0.25, Color::new(255, 0, 0))
+            .point(0.75, Color::new(0, 0, 255))
+            .out_of_range(OutOfRange::Error)
+            .build()
+            .unwrap();
+        let invalid =
+            |r: Result<Styler, StylerError>| matches!(r, Err(StylerError::InvalidArgument(_)));
+        assert!(invalid(df.style().try_background_colormap(
+            "a",
+            &cmap,
+            &Norm::linear()
+        )));
+        assert!(invalid(df.style().try_background_colormap_columns(
+            &["a", "b"],
+            &cmap,
+            &Norm::linear()
+        )));
+        assert!(invalid(
+            df.style().try_bar_colormap("a", &cmap, &None, &None)
+        ));
+        assert!(invalid(df.style().try_background_gradient_shared(
+            &["a", "b"],
+            &cmap,
+            &None,
+            &None
+        )));
+        assert!(invalid(
+            df.style().try_heatmap_symmetric(&cmap, -1.0, 1.0, false)
+        ));
+        let clamped = cmap.with_out_of_range(OutOfRange::Clamp);
+        assert!(df
+            .style()
+            .try_background_colormap("a", &clamped, &Norm::linear())
+            .is_ok());
     }
 
-    let min: f32 = s.min::<f32>().unwrap();
-    s = s - min;
+    #[test]
+    fn test_trend_arrows() {
+        let df = df!(
+            "sales" => &[Some(10), Some(8), Some(5), None],
+            "target" => &[Some(9), Some(9), Some(5), Some(1)]
+        )
+        .unwrap();
+        let html = df.style().trend_arrows("sales", "target").render();
+        assert!(html.contains("10 <span style=\"color: #1a9850\">▲</span></td>"));
+        assert!(html.contains("8 <span style=\"color: #d73027\">▼</span></td>"));
+        assert!(html.contains("5 <span style=\"color: #878787\">▬</span></td>"));
+        assert!(html.contains(">null</td>"));
 
-    let range: f32 = s.max::<f32>().unwrap();
-    s = s / range;
+        let styler = df.style().trend_arrows_expr("sales", col("sales").shift(1));
+        assert!(!styler.suffixes.contains_key(&(0, 0)));
+        assert!(styler.suffixes[&(0, 1)].contains("▼"));
+        assert!(df.style().try_trend_arrows("sales", "goal").is_err());
+    }
 
-    // TODO: return with type fixed to f64 elements
-    s.cast(&DataType::Float64).unwrap()
-}
+    #[test]
+    fn test_details() {
+        let orders = df!("id" => &[1, 2, 3], "total" => &[30, 5, 0]).unwrap();
+        let lines = df!("id" => &[1, 1, 2], "item" => &["a", "b", "c"]).unwrap();
+        let detail = lines.style().highlight(
+            "item",
+            &Condition::Equals("c".to_string()),
+            &Color::new(255, 0, 0),
+        );
+        let html = orders
+            .style()
+            .set_uid("o")
+            .set_details("id", detail, true)
+            .render();
+        assert!(html.contains(
+            "<tr class=\"ps-detail\"><td colspan=\"2\"><details><summary>2 rows</summary><div"
+        ));
+        assert!(html.contains("<table id=\"T_o_d0\""));
+        assert!(html.contains("#T_o_d1_row2_col1 {background-color: #ff0000"));
+        assert!(!html.contains("T_o_d2"));
+        assert!(df!("x" => &[1])
+            .unwrap()
+            .style()
+            .try_set_details("id", lines.style(), false)
+            .is_err());
 
-#[cfg(test)]
-mod test {
-    use super::*;
+        let lines = df!("id" => &[Some(1i64), None, Some(3)], "item" => &["a", "b", "c"]).unwrap();
+        let orders = df!("id" => &[Some(1i32), None, Some(2)]).unwrap();
+        let styler = orders
+            .style()
+            .set_uid("t")
+            .set_details("id", lines.style(), false);
+        let html = styler.render();
+        assert!(html.contains("<table id=\"T_t_d0\""));
+        assert!(html.contains(">a</td>"));
+        assert!(!html.contains("T_t_d1") && !html.contains(">b</td>"));
+        assert!(!html.contains("T_t_d2"));
+    }
 
     #[test]
-    fn test_styler() {
-        let df = DataFrame::new(vec![
-            Series::new("a", &[1, 222, 3]),
-            Series::new("b", &["fooo", "b", "c"]),
-        ])
-        .unwrap();
+    fn test_icon_set() {
+        let df = df!("score" => &[Some(10), Some(50), Some(90), None]).unwrap();
+        let html = df
+            .style()
+            .icon_set(
+                "score",
+                IconSet::ThreeArrows,
+                &Bins::Edges(vec![33.0, 67.0]),
+            )
+            .render();
+        assert!(html.contains("><span style=\"color: #d73027\">▼</span> 10</td>"));
+        assert!(html.contains("><span style=\"color: #f4a000\">▶</span> 50</td>"));
+        assert!(html.contains("><span style=\"color: #1a9850\">▲</span> 90</td>"));
+        assert!(html.contains(">null</td>"));
+        assert!(matches!(
+            df.style()
+                .try_icon_set("score", IconSet::ThreeArrows, &Bins::Count(2)),
+            Err(StylerError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            df.style()
+                .try_icon_set("points", IconSet::ThreeArrows, &Bins::Count(3)),
+            Err(StylerError::UnknownColumn { .. })
+        ));
+    }
 
-        let styler = df.style();
+    #[test]
+    fn test_map_icons() {
+        let df = df!("status" => &["ok", "failed", "unknown"]).unwrap();
+        let icons = HashMap::from([
+            ("ok".to_string(), "✅".to_string()),
+            ("failed".to_string(), "❌".to_string()),
+        ]);
+        let styler = df.style().set_uid("m").map_icons("status", &icons);
+        let reapplied = df.style().set_uid("m").apply_spec(&styler.spec());
         let html = styler.render();
-        println!("{}", html);
-        assert!(html.contains("<style>"));
-        assert!(html.contains("<div>"));
-        assert!(html.contains("</div>"));
-        assert!(html.find("fooo").unwrap() < html.find("222").unwrap());
+        assert!(html.contains("<td id=\"T_m_row0_col0\" title=\"ok\">✅</td>"));
+        assert!(html.contains("<td id=\"T_m_row1_col0\" title=\"failed\">❌</td>"));
+        assert!(html.contains("<td id=\"T_m_row2_col0\">unknown</td>"));
+        assert_eq!(reapplied.render(), html);
     }
 
     #[test]
-    fn test_precision() {
-        let x = 1.123456789;
-        let df = DataFrame::new(vec![Series::new("a", &[x, 2.123456789, 3.123456789])]).unwrap();
+    fn test_emphasize_when() {
+        let df = df!("latency" => &[120, 800, 450], "host" => &["a", "b", "c"]).unwrap();
+        let styler = df
+            .style()
+            .emphasize_when("latency", col("latency").gt(lit(400)), TextStyle::Bold)
+            .emphasize_when("host", col("latency").gt(lit(500)), TextStyle::Underline);
+        assert!(styler.applied_styles[0][0].is_empty());
+        assert_eq!(styler.applied_styles[0][1]["font-weight"], "bold");
+        assert_eq!(styler.applied_styles[0][2]["font-weight"], "bold");
+        assert_eq!(styler.applied_styles[1][1]["text-decoration"], "underline");
+        assert!(styler.applied_styles[1][2].is_empty());
+        assert!(matches!(
+            df.style()
+                .try_emphasize_when("host", col("latency"), TextStyle::Italic),
+            Err(StylerError::Polars(_))
+        ));
+    }
 
-        let styler = df.style().set_precision(2);
-        let html = styler.render();
-        println!("{}", html);
-        assert!(html.contains(format!("{:.2}", x).as_str()));
-        assert!(!html.contains(format!("{:.3}", x).as_str()));
+    #[test]
+    fn test_set_text() {
+        let df = df!("a" => &[1, 2], "b" => &[3, 4], "c" => &[5, 6]).unwrap();
+        let mono = TextProps::new().family("monospace");
+        let red = TextProps::new().bold().color(&Color::new(255, 0, 0));
+        let styler =
+            df.style()
+                .set_text(&["a", "b"], &mono)
+                .set_text_when("c", col("c").gt(lit(5)), &red);
+        assert_eq!(styler.applied_styles[1][0]["font-family"], "monospace");
+        assert!(styler.applied_styles[2][0].is_empty());
+        assert_eq!(styler.applied_styles[2][1]["color"], "#ff0000");
+        let spec = styler.spec();
+        assert_eq!(spec.operations.len(), 1);
+        let reapplied = df.style().apply_spec(&spec);
+        assert_eq!(reapplied.applied_styles[..2], styler.applied_styles[..2]);
     }
 
     #[test]
-    fn test_set_labels_all() {
-        let df = DataFrame::new(vec![Series::new("a", &[0])]).unwrap();
-        let styler = df.style().set_labels(vec!["Foo".to_string()]);
+    fn test_border_when() {
+        let df =
+            df!("check" => &["a", "b", "c", "d"], "ok" => &[true, false, false, true]).unwrap();
+        let border = Border::new(2, &Color::new(255, 0, 0));
+        let styler = df.style().border_rows_when(col("ok").not(), &border);
+        let sides = |c: usize, r: usize| {
+            let mut sides = styler.applied_styles[c][r]
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>();
+            sides.sort();
+            sides
+        };
+        assert!(sides(0, 0).is_empty());
+        assert_eq!(sides(0, 1), ["border-left", "border-top"]);
+        assert_eq!(sides(1, 1), ["border-right", "border-top"]);
+        assert_eq!(sides(0, 2), ["border-bottom", "border-left"]);
+        assert_eq!(
+            styler.applied_styles[1][2]["border-right"],
+            "2px solid #ff0000"
+        );
+        assert!(df
+            .style()
+            .try_border_when(&["x"], col("ok"), &border)
+            .is_err());
+    }
+
+    #[test]
+    fn test_column_separator() {
+        let df = df!("region" => &["x", "y"], "sales" => &[1, 2], "cost" => &[3, 4]).unwrap();
+        let styler = df
+            .style()
+            .set_uid("cs")
+            .add_column_separator("region", &Border::new(2, &Color::new(0, 0, 0)));
+        let reapplied = df.style().set_uid("cs").apply_spec(&styler.spec());
         let html = styler.render();
-        assert!(html.contains("Foo"));
+        assert!(html.contains(
+            "#T_cs > thead > tr > :nth-child(1), \
+             #T_cs > tbody > tr:not(.ellipsis):not(.ps-detail) > :nth-child(1) \
+             {border-right: 2px solid #000000}"
+        ));
+        assert_eq!(reapplied.render(), html);
+        assert!(df
+            .style()
+            .try_add_column_separator("profit", &Border::new(1, &Color::new(0, 0, 0)))
+            .is_err());
     }
 
     #[test]
-    fn test_set_labels_map() {
-        let df = DataFrame::new(vec![Series::new("a", &[0])]).unwrap();
-        let mapping = HashMap::from([("a".to_string(), "Foo".to_string())]);
-        let styler = df.style().relabel(&mapping);
+    fn test_row_numbers() {
+        let df = df!("a" => &[1, 2, 3, 4, 5]).unwrap();
+        let html = df
+            .style()
+            .set_uid("rn")
+            .show_row_numbers(1)
+            .set_max_rows(2)
+            .background_gradient("a", &Color::new(255, 0, 0), &None, &None)
+            .render();
+        assert!(
+            html.contains("<thead><tr><th class=\"ps-row-number\"></th><th scope=\"col\">a</th>")
+        );
+        assert!(
+            html.contains("<tr id=\"T_rn_row4\"><th class=\"ps-row-number\" scope=\"row\">5</th>")
+        );
+        assert!(html.contains("<td colspan=\"2\">… 3 more rows …</td>"));
+    }
+
+    #[test]
+    fn test_highlight_rows() {
+        let df = df!("id" => &["A1", "B2", "C3"], "v" => &[1, 2, 3]).unwrap();
+        let yellow = Color::new(255, 255, 0);
+        let styler = df
+            .style()
+            .highlight_rows_at(&[0], &yellow)
+            .highlight_rows_where_key("id", &["C3", "Z9"], &yellow);
+        let highlighted = |row: usize| {
+            styler
+                .applied_styles
+                .iter()
+                .all(|column| column[row].get("background-color") == Some(&yellow.to_hex()))
+        };
+        assert!(highlighted(0) && highlighted(2));
+        assert!(styler.applied_styles[1][1].is_empty());
+        assert_eq!(
+            df.style().try_highlight_rows_at(&[3], &yellow).err(),
+            Some(StylerError::RowOutOfBounds { row: 3, height: 3 })
+        );
+    }
+
+    #[test]
+    fn test_percent_of_total() {
+        let df =
+            df!("region" => &["a", "b", "c"], "sales" => &[Some(1234), Some(8766), None]).unwrap();
+        let styler = df
+            .style()
+            .set_uid("pt")
+            .format_percent_of_total("sales", true)
+            .set_column_format("sales", CellFormat::new().thousands(","));
+        let reapplied = df.style().set_uid("pt").apply_spec(&styler.spec());
         let html = styler.render();
-        assert!(html.contains("Foo"));
+        assert!(html.contains(">1,234 (12.3%)</td>"));
+        assert!(html.contains(">8,766 (87.7%)</td>"));
+        assert!(html.contains(">null</td>"));
+        assert_eq!(reapplied.render(), html);
+        let html = df
+            .style()
+            .format_percent_of_total("sales", false)
+            .set_max_rows(1)
+            .render();
+        assert!(html.contains(">12.3%</td>"));
     }
 
     #[test]
-    fn test_apply() {
-        let df = DataFrame::new(vec![
-            Series::new("a", &[1, 222, 3]),
-            Series::new("b", &["fooo", "b", "c"]),
-        ])
+    fn test_computed_column() {
+        let df = df!("price" => &[10, 12], "cost" => &[7, 12]).unwrap();
+        let styler = df
+            .style()
+            .trend_arrows("price", "cost")
+            .add_computed_column("margin", col("price") - col("cost"), Some(0))
+            .highlight(
+                "margin",
+                &Condition::Equals("0".to_string()),
+                &Color::new(255, 0, 0),
+            );
+        assert_eq!(
+            styler.data().get_column_names(),
+            ["margin", "price", "cost"]
+        );
+        assert_eq!(df.width(), 2);
+        assert!(styler.suffixes[&(1, 0)].contains("▲"));
+        assert!(!styler.applied_styles[0][1].is_empty());
+        assert!(styler.clone().render().contains(">3</td>"));
+
+        let spec = StyleSpec::parse(&styler.spec().to_json()).unwrap();
+        assert_eq!(spec, styler.spec());
+        let replayed = df.style().apply_spec(&spec);
+        assert_eq!(
+            replayed.data().get_column_names(),
+            ["margin", "price", "cost"]
+        );
+        assert_eq!(replayed.applied_styles[0], styler.applied_styles[0]);
+        let head = styler.set_normalize_on_selection(true).render_head(1);
+        assert!(head.contains(">3</td>"));
+        assert!(matches!(
+            df.style()
+                .try_add_computed_column("cost", col("price") * lit(2), None),
+            Err(StylerError::DuplicateColumn(_))
+        ));
+    }
+
+    #[test]
+    fn test_competition_ranks() {
+        let values = [Some(2.0), None, Some(5.0), Some(2.0), Some(1.0), None];
+        assert_eq!(
+            competition_ranks(&values, false),
+            vec![Some(2), None, Some(4), Some(2), Some(1), None]
+        );
+        assert_eq!(
+            competition_ranks(&values, true),
+            vec![Some(2), None, Some(1), Some(2), Some(4), None]
+        );
+    }
+
+    #[test]
+    fn test_rank_column() {
+        let df =
+            df!("team" => &["a", "b", "c", "d"], "points" => &[Some(7), Some(9), None, Some(9)])
+                .unwrap();
+        let styler = df.style().add_rank_column("points", true, true);
+        assert_eq!(
+            styler.data().get_column_names(),
+            ["team", "points", "points rank"]
+        );
+        let ranks = styler.data().column("points rank").unwrap();
+        assert_eq!(
+            ranks.u32().unwrap().into_iter().collect::<Vec<_>>(),
+            [Some(3), Some(1), None, Some(1)]
+        );
+        assert_eq!(styler.applied_styles[2][1]["background-color"], "#ffd700");
+        assert_eq!(styler.applied_styles[2][0]["background-color"], "#cd7f32");
+        assert!(styler.applied_styles[2][2].is_empty());
+        assert!(styler.clone().render().contains(">Rank</th>"));
+
+        let replayed = df.style().apply_spec(&styler.spec());
+        assert_eq!(replayed.applied_styles, styler.applied_styles);
+        let head = styler.set_normalize_on_selection(true).render_head(2);
+        assert!(head.contains("#ffd700"));
+        assert!(head.contains("#cd7f32"));
+        assert!(df
+            .style()
+            .try_add_rank_column("team", false, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_rules_legend() {
+        let df = df!("price" => &[5.0, 120.0], "target" => &[10.0, 100.0]).unwrap();
+        let styler = df
+            .style()
+            .set_uid("rl")
+            .relabel_column("price", "Price")
+            .highlight("price", &Condition::Gt(100.0), &Color::new(255, 0, 0))
+            .trend_arrows("price", "target")
+            .icon_set(
+                "target",
+                IconSet::ThreeArrows,
+                &Bins::Edges(vec![20.0, 80.0]),
+            );
+        assert!(!styler.clone().render().contains("ps-rules"));
+        let html = styler.with_rules_legend().render();
+        assert!(html.contains(
+            "<div class=\"ps-rule\"><span class=\"ps-rule-swatch\" \
+             style=\"background-color: #ff0000\"></span>Price &gt; 100</div>"
+        ));
+        assert!(html.contains(">▼</span>Price below target</div>"));
+        assert!(html.contains(">▲</span>target ≥ 80</div>"));
+        assert!(html.contains("#T_rl_rules .ps-rule-swatch {"));
+        assert!(!html.contains("ps-legend-bar"));
+    }
+
+    #[test]
+    fn test_config() {
+        let df = df!(
+            "name" => &[Some("a"), None],
+            "sales" => &[Some(1234.5), None],
+            "units" => &[Some(1200), Some(3)]
+        )
         .unwrap();
+        let theme = StyleSpec {
+            table_classes: Some(vec!["report".to_string()]),
+            ..StyleSpec::default()
+        };
+        let config = StylerConfig::new()
+            .precision(2)
+            .na_rep("-")
+            .thousands(",")
+            .theme(theme)
+            .text_align("right");
+        let html = df.style().set_uid("cf").with_config(&config).render();
+        assert!(html.contains(">1,234.50</td>"));
+        assert!(html.contains(">1,200.00</td>"));
+        assert_eq!(html.matches(">-</td>").count(), 2);
+        assert!(html.contains("class=\"report\""));
+        assert!(html.contains("#T_cf td {text-align: right}"));
 
-        let styler = df.style().apply("a", |s| {
-            s.iter()
-                .map(|v| {
-                    if v == AnyValue::Int32(222) {
-                        vec![("background-color".to_string(), "red".to_string())]
-                    } else {
-                        vec![]
-                    }
-                })
-                .map(|v| v.into_iter().collect::<HashMap<_, _>>())
-                .collect::<Vec<_>>()
-        });
-        assert!(styler
-            .applied_styles
-            .iter()
-            .any(|v| { v.iter().any(|hm| !hm.is_empty()) }));
+        let html = df
+            .style()
+            .with_config(&config)
+            .set_precision(1)
+            .set_column_format("units", CellFormat::new().precision(0).thousands(" "))
+            .render();
+        assert!(html.contains(">1,234.5</td>"));
+        assert!(html.contains(">1 200</td>"));
     }
 
     #[test]
-    fn test_background_gradient() {
-        let df = DataFrame::new(vec![
-            Series::new("a", &[1, 222, 3]),
-            Series::new("b", &["fooo", "b", "c"]),
-        ])
+    fn test_dtype_formats() {
+        let df = df!(
+            "x" => &[1.0, 2.5],
+            "ok" => &[true, false],
+            "n" => &[1, 2]
+        )
         .unwrap();
+        let styler = df
+            .style()
+            .set_uid("df")
+            .with_recommended_dtype_formats()
+            .set_dtype_format(DtypeKind::Integer, CellFormat::new().suffix(" pcs"));
+        let reapplied = df.style().set_uid("df").apply_spec(&styler.spec());
+        let html = styler.clone().render();
+        assert!(html.contains(">2.50</td>"));
+        assert!(html.contains(">✓</td>"));
+        assert!(html.contains(">✗</td>"));
+        assert!(html.contains(">1 pcs</td>"));
+        assert_eq!(reapplied.render(), html);
+        let html = styler
+            .set_precision(1)
+            .set_column_format("ok", CellFormat::new().booleans("yes", "no"))
+            .render();
+        assert!(html.contains(">2.5</td>"));
+        assert!(html.contains(">yes</td>"));
+    }
 
+    #[test]
+    fn test_column_meta() {
+        let df =
+            df!("item" => &["a", "b"], "weight" => &[Some(1.5), None], "price" => &[3, 4]).unwrap();
         let styler = df
             .style()
-            .background_gradient_expr(col("a").log(2.0), &Color::new(0, 0, 0));
+            .set_uid("cm")
+            .set_column_meta(
+                "weight",
+                ColumnMeta::new()
+                    .unit("kg")
+                    .description("Net \"dry\" weight"),
+            )
+            .set_column_meta("price", ColumnMeta::new().unit("USD").unit_in_values());
+        let reapplied = df.style().set_uid("cm").apply_spec(&styler.spec());
+        let html = styler.clone().render();
+        assert!(html.contains("<th scope=\"col\" title=\"Net &quot;dry&quot; weight\">weight</th>"));
+        assert!(html.contains("<tr class=\"ps-units\"><th></th><th>kg</th><th></th></tr>"));
+        assert!(html.contains(">3 USD</td>"));
+        assert!(html.contains(">null</td>"));
+        assert_eq!(reapplied.render(), html);
         assert!(styler
-            .applied_styles
-            .iter()
-            .any(|v| { v.iter().any(|hm| !hm.is_empty()) }));
+            .render_markdown()
+            .starts_with("| item | weight (kg) | price |"));
+        assert!(df
+            .style()
+            .try_set_column_meta("wieght", ColumnMeta::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_summary_footer() {
+        let df = df!("region" => &["a", "b", "c"], "sales" => &[1.5, 2.0, 4.0]).unwrap();
+        let styler = df
+            .style()
+            .set_uid("sf")
+            .set_precision(1)
+            .add_summary_row("Total", Aggregation::Sum, &["sales"])
+            .add_summary_row("Count", Aggregation::Count, &["region"])
+            .set_max_rows(2)
+            .sticky_footer();
+        let reapplied = df.style().set_uid("sf").apply_spec(&styler.spec());
+        let html = styler.render();
+        assert!(html.contains(
+            "</tbody><tfoot><tr class=\"ps-summary\"><td>Total</td><td>7.5</td></tr>\
+             <tr class=\"ps-summary\"><td>3</td><td></td></tr></tfoot></table>"
+        ));
+        assert!(
+            html.contains("#T_sf > tfoot {position: sticky; bottom: 0; background-color: white}")
+        );
+        assert_eq!(reapplied.render(), html);
+        assert!(df
+            .style()
+            .try_add_summary_row("Total", Aggregation::Mean, &["region"])
+            .is_err());
     }
 
     #[test]
-    fn test_normalize_series_float() {
-        let s = Series::new("a", &[-1.0, 2.0, 3.0]);
-        let s = normalize_series(&s, &None, &None);
-        assert_eq!(s, Series::new("a", &[0.0, 0.75, 1.0]));
+    fn test_clip_column() {
+        let df = df!("id" => &[1, 2], "message" => &["short", "a \"very\" long message"]).unwrap();
+        let styler = df.style().set_uid("cl").clip_column("message", "10em");
+        let reapplied = df.style().set_uid("cl").apply_spec(&styler.spec());
+        let html = styler.render();
+        assert!(html.contains(
+            "#T_cl > tbody > tr:not(.ellipsis):not(.ps-detail) > :nth-child(2) \
+             {max-width: 10em; overflow: hidden; text-overflow: ellipsis; white-space: nowrap}"
+        ));
+        assert!(
+            html.contains("title=\"a &quot;very&quot; long message\">a \"very\" long message</td>")
+        );
+        assert!(!html.contains("title=\"1\""));
+        assert_eq!(reapplied.render(), html);
+    }
+
+    #[test]
+    fn test_expandable_text() {
+        let df = df!("id" => &[1, 2], "log" => &["ok", "connection řeset by peer"]).unwrap();
+        let styler = df.style().set_uid("ex").expandable_text("log", 12);
+        let reapplied = df.style().set_uid("ex").apply_spec(&styler.spec());
+        let html = styler.render();
+        assert!(html.contains(
+            ">connection ř<details class=\"ps-more\"><summary>… more</summary>eset by peer</details></td>"
+        ));
+        assert!(html.contains(">ok</td>"));
+        assert!(html.contains("#T_ex .ps-more[open] > summary {display: none}"));
+        assert_eq!(reapplied.render(), html);
+        assert!(!df.style().render().contains("ps-more"));
+    }
+
+    #[test]
+    fn test_render_deterministic() {
+        let df = df!("a" => &[1, 2, 3], "b" => &["x", "y", "z"]).unwrap();
+        let render = || {
+            df.style()
+                .background_gradient("a", &Color::new(255, 0, 0), &None, &None)
+                .highlight(
+                    "b",
+                    &Condition::Equals("y".to_string()),
+                    &Color::new(0, 0, 255),
+                )
+                .render_deterministic()
+        };
+        let html = render();
+        assert_eq!(html, render());
+        assert!(html.contains("<table id=\"T_snapshot\""));
+        assert!(!html.contains("\n "));
+        assert!(df
+            .style()
+            .set_uid("own")
+            .render_deterministic()
+            .contains("id=\"T_own\""));
+    }
+
+    #[test]
+    fn test_render_hydratable() {
+        let df = df!("a" => &[1, 2, 3]).unwrap();
+        let html = df.style().render_hydratable();
+        assert_eq!(html, df.style().render_hydratable());
+        assert_ne!(
+            html,
+            df!("a" => &[1, 2, 4]).unwrap().style().render_hydratable()
+        );
+        assert!(df
+            .style()
+            .set_uid("own")
+            .render_hydratable()
+            .contains("<table id=\"T_own\""));
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let styler = DataFrame::empty().style();
+        let no_columns = |r: Result<String, StylerError>| matches!(r, Err(StylerError::NoColumns));
+        assert!(no_columns(styler.clone().try_render()));
+        assert!(no_columns(styler.clone().try_render_web_component()));
+        assert!(no_columns(styler.clone().try_render_rows_only()));
+        assert!(no_columns(styler.clone().try_render_body()));
+        assert!(no_columns(styler.clone().try_render_markdown()));
+        assert!(no_columns(styler.clone().try_render_latex()));
+        assert!(no_columns(styler.clone().try_render_css()));
+        assert!(no_columns(styler.clone().try_render_head(5)));
+        assert!(no_columns(styler.clone().try_render_tail(5)));
+        assert!(no_columns(styler.clone().try_render_sample(5, 0)));
+        assert!(matches!(
+            styler.try_to_context(),
+            Err(StylerError::NoColumns)
+        ));
+    }
+
+    #[test]
+    fn test_render_changed_rows() {
+        let before = df!("id" => &[1, 2, 3], "v" => &[10, 20, 30]).unwrap();
+        let after = df!("id" => &[1, 2], "v" => &[10, 25]).unwrap();
+        let style = |df: &DataFrame| {
+            df.style()
+                .highlight("v", &Condition::Gt(15.0), &Color::new(255, 0, 0))
+        };
+        let updates = style(&after).render_changed_rows(style(&before).set_uid("cr"));
+        assert_eq!(updates.rows.len(), 1);
+        assert!(updates.rows[0].starts_with("<tr id=\"T_cr_row1\">"));
+        assert!(updates.rows[0].contains(">25</td>"));
+        assert_eq!(updates.removed, ["T_cr_row2"]);
+        assert!(updates
+            .css
+            .contains("#T_cr_row1_col1 {background-color: #ff0000}"));
+    }
+
+    #[test]
+    fn test_selection_cache() {
+        let df = df!("a" => &[1.0, 2.0, 3.0, 10.0]).unwrap();
+        let styler = df
+            .style()
+            .set_normalize_on_selection(true)
+            .background_gradient("a", &Color::new(255, 0, 0), &None, &None);
+        assert!(styler.clone().render_head(2).contains("rgba(255, 0, 0, 1)"));
+        // Marks the cached selection to tell hits from recomputations.
+        let mark = |styler: &Styler| {
+            let mut cache = styler.selections.lock().unwrap();
+            let (_, selection) = cache.as_mut().unwrap();
+            *selection = selection.clone().set_caption("cached");
+        };
+        mark(&styler);
+        assert!(styler.clone().render_head(2).contains("cached"));
+        assert!(!styler.clone().render_head(3).contains("cached"));
+        mark(&styler);
+        assert!(!styler
+            .clone()
+            .set_precision(1)
+            .render_head(3)
+            .contains("cached"));
+        mark(&styler);
+        let changed = styler
+            .clone()
+            .bar("a", &Color::new(0, 0, 255), &None, &None);
+        assert!(!changed.render_head(3).contains("cached"));
+        mark(&styler);
+        let diverged = styler
+            .clone()
+            .bar("a", &Color::new(0, 255, 0), &None, &None);
+        assert!(!diverged.clone().render_head(3).contains("cached"));
+        assert!(diverged.render_head(3).contains("#00ff00"));
     }
 
     #[test]
-    fn test_normalize_series_int() {
-        let s = Series::new("a", &[-1, 2, 3]);
-        let s = normalize_series(&s, &None, &None);
-        assert_eq!(s, Series::new("a", &[0.0, 0.75, 1.0]));
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Styler>();
+        assert_send_sync::<StylerParams>();
+        assert_send_sync::<StyleSpec>();
+        assert_send_sync::<Renderer>();
+        assert_send_sync::<StylerError>();
+
+        let df = df!("a" => &[1.0, 2.0, 3.0]).unwrap();
+        let styler = df
+            .style()
+            .set_uid("ss")
+            .set_column_formatter("a", |v| format!("<{}>", v))
+            .background_gradient("a", &Color::new(255, 0, 0), &None, &None);
+        let expected = styler.clone().render();
+        std::thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| scope.spawn(|| styler.clone().render()))
+                .collect::<Vec<_>>();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap(), expected);
+            }
+        });
     }
 }
@@ -0,0 +1,117 @@
+use crate::colors::Color;
+use crate::styler::TextStyle;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Typed text properties of cells: weight, style, decoration, color and font family.
+///
+/// Only the properties that are set are written, so props applied later override
+/// earlier ones property by property.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct TextProps {
+    /// `font-weight`, e.g. `bold` or `600`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<String>,
+    /// `font-style`, e.g. `italic`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// `text-decoration`, e.g. `underline` or `line-through`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    /// `font-family`, e.g. `monospace` or `"Fira Code", monospace`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+}
+
+impl TextProps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight("bold")
+    }
+
+    pub fn weight(mut self, weight: &str) -> Self {
+        self.weight = Some(weight.to_string());
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.style = Some("italic".to_string());
+        self
+    }
+
+    pub fn underline(self) -> Self {
+        self.decoration("underline")
+    }
+
+    pub fn strikethrough(self) -> Self {
+        self.decoration("line-through")
+    }
+
+    pub fn decoration(mut self, decoration: &str) -> Self {
+        self.decoration = Some(decoration.to_string());
+        self
+    }
+
+    pub fn color(mut self, color: &Color) -> Self {
+        self.color = Some(color.clone());
+        self
+    }
+
+    pub fn family(mut self, family: &str) -> Self {
+        self.family = Some(family.to_string());
+        self
+    }
+
+    /// CSS declarations of the properties that are set.
+    pub fn css(&self) -> HashMap<String, String> {
+        [
+            ("font-weight", self.weight.clone()),
+            ("font-style", self.style.clone()),
+            ("text-decoration", self.decoration.clone()),
+            ("color", self.color.as_ref().map(|c| c.to_hex())),
+            ("font-family", self.family.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(property, value)| Some((property.to_string(), value?)))
+        .collect()
+    }
+}
+
+impl From<TextStyle> for TextProps {
+    fn from(style: TextStyle) -> Self {
+        match style {
+            TextStyle::Bold => TextProps::new().bold(),
+            TextStyle::Italic => TextProps::new().italic(),
+            TextStyle::Underline => TextProps::new().underline(),
+            TextStyle::Strikethrough => TextProps::new().strikethrough(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_css() {
+        let props = TextProps::new()
+            .bold()
+            .color(&Color::new(255, 0, 0))
+            .family("monospace");
+        assert_eq!(
+            props.css(),
+            HashMap::from([
+                ("font-weight".to_string(), "bold".to_string()),
+                ("color".to_string(), "#ff0000".to_string()),
+                ("font-family".to_string(), "monospace".to_string()),
+            ])
+        );
+        assert_eq!(TextProps::from(TextStyle::Underline).css().len(), 1);
+    }
+}
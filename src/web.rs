@@ -0,0 +1,40 @@
+use crate::styler::Styler;
+
+/// Styled table returned from a web handler; with the `axum` or `actix` feature it is
+/// rendered when the response is built and sent as `text/html`. A table that cannot be
+/// rendered results in a 500 response. With the `yew` feature it can be embedded in `html!`.
+pub struct StyledTable(pub Styler);
+
+impl From<Styler> for StyledTable {
+    fn from(styler: Styler) -> Self {
+        Self(styler)
+    }
+}
+
+/// HTML of a rendered table. Template engines embed it as is, without escaping: with the
+/// `maud` feature it implements `maud::Render` and with the `askama` feature it is marked
+/// as HTML safe, so `{{ table }}` needs no `|safe` filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreRendered(String);
+
+impl PreRendered {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl From<Styler> for PreRendered {
+    fn from(styler: Styler) -> Self {
+        Self(styler.render())
+    }
+}
+
+impl std::fmt::Display for PreRendered {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,38 @@
+pub use crate::web::StyledTable;
+use ::yew::html::ToHtml;
+use ::yew::Html;
+
+/// Embeds the table in `html!` as raw markup rendered by `Styler::render_hydratable`, so
+/// that server-side rendered tables hydrate without mismatches.
+impl ToHtml for StyledTable {
+    fn to_html(&self) -> Html {
+        self.0.clone().into_html()
+    }
+}
+
+impl ToHtml for crate::styler::Styler {
+    fn to_html(&self) -> Html {
+        self.clone().into_html()
+    }
+
+    fn into_html(self) -> Html {
+        Html::from_html_unchecked(self.render_hydratable().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::styler::StylerExt;
+    use ::yew::virtual_dom::VNode;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_to_html() {
+        let df = df!("a" => &[1, 2, 3]).unwrap();
+        let VNode::VRaw(raw) = StyledTable::from(df.style()).to_html() else {
+            panic!("expected raw html");
+        };
+        assert_eq!(raw.html.as_str(), df.style().render_hydratable());
+    }
+}